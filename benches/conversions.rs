@@ -1,6 +1,7 @@
-use dasp_sample::{Duplex, Sample as DaspSample};
+use dasp_sample::{Duplex, FromSample, Sample as DaspSample};
 use divan::Bencher;
-use rodio::{conversions::SampleTypeConverter, Sample};
+use rodio::conversions::{convert_samples, SampleTypeConverter};
+use rodio::Sample;
 
 mod shared;
 
@@ -21,3 +22,29 @@ fn from_sample<S: Duplex<Sample>>(bencher: Bencher) {
             SampleTypeConverter::<_, Sample>::new(source).for_each(divan::black_box_drop)
         })
 }
+
+fn samples_and_output<S: Default + Clone>() -> (Vec<Sample>, Vec<S>) {
+    let samples: Vec<_> = shared::music_wav().collect();
+    let out = vec![S::default(); samples.len()];
+    (samples, out)
+}
+
+/// Sample by sample, as a baseline for `slice_to_device`.
+#[divan::bench(types = [i16, f32])]
+fn slice_to_device_scalar<S: FromSample<Sample> + Default + Clone>(bencher: Bencher) {
+    bencher
+        .with_inputs(samples_and_output::<S>)
+        .bench_local_refs(|(samples, out)| {
+            for (out, sample) in out.iter_mut().zip(samples.iter()) {
+                *out = S::from_sample_(*sample);
+            }
+        })
+}
+
+/// Run with and without the `simd` feature to compare.
+#[divan::bench(types = [i16, f32])]
+fn slice_to_device<S: FromSample<Sample> + Default + Clone + 'static>(bencher: Bencher) {
+    bencher
+        .with_inputs(samples_and_output::<S>)
+        .bench_local_refs(|(samples, out)| convert_samples(samples, out))
+}
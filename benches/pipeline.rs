@@ -2,6 +2,7 @@ use std::num::NonZero;
 use std::time::Duration;
 
 use divan::Bencher;
+use rodio::buffer::SamplesBuffer;
 use rodio::ChannelCount;
 use rodio::{source::UniformSourceIterator, Source};
 
@@ -46,3 +47,40 @@ fn short(bencher: Bencher) {
             .for_each(divan::black_box_drop)
     })
 }
+
+/// A mixer playing a few sounds, each through a chain of effects.
+fn mixed_chains() -> impl Source {
+    let source = music_wav();
+    let (channels, sample_rate) = (source.channels(), source.sample_rate());
+    let samples: Vec<_> = source.collect();
+
+    let (mixer, output) = rodio::mixer::mixer(channels, sample_rate);
+    for gain in [0.1, 0.2, 0.3, 0.4] {
+        let sound = SamplesBuffer::new(channels, sample_rate, samples.clone())
+            .amplify(gain)
+            .amplify(1.2)
+            .amplify_decibel(-1.0)
+            .amplify_normalized(0.8);
+        mixer.add(sound);
+    }
+    output
+}
+
+#[divan::bench]
+fn mixer_next(bencher: Bencher) {
+    bencher
+        .with_inputs(mixed_chains)
+        .bench_values(|source| source.for_each(divan::black_box_drop))
+}
+
+#[divan::bench]
+fn mixer_fill_buffer(bencher: Bencher) {
+    bencher
+        .with_inputs(mixed_chains)
+        .bench_values(|mut source| {
+            let mut out = [0.0; 512];
+            while source.fill_buffer(&mut out) == out.len() {
+                divan::black_box(&out);
+            }
+        })
+}
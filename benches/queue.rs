@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use divan::Bencher;
+use rodio::buffer::SamplesBuffer;
+use rodio::{nz, queue};
+
+fn main() {
+    divan::main();
+}
+
+fn short_sound() -> SamplesBuffer {
+    SamplesBuffer::new(nz!(1), nz!(48000), vec![0.0])
+}
+
+/// Appends a sound of a single sample and plays it, so each pull goes to the next sound.
+#[divan::bench(sample_count = 10_000)]
+fn append_and_go_next(bencher: Bencher) {
+    let (tx, mut rx) = queue::queue(true);
+    bencher.bench_local(|| {
+        tx.append(short_sound());
+        rx.next()
+    });
+}
+
+/// Pulls sounds of a single sample while another thread keeps the queue full. Compare the
+/// slowest samples to see how long the output waits on the appending thread.
+#[divan::bench(sample_count = 10_000)]
+fn go_next_while_appending(bencher: Bencher) {
+    let (tx, mut rx) = queue::queue(true);
+    tx.set_capacity(Some(1024));
+    let stop = Arc::new(AtomicBool::new(false));
+    let producer = {
+        let stop = stop.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let _ = tx.try_append(short_sound());
+            }
+        })
+    };
+
+    bencher.bench_local(|| rx.next());
+
+    stop.store(true, Ordering::Relaxed);
+    producer.join().unwrap();
+}
+
+/// Like `append_and_go_next` with a track change callback set, which the output calls on
+/// every sound.
+#[divan::bench(sample_count = 10_000)]
+fn go_next_with_track_change_callback(bencher: Bencher) {
+    let (tx, mut rx) = queue::queue(true);
+    tx.set_on_track_change(|index| {
+        divan::black_box(index);
+    });
+    bencher.bench_local(|| {
+        tx.append(short_sound());
+        rx.next()
+    });
+}
+
+/// Pulls from the output right after clearing a sound, so the output has to skip it. The
+/// cleared sound is handed back to the input instead of being dropped while pulling.
+#[divan::bench(sample_count = 10_000)]
+fn skip_cleared_sound(bencher: Bencher) {
+    let (tx, mut rx) = queue::queue(true);
+    bencher.bench_local(|| {
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![0.0; 4096]));
+        tx.clear();
+        rx.next()
+    });
+}
@@ -0,0 +1,42 @@
+use rodio::source::{SineWave, Source};
+use rodio::{nz, Player};
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+const BLOCK_DURATION: Duration = Duration::from_millis(10);
+
+/// Plays sounds through a custom output instead of an audio device.
+///
+/// Players and sources are added to the mixer as usual, while the output pulls blocks of
+/// samples from the `MixerSource`. A real output would hand these blocks to an `AudioWorklet`,
+/// a network connection or a plugin host. This example does not use any audio devices.
+fn main() -> Result<(), Box<dyn Error>> {
+    let (channels, sample_rate) = (nz!(2), nz!(48_000));
+    let (mixer, mut output) = rodio::mixer(channels, sample_rate);
+
+    let player = Player::connect_new(&mixer);
+    player.append(SineWave::new(440.0).take_duration(Duration::from_millis(200)));
+    player.append(
+        SineWave::new(660.0)
+            .amplify(0.5)
+            .take_duration(Duration::from_millis(200)),
+    );
+
+    let frames = (sample_rate.get() as u128 * BLOCK_DURATION.as_millis() / 1000) as usize;
+    let mut block = vec![0.0; frames * channels.get() as usize];
+    while !player.empty() {
+        let filled = output.fill_buffer(&mut block);
+        // The mixer may end when no sound is added to it, send silence then.
+        block[filled..].fill(0.0);
+        let peak = block[..filled]
+            .iter()
+            .fold(0.0, |peak: rodio::Float, sample| peak.max(sample.abs()));
+        println!("Sent {filled} samples, peak level {peak:.2}");
+
+        // Pull at the rate the output consumes audio.
+        thread::sleep(BLOCK_DURATION);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Plays one mix on two outputs at the same time.
+//!
+//! This uses null outputs so it runs without audio hardware. To play on real devices open them
+//! with `DeviceSinkBuilder::from_device` instead. Devices do not share a clock, so the outputs
+//! slowly drift apart, this is best effort.
+
+use rodio::source::{SineWave, Source};
+use rodio::{mixer, nz, MixerDeviceSink, Player};
+use std::error::Error;
+use std::time::Duration;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let first_device = MixerDeviceSink::null(nz!(2), nz!(44_100));
+    let second_device = MixerDeviceSink::null(nz!(2), nz!(44_100));
+
+    // Everything added to this mixer plays on both devices.
+    let (controller, mix) = mixer::mixer(nz!(2), nz!(44_100));
+    let first_output = mix.tee();
+    let second_output = first_output.clone();
+    first_device.mixer().add(first_output);
+    second_device.mixer().add(second_output);
+
+    let player = Player::connect_new(&controller);
+    player.append(
+        SineWave::new(440.0)
+            .take_duration(Duration::from_secs(1))
+            .amplify(0.2),
+    );
+    player.sleep_until_end();
+
+    println!(
+        "First device played {} frames, second device played {} frames",
+        first_device.frames_played(),
+        second_device.frames_played()
+    );
+
+    Ok(())
+}
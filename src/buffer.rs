@@ -59,6 +59,19 @@ impl SamplesBuffer {
         }
     }
 
+    /// Builds a new `SamplesBuffer` from a copy of `data`.
+    ///
+    /// A [`Player`](crate::Player) only plays sources that own their samples, so borrowed samples
+    /// have to be copied. Samples in a `static` can be played without a copy using a
+    /// [`StaticSamplesBuffer`](crate::static_buffer::StaticSamplesBuffer).
+    ///
+    /// # Panics
+    ///
+    /// See [`new`](SamplesBuffer::new).
+    pub fn from_slice(channels: ChannelCount, sample_rate: SampleRate, data: &[Sample]) -> Self {
+        Self::new(channels, sample_rate, data)
+    }
+
     pub(crate) fn record_source(source: impl Source) -> Self {
         let channel_count = source.channels();
         let sample_rate = source.sample_rate();
@@ -71,6 +84,71 @@ impl SamplesBuffer {
     }
 }
 
+/// Samples decoded once that can be played any number of times, also at the same time.
+///
+/// Sound effects in games are often triggered many times while the previous ones still play.
+/// Decoding the file every time costs CPU, so decode it once, for example with
+/// [`Decoder::into_shared_buffer`](crate::Decoder::into_shared_buffer), and call
+/// [`source`](SharedBuffer::source) for every play. All sources share the samples in memory
+/// while each one has its own position, so they can be played and seeked independently.
+///
+/// # Example
+///
+/// ```
+/// use rodio::buffer::{SamplesBuffer, SharedBuffer};
+/// use rodio::{nz, Player};
+///
+/// let click = SharedBuffer::from(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.0, 1.0, 0.0]));
+/// let (player, _output) = Player::new();
+/// player.append(click.source());
+/// player.append(click.source());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedBuffer {
+    // Kept at the start, sources are clones of it.
+    buffer: SamplesBuffer,
+}
+
+/// A source playing a [`SharedBuffer`] from its own position.
+///
+/// Cloning it is cheap and does not copy the samples.
+pub type SharedBufferSource = SamplesBuffer;
+
+impl SharedBuffer {
+    /// Returns a new source playing the samples from the start.
+    #[inline]
+    pub fn source(&self) -> SharedBufferSource {
+        self.buffer.clone()
+    }
+
+    /// Returns the number of channels of the samples.
+    #[inline]
+    pub fn channels(&self) -> ChannelCount {
+        self.buffer.channels
+    }
+
+    /// Returns the sample rate of the samples.
+    #[inline]
+    pub fn sample_rate(&self) -> SampleRate {
+        self.buffer.sample_rate
+    }
+
+    /// Returns how long the samples take to play.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.buffer.duration
+    }
+}
+
+impl From<SamplesBuffer> for SharedBuffer {
+    /// Shares the samples of a buffer, sources start at the start of the buffer regardless of
+    /// where it was.
+    fn from(mut buffer: SamplesBuffer) -> Self {
+        buffer.pos = 0;
+        Self { buffer }
+    }
+}
+
 impl Source for SamplesBuffer {
     #[inline]
     fn current_span_len(&self) -> Option<usize> {
@@ -96,6 +174,20 @@ impl Source for SamplesBuffer {
         Some(self.duration)
     }
 
+    #[inline]
+    fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+        let remaining = &self.data[self.pos.min(self.data.len())..];
+        let written = out.len().min(remaining.len());
+        out[..written].copy_from_slice(&remaining[..written]);
+        self.pos += written;
+        written
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
     /// This jumps in memory till the sample for `pos`.
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
@@ -118,6 +210,20 @@ impl Source for SamplesBuffer {
         self.pos = new_pos;
         Ok(())
     }
+
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), SeekError> {
+        let channels = self.channels().get() as usize;
+        let curr_channel = self.pos % channels;
+        // saturate pos at the end of the source
+        let frame = usize::try_from(frame).unwrap_or(usize::MAX);
+        let new_pos = frame
+            .saturating_mul(channels)
+            .min(self.data.len() / channels * channels);
+
+        // keep playing the same channel
+        self.pos = (new_pos + curr_channel).min(self.data.len());
+        Ok(())
+    }
 }
 
 impl Iterator for SamplesBuffer {
@@ -158,6 +264,13 @@ mod tests {
         assert_eq!(dur.subsec_nanos(), 500_000_000);
     }
 
+    #[test]
+    fn from_slice_copies_samples() {
+        let samples = [0.5, -0.5, 0.25];
+        let buf = SamplesBuffer::from_slice(nz!(1), nz!(44100), &samples);
+        assert_eq!(buf.collect::<Vec<_>>(), samples);
+    }
+
     #[test]
     fn iteration() {
         let mut buf = SamplesBuffer::new(nz!(1), nz!(44100), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
@@ -198,5 +311,30 @@ mod tests {
             buf.try_seek(Duration::from_secs(6)).unwrap();
             assert!(buf.next().is_some_and(|s| s.trunc() as i32 % 2 == 1),);
         }
+
+        #[test]
+        fn seek_frame() {
+            let mut buf = SamplesBuffer::new(
+                nz!(2),
+                nz!(44100),
+                (0..20i16).map(|s| s as Sample).collect::<Vec<_>>(),
+            );
+            buf.try_seek_frame(3).unwrap();
+            assert_eq!(buf.next(), Some(6.0));
+
+            // The channel that is played next stays the same.
+            buf.try_seek_frame(8).unwrap();
+            assert_eq!(buf.next(), Some(17.0));
+
+            buf.try_seek_frame(100).unwrap();
+            assert_eq!(buf.next(), None);
+        }
+
+        #[test]
+        fn saturates_instead_of_out_of_range() {
+            let mut buf = SamplesBuffer::new(nz!(1), nz!(10), vec![1.0; 10]);
+            buf.try_seek(Duration::from_secs(60)).unwrap();
+            assert_eq!(buf.next(), None);
+        }
     }
 }
@@ -1,6 +1,43 @@
-use crate::common::ChannelCount;
+use crate::common::{ChannelCount, Float};
 use crate::Sample;
 
+/// How channels are combined when they are mixed down to mono.
+///
+/// Summing channels raises the level, so they have to be scaled down to stay within full scale.
+/// How much depends on how similar the channels are, which is why there is a choice.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DownmixMode {
+    /// Divides the sum by the number of channels.
+    ///
+    /// Never clips as long as the channels are within `-1.0..=1.0`. Identical channels keep
+    /// their level, but uncorrelated content such as a wide stereo recording drops by about
+    /// 3 dB, and a sound on only one of two channels drops by 6 dB.
+    #[default]
+    Average,
+    /// Sums the channels and clamps the result to `-1.0..=1.0`.
+    ///
+    /// A sound on a single channel keeps its level, but identical channels add up to a
+    /// louder signal that clips hard once it exceeds full scale.
+    SumClamped,
+    /// Divides the sum by the square root of the number of channels.
+    ///
+    /// Keeps the loudness of uncorrelated content. Identical channels get louder, by 3 dB for
+    /// stereo, and can exceed full scale, so consider a limiter after the downmix.
+    PowerPreserving,
+}
+
+impl DownmixMode {
+    /// Scales the sum of `channels` samples according to the mode.
+    pub(crate) fn mix(self, sum: Sample, channels: u16) -> Sample {
+        let channels = channels as Float;
+        match self {
+            DownmixMode::Average => sum / channels,
+            DownmixMode::SumClamped => sum.clamp(-1.0, 1.0),
+            DownmixMode::PowerPreserving => sum / channels.sqrt(),
+        }
+    }
+}
+
 /// Iterator that converts from a certain channel count to another.
 #[derive(Clone, Debug)]
 pub struct ChannelCountConverter<I>
@@ -12,6 +49,7 @@ where
     to: ChannelCount,
     sample_repeat: Option<Sample>,
     next_output_sample_pos: u16,
+    downmix: DownmixMode,
 }
 
 impl<I> ChannelCountConverter<I>
@@ -20,6 +58,9 @@ where
 {
     /// Initializes the iterator.
     ///
+    /// Conversions to mono mix all channels with [`DownmixMode::Average`], use
+    /// [`with_downmix_mode`](Self::with_downmix_mode) to choose another mode.
+    ///
     /// # Panic
     ///
     /// Panics if `from` or `to` are equal to 0.
@@ -32,15 +73,35 @@ where
             to,
             sample_repeat: None,
             next_output_sample_pos: 0,
+            downmix: DownmixMode::default(),
         }
     }
 
+    /// Sets how all channels are mixed together when converting to mono, by default
+    /// [`DownmixMode::Average`]. See [`DownmixMode`] for how the level changes.
+    ///
+    /// Conversions to more than one channel do not mix, they drop the extra channels. The
+    /// channels that are kept play at their original level and can not clip, but anything only
+    /// present on the dropped channels is lost, so a 5.1 source converted to stereo plays its
+    /// front left and right channels only.
+    #[inline]
+    pub fn with_downmix_mode(mut self, mode: DownmixMode) -> Self {
+        self.downmix = mode;
+        self
+    }
+
     /// Destroys this iterator and returns the underlying iterator.
     #[inline]
     pub fn into_inner(self) -> I {
         self.input
     }
 
+    /// Get access to the iterator
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
     /// Get mutable access to the iterator
     #[inline]
     pub fn inner_mut(&mut self) -> &mut I {
@@ -55,6 +116,14 @@ where
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.to.get() == 1 && self.from.get() > 1 {
+            let mut sum = self.input.next()?;
+            for _ in 1..self.from.get() {
+                sum += self.input.next().unwrap_or(0.0);
+            }
+            return Some(self.downmix.mix(sum, self.from.get()));
+        }
+
         let result = match self.next_output_sample_pos {
             0 => {
                 // save first sample for mono -> stereo conversion
@@ -106,7 +175,7 @@ impl<I> ExactSizeIterator for ChannelCountConverter<I> where I: ExactSizeIterato
 
 #[cfg(test)]
 mod test {
-    use super::ChannelCountConverter;
+    use super::{ChannelCountConverter, DownmixMode};
     use crate::common::ChannelCount;
     use crate::math::nz;
     use crate::Sample;
@@ -118,10 +187,27 @@ mod test {
             ChannelCountConverter::new(input.into_iter(), nz!(3), nz!(2)).collect::<Vec<_>>();
         assert_eq!(output, [1.0, 2.0, 4.0, 5.0]);
 
+        // Mono averages all channels.
         let input = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
         let output =
             ChannelCountConverter::new(input.into_iter(), nz!(4), nz!(1)).collect::<Vec<_>>();
-        assert_eq!(output, [1.0, 5.0]);
+        assert_eq!(output, [2.5, 6.5]);
+    }
+
+    #[test]
+    fn downmix_to_mono() {
+        let input = vec![1.0, 1.0, -1.0, -1.0, 0.5, -0.5];
+        let downmix = |mode| {
+            ChannelCountConverter::new(input.clone().into_iter(), nz!(2), nz!(1))
+                .with_downmix_mode(mode)
+                .collect::<Vec<_>>()
+        };
+
+        // Identical full scale channels stay within full scale.
+        assert_eq!(downmix(DownmixMode::Average), [1.0, -1.0, 0.0]);
+        assert_eq!(downmix(DownmixMode::SumClamped), [1.0, -1.0, 0.0]);
+        let power = downmix(DownmixMode::PowerPreserving);
+        assert!((power[0] * power[0] - 2.0).abs() < 1e-6);
     }
 
     #[test]
@@ -0,0 +1,126 @@
+use crate::common::ChannelCount;
+use crate::Sample;
+
+/// Splits interleaved samples into one buffer per channel (planar layout).
+///
+/// A trailing incomplete frame is dropped.
+///
+/// # Example
+/// ```
+/// use rodio::conversions::deinterleave;
+/// use rodio::nz;
+///
+/// let planes = deinterleave(&[1.0, -1.0, 2.0, -2.0], nz!(2));
+/// assert_eq!(planes, [vec![1.0, 2.0], vec![-1.0, -2.0]]);
+/// ```
+pub fn deinterleave(samples: &[Sample], channels: ChannelCount) -> Vec<Vec<Sample>> {
+    let mut planes = vec![Vec::new(); channels.get() as usize];
+    deinterleave_into(samples, &mut planes);
+    planes
+}
+
+/// Splits interleaved samples into the given per-channel buffers, reusing their allocations.
+///
+/// The number of channels is the number of buffers. Each buffer is cleared before the samples
+/// of its channel are written to it. A trailing incomplete frame is dropped.
+///
+/// # Panics
+///
+/// Panics if `planes` is empty.
+pub fn deinterleave_into(samples: &[Sample], planes: &mut [Vec<Sample>]) {
+    assert!(!planes.is_empty(), "need at least one channel");
+    let frames = samples.chunks_exact(planes.len());
+    for plane in planes.iter_mut() {
+        plane.clear();
+        plane.reserve(frames.len());
+    }
+    for frame in frames {
+        for (plane, sample) in planes.iter_mut().zip(frame) {
+            plane.push(*sample);
+        }
+    }
+}
+
+/// Merges one buffer per channel (planar layout) into interleaved samples.
+///
+/// # Panics
+///
+/// Panics if the buffers do not all have the same length.
+///
+/// # Example
+/// ```
+/// use rodio::conversions::interleave;
+///
+/// let samples = interleave(&[[1.0, 2.0], [-1.0, -2.0]]);
+/// assert_eq!(samples, [1.0, -1.0, 2.0, -2.0]);
+/// ```
+pub fn interleave<P: AsRef<[Sample]>>(planes: &[P]) -> Vec<Sample> {
+    let mut samples = Vec::new();
+    interleave_into(planes, &mut samples);
+    samples
+}
+
+/// Merges one buffer per channel (planar layout) into `samples`, reusing its allocation.
+///
+/// `samples` is cleared before the interleaved samples are written to it.
+///
+/// # Panics
+///
+/// Panics if the buffers do not all have the same length.
+pub fn interleave_into<P: AsRef<[Sample]>>(planes: &[P], samples: &mut Vec<Sample>) {
+    samples.clear();
+    let Some(frames) = planes.first().map(|plane| plane.as_ref().len()) else {
+        return;
+    };
+    assert!(
+        planes.iter().all(|plane| plane.as_ref().len() == frames),
+        "all channels must have the same number of samples"
+    );
+
+    samples.reserve(frames * planes.len());
+    for frame in 0..frames {
+        samples.extend(planes.iter().map(|plane| plane.as_ref()[frame]));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::nz;
+
+    fn round_trip(channels: ChannelCount) {
+        let samples: Vec<Sample> = (0..channels.get() * 4).map(Sample::from).collect();
+
+        let planes = deinterleave(&samples, channels);
+        assert_eq!(planes.len(), channels.get() as usize);
+        for (channel, plane) in planes.iter().enumerate() {
+            let expected: Vec<Sample> = samples
+                .iter()
+                .copied()
+                .skip(channel)
+                .step_by(channels.get() as usize)
+                .collect();
+            assert_eq!(plane, &expected);
+        }
+
+        assert_eq!(interleave(&planes), samples);
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(nz!(1));
+        round_trip(nz!(2));
+        round_trip(nz!(5));
+    }
+
+    #[test]
+    fn into_variants_reuse_buffers() {
+        let mut planes = vec![vec![9.0; 8], vec![9.0; 8]];
+        deinterleave_into(&[1.0, 2.0, 3.0, 4.0, 5.0], &mut planes);
+        assert_eq!(planes, [vec![1.0, 3.0], vec![2.0, 4.0]]);
+
+        let mut samples = vec![9.0; 8];
+        interleave_into(&planes, &mut samples);
+        assert_eq!(samples, [1.0, 2.0, 3.0, 4.0]);
+    }
+}
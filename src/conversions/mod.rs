@@ -1,13 +1,18 @@
 /*!
 This module contains functions that convert from one PCM format to another.
 
-This includes conversion between sample formats, channels or sample rates.
+This includes conversion between sample formats, channels, sample rates or between
+interleaved and planar layouts.
 */
 
-pub use self::channels::ChannelCountConverter;
-pub use self::sample::SampleTypeConverter;
+pub use self::channels::{ChannelCountConverter, DownmixMode};
+pub use self::layout::{deinterleave, deinterleave_into, interleave, interleave_into};
+pub use self::sample::{convert_samples, SampleTypeConverter};
 pub use self::sample_rate::SampleRateConverter;
 
 mod channels;
+mod layout;
 mod sample;
 mod sample_rate;
+#[cfg(all(feature = "simd", target_arch = "x86_64", not(feature = "64bit")))]
+mod simd;
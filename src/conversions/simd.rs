@@ -0,0 +1,45 @@
+//! Sample type conversions using SSE2, which every x86_64 CPU supports.
+
+use std::arch::x86_64::{
+    __m128i, _mm_and_ps, _mm_cmpord_ps, _mm_cvttps_epi32, _mm_loadu_ps, _mm_max_ps, _mm_min_ps,
+    _mm_mul_ps, _mm_packs_epi32, _mm_set1_ps, _mm_storeu_si128,
+};
+
+use dasp_sample::FromSample;
+
+/// Same as `i16::from_sample_` for every sample, eight samples at a time.
+pub(super) fn to_i16(samples: &[f32], out: &mut [i16]) {
+    let mut inputs = samples.chunks_exact(8);
+    let mut outputs = out.chunks_exact_mut(8);
+    for (input, output) in (&mut inputs).zip(&mut outputs) {
+        // SAFETY: SSE2 is always available on x86_64. Both chunks hold exactly eight elements
+        // and the loads and stores are unaligned.
+        unsafe {
+            let low = to_i32(input.as_ptr());
+            let high = to_i32(input.as_ptr().add(4));
+            _mm_storeu_si128(
+                output.as_mut_ptr().cast::<__m128i>(),
+                _mm_packs_epi32(low, high),
+            );
+        }
+    }
+    for (out, sample) in outputs.into_remainder().iter_mut().zip(inputs.remainder()) {
+        *out = i16::from_sample_(*sample);
+    }
+}
+
+/// Scales four samples to the `i16` range and truncates them like an `as` cast, which
+/// saturates and turns NaN into zero.
+///
+/// # Safety
+/// `samples` must point to four readable `f32`s.
+#[inline]
+unsafe fn to_i32(samples: *const f32) -> __m128i {
+    let scaled = _mm_mul_ps(_mm_loadu_ps(samples), _mm_set1_ps(32768.0));
+    let not_nan = _mm_and_ps(scaled, _mm_cmpord_ps(scaled, scaled));
+    let clamped = _mm_min_ps(
+        _mm_max_ps(not_nan, _mm_set1_ps(-32768.0)),
+        _mm_set1_ps(32767.0),
+    );
+    _mm_cvttps_epi32(clamped)
+}
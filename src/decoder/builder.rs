@@ -348,7 +348,7 @@ impl<R: Read + Seek + Send + Sync + 'static> DecoderBuilder<R> {
     /// or is not supported.
     pub fn build(self) -> Result<Decoder<R>, DecoderError> {
         let (decoder, _) = self.build_impl()?;
-        Ok(Decoder(decoder))
+        Ok(Decoder(FoldDown::new(decoder).track_position()))
     }
 
     /// Creates a new looped decoder with previously configured settings.
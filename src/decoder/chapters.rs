@@ -0,0 +1,310 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use symphonia::core::meta::{Tag, Value};
+
+use super::Chapter;
+
+const ID3_HEADER_LEN: usize = 10;
+const ID3_FRAME_HEADER_LEN: usize = 10;
+
+/// Reads the chapters from the `CHAP` frames of an ID3v2 tag at the current position of
+/// `reader`, which is left unchanged. Symphonia skips these frames when it reads the tag.
+///
+/// Returns no chapters if there is no tag or it can not be read.
+pub(crate) fn read_id3v2_chapters<R: Read + Seek>(reader: &mut R) -> Vec<Chapter> {
+    let Ok(start) = reader.stream_position() else {
+        return Vec::new();
+    };
+    let chapters = read_id3v2_tag(reader).unwrap_or_default();
+    if reader.seek(SeekFrom::Start(start)).is_err() {
+        // The decoder can not start without its data, let it report the error.
+        return Vec::new();
+    }
+    chapters
+}
+
+fn read_id3v2_tag<R: Read + Seek>(reader: &mut R) -> io::Result<Vec<Chapter>> {
+    let mut header = [0; ID3_HEADER_LEN];
+    reader.read_exact(&mut header)?;
+    let [b'I', b'D', b'3', major, _, flags, ..] = header else {
+        return Ok(Vec::new());
+    };
+    // Only ID3v2.3 and later have chapters. An unsynchronised tag would need decoding first.
+    if !(3..=4).contains(&major) || flags & 0x80 != 0 {
+        return Ok(Vec::new());
+    }
+    let mut remaining = u64::from(syncsafe(&header[6..10]));
+
+    if flags & 0x40 != 0 {
+        let mut size = [0; 4];
+        reader.read_exact(&mut size)?;
+        // ID3v2.4 counts the size field itself, ID3v2.3 does not.
+        let extended_len = match major {
+            3 => u64::from(u32::from_be_bytes(size)),
+            _ => u64::from(syncsafe(&size)).saturating_sub(4),
+        };
+        reader.seek(SeekFrom::Current(extended_len as i64))?;
+        remaining = remaining.saturating_sub(4 + extended_len);
+    }
+
+    let mut chapters = Vec::new();
+    while remaining >= ID3_FRAME_HEADER_LEN as u64 {
+        let mut frame_header = [0; ID3_FRAME_HEADER_LEN];
+        reader.read_exact(&mut frame_header)?;
+        let Some((id, len, format_flags)) = parse_frame_header(&frame_header, major) else {
+            // Padding.
+            break;
+        };
+        remaining = remaining.saturating_sub(ID3_FRAME_HEADER_LEN as u64);
+        let len = u64::from(len).min(remaining);
+        remaining -= len;
+
+        if id != *b"CHAP" {
+            reader.seek(SeekFrom::Current(len as i64))?;
+            continue;
+        }
+        let mut body = vec![0; len as usize];
+        reader.read_exact(&mut body)?;
+        if let Some(body) = frame_body(&body, format_flags, major) {
+            chapters.extend(parse_chap(body, major));
+        }
+    }
+
+    chapters.sort_by_key(|chapter| chapter.start);
+    Ok(chapters)
+}
+
+fn syncsafe(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0, |value, byte| (value << 7) | u32::from(byte & 0x7f))
+}
+
+/// Returns the id, length and format flags of a frame, `None` for padding.
+fn parse_frame_header(header: &[u8], major: u8) -> Option<([u8; 4], u32, u8)> {
+    let id: [u8; 4] = header[..4].try_into().ok()?;
+    if !id
+        .iter()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    {
+        return None;
+    }
+    let len = match major {
+        3 => u32::from_be_bytes(header[4..8].try_into().ok()?),
+        _ => syncsafe(&header[4..8]),
+    };
+    Some((id, len, header[9]))
+}
+
+/// Returns the contents of a frame, `None` if they are compressed, encrypted or
+/// unsynchronised.
+fn frame_body(body: &[u8], format_flags: u8, major: u8) -> Option<&[u8]> {
+    if major == 3 {
+        // Compression and encryption, a group id is one byte in front of the contents.
+        if format_flags & 0xc0 != 0 {
+            return None;
+        }
+        let group_len = usize::from(format_flags & 0x20 != 0);
+        return body.get(group_len..);
+    }
+    // Compression, encryption and unsynchronisation, in ID3v2.4 a group id and data length
+    // indicator are in front of the contents.
+    if format_flags & 0x0e != 0 {
+        return None;
+    }
+    let prefix_len = usize::from(format_flags & 0x40 != 0) + 4 * usize::from(format_flags & 0x01);
+    body.get(prefix_len..)
+}
+
+/// Parses a `CHAP` frame: an element id, the start and end in milliseconds, byte offsets that
+/// are not used here and embedded frames, of which `TIT2` holds the title.
+fn parse_chap(body: &[u8], major: u8) -> Option<Chapter> {
+    let id_end = body.iter().position(|byte| *byte == 0)?;
+    let times = body.get(id_end + 1..id_end + 17)?;
+    let start_ms = u32::from_be_bytes(times[..4].try_into().ok()?);
+
+    let mut title = String::new();
+    let mut frames = &body[id_end + 17..];
+    while frames.len() >= ID3_FRAME_HEADER_LEN {
+        let Some((id, len, format_flags)) = parse_frame_header(frames, major) else {
+            break;
+        };
+        let frame = frames.get(ID3_FRAME_HEADER_LEN..ID3_FRAME_HEADER_LEN + len as usize)?;
+        if id == *b"TIT2" {
+            if let Some(text) = frame_body(frame, format_flags, major) {
+                title = decode_text(text);
+            }
+            break;
+        }
+        frames = &frames[ID3_FRAME_HEADER_LEN + len as usize..];
+    }
+
+    Some(Chapter {
+        start: Duration::from_millis(u64::from(start_ms)),
+        title,
+    })
+}
+
+/// Decodes the contents of an ID3v2 text frame, which start with the encoding.
+fn decode_text(text: &[u8]) -> String {
+    let Some((&encoding, text)) = text.split_first() else {
+        return String::new();
+    };
+    let utf16 = |text: &[u8], big_endian: bool| {
+        let units: Vec<u16> = text
+            .chunks_exact(2)
+            .map(|pair| match big_endian {
+                true => u16::from_be_bytes([pair[0], pair[1]]),
+                false => u16::from_le_bytes([pair[0], pair[1]]),
+            })
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    let decoded = match encoding {
+        0 => text.iter().map(|&byte| char::from(byte)).collect(),
+        1 => match text {
+            [0xff, 0xfe, rest @ ..] => utf16(rest, false),
+            [0xfe, 0xff, rest @ ..] => utf16(rest, true),
+            _ => utf16(text, true),
+        },
+        2 => utf16(text, true),
+        _ => String::from_utf8_lossy(text).into_owned(),
+    };
+    decoded.trim_end_matches('\0').to_owned()
+}
+
+/// Reads chapters from Vorbis comments like `CHAPTER001=00:01:30.500` and
+/// `CHAPTER001NAME=Introduction`, as used in Ogg and FLAC files.
+pub(crate) fn read_vorbis_chapters(tags: &[Tag]) -> Vec<Chapter> {
+    let mut found: BTreeMap<u32, (Option<Duration>, String)> = BTreeMap::new();
+    for tag in tags {
+        let Value::String(value) = &tag.value else {
+            continue;
+        };
+        let key = tag.key.to_ascii_uppercase();
+        let Some(rest) = key.strip_prefix("CHAPTER") else {
+            continue;
+        };
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let Ok(number) = rest[..digits].parse() else {
+            continue;
+        };
+        let chapter = found.entry(number).or_default();
+        match &rest[digits..] {
+            "" => chapter.0 = parse_chapter_time(value),
+            "NAME" => chapter.1 = value.clone(),
+            _ => {}
+        }
+    }
+
+    let mut chapters: Vec<_> = found
+        .into_values()
+        .filter_map(|(start, title)| {
+            Some(Chapter {
+                start: start?,
+                title,
+            })
+        })
+        .collect();
+    chapters.sort_by_key(|chapter| chapter.start);
+    chapters
+}
+
+/// Parses a time like `01:02:03.450`.
+fn parse_chapter_time(time: &str) -> Option<Duration> {
+    let mut parts = time.trim().split(':');
+    let hours: u64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+    Some(Duration::from_secs(hours * 3600 + minutes * 60) + Duration::from_secs_f64(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn frame(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut frame = id.to_vec();
+        frame.extend((body.len() as u32).to_be_bytes());
+        frame.extend([0, 0]);
+        frame.extend(body);
+        frame
+    }
+
+    fn chap(id: &str, start_ms: u32, title: &str) -> Vec<u8> {
+        let mut body = id.as_bytes().to_vec();
+        body.push(0);
+        body.extend(start_ms.to_be_bytes());
+        body.extend([0xff; 12]);
+        let mut text = vec![3];
+        text.extend(title.as_bytes());
+        body.extend(frame(b"TIT2", &text));
+        frame(b"CHAP", &body)
+    }
+
+    #[test]
+    fn reads_id3v2_chapters() {
+        let mut frames = frame(b"TIT2", b"\x03Audiobook");
+        frames.extend(chap("ch2", 90_500, "Second"));
+        frames.extend(chap("ch1", 0, "First"));
+        frames.extend([0; 16]);
+
+        let len = frames.len() as u32;
+        let mut tag = b"ID3\x03\x00\x00".to_vec();
+        tag.extend([21, 14, 7, 0].map(|shift| (len >> shift) as u8 & 0x7f));
+        tag.extend(frames);
+        tag.extend(b"audio");
+
+        let mut reader = Cursor::new(tag);
+        let chapters = read_id3v2_chapters(&mut reader);
+        assert_eq!(
+            chapters,
+            [
+                Chapter {
+                    start: Duration::ZERO,
+                    title: "First".to_owned()
+                },
+                Chapter {
+                    start: Duration::from_millis(90_500),
+                    title: "Second".to_owned()
+                },
+            ]
+        );
+        assert_eq!(reader.position(), 0);
+
+        assert!(read_id3v2_chapters(&mut Cursor::new(b"RIFF")).is_empty());
+    }
+
+    #[test]
+    fn reads_vorbis_chapters() {
+        let tag = |key: &str, value: &str| Tag::new(None, key, Value::from(value));
+        let tags = [
+            tag("CHAPTER002", "00:01:30.500"),
+            tag("CHAPTER002NAME", "Second"),
+            tag("chapter001", "00:00:00.000"),
+            tag("chapter001name", "First"),
+            tag("CHAPTER003NAME", "No start"),
+            tag("TITLE", "Audiobook"),
+        ];
+        let chapters = read_vorbis_chapters(&tags);
+        assert_eq!(
+            chapters,
+            [
+                Chapter {
+                    start: Duration::ZERO,
+                    title: "First".to_owned()
+                },
+                Chapter {
+                    start: Duration::from_millis(90_500),
+                    title: "Second".to_owned()
+                },
+            ]
+        );
+    }
+}
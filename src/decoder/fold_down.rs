@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use crate::common::{ChannelCount, Float, SampleRate};
+use crate::conversions::DownmixMode;
+use crate::math::FRAC_1_SQRT_2;
+use crate::source::SeekError;
+use crate::{Sample, Source};
+
+/// Mixes the channels of a decoded stream down to at most a given number of channels.
+///
+/// Does nothing until a maximum is set. Surround layouts are folded down to stereo using the
+/// usual coefficients: center and surround channels are mixed in at -3 dB and the LFE channel is
+/// dropped. Mono combines the stereo fold-down according to a [`DownmixMode`], by default
+/// the average. Folding down to more than two channels
+/// keeps the first channels and drops the rest, since there is no common layout to fold into.
+pub(crate) struct FoldDown<I> {
+    input: I,
+    max_channels: Option<ChannelCount>,
+    downmix_mode: DownmixMode,
+    // Weight of every input channel in the left and right output, for `matrix_channels`.
+    matrix: Vec<[Float; 2]>,
+    matrix_channels: u16,
+    // The frame being played and what the input reported when it was read.
+    frame: Vec<Sample>,
+    frame_pos: usize,
+    span_len: Option<usize>,
+    input_channels: ChannelCount,
+    sample_rate: SampleRate,
+}
+
+impl<I> FoldDown<I>
+where
+    I: Source,
+{
+    pub(crate) fn new(input: I) -> Self {
+        let input_channels = input.channels();
+        let sample_rate = input.sample_rate();
+        Self {
+            input,
+            max_channels: None,
+            downmix_mode: DownmixMode::default(),
+            matrix: Vec::new(),
+            matrix_channels: 0,
+            frame: Vec::new(),
+            frame_pos: 0,
+            span_len: None,
+            input_channels,
+            sample_rate,
+        }
+    }
+
+    pub(crate) fn inner(&self) -> &I {
+        &self.input
+    }
+
+    pub(crate) fn set_max_channels(&mut self, max_channels: ChannelCount) {
+        self.max_channels = Some(max_channels);
+    }
+
+    pub(crate) fn set_downmix_mode(&mut self, mode: DownmixMode) {
+        self.downmix_mode = mode;
+    }
+
+    fn in_frame(&self) -> bool {
+        self.frame_pos < self.frame.len()
+    }
+
+    fn output_channels(&self, input_channels: ChannelCount) -> ChannelCount {
+        match self.max_channels {
+            Some(max) => input_channels.min(max),
+            None => input_channels,
+        }
+    }
+
+    /// Reads the next frame of the input and folds it down, returns `false` at the end.
+    fn next_frame(&mut self, max: ChannelCount) -> bool {
+        self.span_len = self.input.current_span_len();
+        self.input_channels = self.input.channels();
+        self.sample_rate = self.input.sample_rate();
+
+        let channels = self.input_channels.get();
+        self.frame.clear();
+        for _ in 0..channels {
+            match self.input.next() {
+                Some(sample) => self.frame.push(sample),
+                None => break,
+            }
+        }
+        if self.frame.len() < channels as usize {
+            self.frame.clear();
+            self.frame_pos = 0;
+            return false;
+        }
+
+        if channels > max.get() {
+            if max.get() > 2 {
+                self.frame.truncate(max.get() as usize);
+            } else {
+                self.fold(max.get());
+            }
+        }
+        self.frame_pos = 0;
+        true
+    }
+
+    fn fold(&mut self, max: u16) {
+        let channels = self.input_channels.get();
+        if self.matrix_channels != channels {
+            self.matrix = stereo_matrix(channels);
+            self.matrix_channels = channels;
+        }
+
+        let (mut left, mut right) = (0.0, 0.0);
+        for (sample, [to_left, to_right]) in self.frame.iter().zip(&self.matrix) {
+            left += sample * to_left;
+            right += sample * to_right;
+        }
+
+        self.frame.clear();
+        if max == 1 {
+            self.frame.push(self.downmix_mode.mix(left + right, 2));
+        } else {
+            self.frame.extend([left, right]);
+        }
+    }
+}
+
+/// Weights of every channel in the left and right output, in the usual channel order of WAV
+/// and FLAC files. Every output is normalized so full scale input does not clip.
+fn stereo_matrix(channels: u16) -> Vec<[Float; 2]> {
+    const LEFT: [Float; 2] = [1.0, 0.0];
+    const RIGHT: [Float; 2] = [0.0, 1.0];
+    const CENTER: [Float; 2] = [FRAC_1_SQRT_2, FRAC_1_SQRT_2];
+    const LFE: [Float; 2] = [0.0, 0.0];
+    const SURROUND_LEFT: [Float; 2] = [FRAC_1_SQRT_2, 0.0];
+    const SURROUND_RIGHT: [Float; 2] = [0.0, FRAC_1_SQRT_2];
+    const SURROUND_CENTER: [Float; 2] = [0.5, 0.5];
+
+    let mut matrix = match channels {
+        1 => vec![[1.0, 1.0]],
+        2 => vec![LEFT, RIGHT],
+        3 => vec![LEFT, RIGHT, CENTER],
+        4 => vec![LEFT, RIGHT, SURROUND_LEFT, SURROUND_RIGHT],
+        5 => vec![LEFT, RIGHT, CENTER, SURROUND_LEFT, SURROUND_RIGHT],
+        6 => vec![LEFT, RIGHT, CENTER, LFE, SURROUND_LEFT, SURROUND_RIGHT],
+        7 => vec![
+            LEFT,
+            RIGHT,
+            CENTER,
+            LFE,
+            SURROUND_CENTER,
+            SURROUND_LEFT,
+            SURROUND_RIGHT,
+        ],
+        8 => vec![
+            LEFT,
+            RIGHT,
+            CENTER,
+            LFE,
+            SURROUND_LEFT,
+            SURROUND_RIGHT,
+            SURROUND_LEFT,
+            SURROUND_RIGHT,
+        ],
+        // Unknown layout, alternate between left and right.
+        _ => (0..channels)
+            .map(|channel| if channel % 2 == 0 { LEFT } else { RIGHT })
+            .collect(),
+    };
+
+    for side in 0..2 {
+        let total: Float = matrix.iter().map(|weights| weights[side]).sum();
+        for weights in &mut matrix {
+            weights[side] /= total;
+        }
+    }
+    matrix
+}
+
+impl<I> Iterator for FoldDown<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let Some(max) = self.max_channels else {
+            return self.input.next();
+        };
+
+        if !self.in_frame() && !self.next_frame(max) {
+            return None;
+        }
+        let sample = self.frame[self.frame_pos];
+        self.frame_pos += 1;
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.input.size_hint();
+        if self.max_channels.is_none() {
+            return (lower, upper);
+        }
+
+        let input_channels = self.input.channels();
+        let channels = self.output_channels(input_channels).get() as usize;
+        let scale = |len: usize| len / input_channels.get() as usize * channels;
+        let buffered = self.frame.len() - self.frame_pos;
+        (
+            scale(lower) + buffered,
+            upper.map(|upper| scale(upper) + buffered),
+        )
+    }
+}
+
+impl<I> Source for FoldDown<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        if self.max_channels.is_none() {
+            return self.input.current_span_len();
+        }
+
+        let (span_len, input_channels) = if self.in_frame() {
+            (self.span_len, self.input_channels)
+        } else {
+            (self.input.current_span_len(), self.input.channels())
+        };
+        let channels = self.output_channels(input_channels).get() as usize;
+        span_len.map(|len| len / input_channels.get() as usize * channels)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        if self.in_frame() {
+            self.output_channels(self.input_channels)
+        } else {
+            self.output_channels(self.input.channels())
+        }
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        if self.in_frame() {
+            self.sample_rate
+        } else {
+            self.input.sample_rate()
+        }
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.frame.clear();
+        self.frame_pos = 0;
+        Ok(())
+    }
+
+    #[inline]
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), SeekError> {
+        self.input.try_seek_frame(frame)?;
+        self.frame.clear();
+        self.frame_pos = 0;
+        Ok(())
+    }
+}
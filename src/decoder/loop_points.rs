@@ -0,0 +1,122 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+const RIFF_HEADER_LEN: usize = 12;
+const CHUNK_HEADER_LEN: usize = 8;
+// Manufacturer, product, sample period, MIDI note and pitch, SMPTE format and offset, loop
+// count and sampler data length.
+const SMPL_HEADER_LEN: usize = 36;
+// Cue point id, type, start, end, fraction and play count.
+const SMPL_LOOP_LEN: usize = 24;
+
+/// Reads the first loop of the `smpl` chunk of a WAV file at the current position of
+/// `reader`, which is left unchanged. Symphonia skips this chunk when it reads the file.
+///
+/// Returns the first frame of the loop and the frame after it, or `None` if there is no loop
+/// or the file can not be read.
+pub(crate) fn read_wav_loop_points<R: Read + Seek>(reader: &mut R) -> Option<(u64, u64)> {
+    let start = reader.stream_position().ok()?;
+    let loop_points = read_riff_chunks(reader).ok().flatten();
+    // The decoder can not start without its data, let it report the error.
+    reader.seek(SeekFrom::Start(start)).ok()?;
+    loop_points
+}
+
+fn read_riff_chunks<R: Read + Seek>(reader: &mut R) -> io::Result<Option<(u64, u64)>> {
+    let mut header = [0; RIFF_HEADER_LEN];
+    reader.read_exact(&mut header)?;
+    if &header[..4] != b"RIFF" || &header[8..] != b"WAVE" {
+        return Ok(None);
+    }
+    let mut remaining =
+        u64::from(u32::from_le_bytes(header[4..8].try_into().unwrap())).saturating_sub(4);
+
+    while remaining >= CHUNK_HEADER_LEN as u64 {
+        let mut chunk_header = [0; CHUNK_HEADER_LEN];
+        reader.read_exact(&mut chunk_header)?;
+        let len = u64::from(u32::from_le_bytes(chunk_header[4..].try_into().unwrap()));
+        // Chunks are padded to an even length.
+        let padded_len = len + (len & 1);
+        remaining = remaining.saturating_sub(CHUNK_HEADER_LEN as u64 + padded_len);
+
+        if &chunk_header[..4] != b"smpl" {
+            reader.seek(SeekFrom::Current(padded_len as i64))?;
+            continue;
+        }
+        let mut body = vec![0; len as usize];
+        reader.read_exact(&mut body)?;
+        return Ok(parse_smpl(&body));
+    }
+    Ok(None)
+}
+
+/// Parses the first loop of a `smpl` chunk, whose end is the last frame played.
+fn parse_smpl(body: &[u8]) -> Option<(u64, u64)> {
+    let field = |offset: usize| u32::from_le_bytes(body[offset..offset + 4].try_into().unwrap());
+    if body.len() < SMPL_HEADER_LEN + SMPL_LOOP_LEN || field(28) == 0 {
+        return None;
+    }
+    let start = field(SMPL_HEADER_LEN + 8);
+    let end = field(SMPL_HEADER_LEN + 12);
+    (start <= end).then(|| (u64::from(start), u64::from(end) + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn chunk(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut chunk = id.to_vec();
+        chunk.extend((body.len() as u32).to_le_bytes());
+        chunk.extend(body);
+        if body.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    }
+
+    fn wav(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let chunks = chunks.concat();
+        let mut wav = b"RIFF".to_vec();
+        wav.extend((chunks.len() as u32 + 4).to_le_bytes());
+        wav.extend(b"WAVE");
+        wav.extend(chunks);
+        wav
+    }
+
+    fn smpl(loops: &[(u32, u32)]) -> Vec<u8> {
+        let mut body = vec![0; 28];
+        body.extend((loops.len() as u32).to_le_bytes());
+        body.extend(0u32.to_le_bytes());
+        for (start, end) in loops {
+            for field in [0, 0, *start, *end, 0, 0] {
+                body.extend(u32::to_le_bytes(field));
+            }
+        }
+        chunk(b"smpl", &body)
+    }
+
+    #[test]
+    fn reads_first_loop() {
+        let file = wav(&[
+            chunk(b"fmt ", &[0; 16]),
+            chunk(b"LIST", b"odd"),
+            smpl(&[(100, 199), (300, 399)]),
+            chunk(b"data", &[0; 8]),
+        ]);
+        let mut reader = Cursor::new(file);
+        assert_eq!(read_wav_loop_points(&mut reader), Some((100, 200)));
+        assert_eq!(reader.position(), 0);
+    }
+
+    #[test]
+    fn no_loop() {
+        let without_chunk = wav(&[chunk(b"fmt ", &[0; 16]), chunk(b"data", &[0; 8])]);
+        assert_eq!(read_wav_loop_points(&mut Cursor::new(without_chunk)), None);
+
+        let without_loops = wav(&[smpl(&[])]);
+        assert_eq!(read_wav_loop_points(&mut Cursor::new(without_loops)), None);
+
+        assert_eq!(read_wav_loop_points(&mut Cursor::new(b"ID3")), None);
+    }
+}
@@ -56,19 +56,30 @@ use std::{
 use std::io::SeekFrom;
 
 use crate::{
-    common::{assert_error_traits, ChannelCount, SampleRate},
-    math::nz,
-    source::{SeekError, Source},
+    buffer::{SamplesBuffer, SharedBuffer},
+    common::{assert_error_traits, ChannelCount, Float, SampleRate},
+    conversions::DownmixMode,
+    math::{frame_to_duration, nz},
+    source::{SeekError, Source, TrackPosition},
     Sample,
 };
 
 pub mod builder;
 pub use builder::{DecoderBuilder, Settings};
+pub use raw_pcm::{Endianness, PcmFormat, PcmSampleType, RawPcmSource};
 
+use fold_down::FoldDown;
+
+#[cfg(feature = "symphonia")]
+mod chapters;
 #[cfg(all(feature = "claxon", not(feature = "symphonia-flac")))]
 mod flac;
+mod fold_down;
+#[cfg(feature = "symphonia")]
+mod loop_points;
 #[cfg(all(feature = "minimp3", not(feature = "symphonia-mp3")))]
 mod mp3;
+mod raw_pcm;
 #[cfg(feature = "symphonia")]
 mod read_seek_source;
 #[cfg(feature = "symphonia")]
@@ -81,7 +92,7 @@ mod wav;
 
 /// Source of audio samples decoded from an input stream.
 /// See the [module-level documentation](self) for examples and usage.
-pub struct Decoder<R: Read + Seek>(DecoderImpl<R>);
+pub struct Decoder<R: Read + Seek>(TrackPosition<FoldDown<DecoderImpl<R>>>);
 
 /// Source of audio samples from decoding a file that never ends.
 /// When the end of the file is reached, the decoder starts again from the beginning.
@@ -128,7 +139,111 @@ enum DecoderImpl<R: Read + Seek> {
 
 enum Unreachable {}
 
+/// Silence an encoder added around the audio, see [`Decoder::gapless_info`].
+///
+/// Lossy encoders like MP3 and AAC need some frames before the audio to prime the decoder, the
+/// delay, and fill up the last packet after it, the padding. Both are measured in frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GaplessInfo {
+    /// Frames before the audio.
+    pub delay: u32,
+    /// Frames after the audio.
+    pub padding: u32,
+}
+
+/// A chapter of an audiobook or podcast, see [`Decoder::chapters`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chapter {
+    /// Position in the stream where the chapter starts.
+    pub start: Duration,
+    /// Title of the chapter, empty if it has none.
+    pub title: String,
+}
+
 impl<R: Read + Seek> DecoderImpl<R> {
+    #[inline]
+    fn chapters(&self) -> &[Chapter] {
+        match self {
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.chapters(),
+            DecoderImpl::None(_, _) => unreachable!(),
+            #[allow(unreachable_patterns)]
+            _ => &[],
+        }
+    }
+
+    #[inline]
+    fn loop_points(&self) -> Option<(u64, u64)> {
+        match self {
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.loop_points(),
+            DecoderImpl::None(_, _) => unreachable!(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn gapless_info(&self) -> Option<GaplessInfo> {
+        match self {
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.gapless_info(),
+            DecoderImpl::None(_, _) => unreachable!(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn replay_gain(&self) -> Option<Float> {
+        match self {
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.replay_gain(),
+            DecoderImpl::None(_, _) => unreachable!(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn replay_gain_peak(&self) -> Option<Float> {
+        match self {
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.replay_gain_peak(),
+            DecoderImpl::None(_, _) => unreachable!(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn codec_name(&self) -> &'static str {
+        match self {
+            #[cfg(all(feature = "hound", not(feature = "symphonia-wav")))]
+            DecoderImpl::Wav(_) => "pcm",
+            #[cfg(all(feature = "lewton", not(feature = "symphonia-vorbis")))]
+            DecoderImpl::Vorbis(_) => "vorbis",
+            #[cfg(all(feature = "claxon", not(feature = "symphonia-flac")))]
+            DecoderImpl::Flac(_) => "flac",
+            #[cfg(all(feature = "minimp3", not(feature = "symphonia-mp3")))]
+            DecoderImpl::Mp3(_) => "mp3",
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.codec_name(),
+            DecoderImpl::None(_, _) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn bitrate(&self) -> Option<u32> {
+        match self {
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.bitrate(),
+            DecoderImpl::None(_, _) => unreachable!(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
     #[inline]
     fn next(&mut self) -> Option<Sample> {
         match self {
@@ -237,6 +352,23 @@ impl<R: Read + Seek> DecoderImpl<R> {
         }
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        match self {
+            #[cfg(all(feature = "hound", not(feature = "symphonia-wav")))]
+            DecoderImpl::Wav(source) => source.is_seekable(),
+            #[cfg(all(feature = "lewton", not(feature = "symphonia-vorbis")))]
+            DecoderImpl::Vorbis(source) => source.is_seekable(),
+            #[cfg(all(feature = "claxon", not(feature = "symphonia-flac")))]
+            DecoderImpl::Flac(source) => source.is_seekable(),
+            #[cfg(all(feature = "minimp3", not(feature = "symphonia-mp3")))]
+            DecoderImpl::Mp3(source) => source.is_seekable(),
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.is_seekable(),
+            DecoderImpl::None(_, _) => unreachable!(),
+        }
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         match self {
@@ -256,6 +388,68 @@ impl<R: Read + Seek> DecoderImpl<R> {
             }
         }
     }
+
+    #[inline]
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), SeekError> {
+        match self {
+            #[cfg(feature = "symphonia")]
+            DecoderImpl::Symphonia(source, PhantomData) => source.try_seek_frame(frame),
+            DecoderImpl::None(_, _) => unreachable!(),
+            #[allow(unreachable_patterns)]
+            _ => DecoderImpl::try_seek(self, frame_to_duration(frame, self.sample_rate())),
+        }
+    }
+}
+
+impl<R: Read + Seek> Iterator for DecoderImpl<R> {
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        DecoderImpl::next(self)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        DecoderImpl::size_hint(self)
+    }
+}
+
+impl<R: Read + Seek> Source for DecoderImpl<R> {
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        DecoderImpl::current_span_len(self)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        DecoderImpl::channels(self)
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        DecoderImpl::sample_rate(self)
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        DecoderImpl::total_duration(self)
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        DecoderImpl::is_seekable(self)
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        DecoderImpl::try_seek(self, pos)
+    }
+
+    #[inline]
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), SeekError> {
+        DecoderImpl::try_seek_frame(self, frame)
+    }
 }
 
 /// Converts a `File` into a `Decoder` with automatic optimizations.
@@ -285,10 +479,7 @@ impl TryFrom<std::fs::File> for Decoder<BufReader<std::fs::File>> {
     type Error = DecoderError;
 
     fn try_from(file: std::fs::File) -> Result<Self, Self::Error> {
-        let len = file
-            .metadata()
-            .map_err(|e| Self::Error::IoError(e.to_string()))?
-            .len();
+        let len = file.metadata().map_err(DecoderError::from)?.len();
 
         Self::builder()
             .with_data(BufReader::new(file))
@@ -359,6 +550,97 @@ where
     }
 }
 
+#[cfg(feature = "mmap")]
+impl Decoder<std::io::Cursor<memmap2::Mmap>> {
+    /// Builds a new decoder that reads a file through a memory mapping.
+    ///
+    /// The operating system loads the parts of the file that are read and can drop them again
+    /// under memory pressure, so large files do not have to fit in memory while seeking still
+    /// works. Several decoders of the same file share the pages that are loaded. The file
+    /// extension, if any, is used as format hint.
+    ///
+    /// # Safety caveat
+    ///
+    /// The file must not be modified or truncated while the decoder exists. The mapping would
+    /// change under the decoder, which is undefined behavior and may crash the process.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecoderError::IoError` if the file could not be opened or mapped, and
+    /// `DecoderError::UnrecognizedFormat` if the audio format could not be determined or is not
+    /// supported.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use rodio::Decoder;
+    ///
+    /// let decoder = Decoder::new_mmap("audio.flac").unwrap();
+    /// ```
+    pub fn new_mmap(path: impl AsRef<std::path::Path>) -> Result<Self, DecoderError> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the caller is told not to modify the file while it is mapped.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }?;
+        let len = mmap.len() as u64;
+
+        let mut builder = Self::builder()
+            .with_data(std::io::Cursor::new(mmap))
+            .with_byte_len(len)
+            .with_seekable(true);
+        if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+            builder = builder.with_hint(extension);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(feature = "symphonia")]
+impl Decoder<::symphonia::core::io::MediaSourceStream> {
+    /// Builds a new decoder from a Symphonia media source that is already set up, for example
+    /// with a custom [`MediaSource`](::symphonia::core::io::MediaSource) that streams over a
+    /// network protocol.
+    ///
+    /// The format is probed with `hint`, which can hold both an extension and a MIME type.
+    /// Whether the decoder can seek and the length of the stream are taken from the media
+    /// source. Only available with the `symphonia` feature, which any of the `symphonia-*`
+    /// and the default format features enable. The Symphonia version must match the one
+    /// rodio uses.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecoderError::UnrecognizedFormat` if the audio format could not be determined
+    /// or is not supported.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::fs::File;
+    /// use rodio::Decoder;
+    /// use symphonia::core::io::MediaSourceStream;
+    /// use symphonia::core::probe::Hint;
+    ///
+    /// let file = File::open("audio.flac").unwrap();
+    /// let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    /// let mut hint = Hint::new();
+    /// hint.with_extension("flac");
+    /// let decoder = Decoder::from_symphonia_source(mss, hint).unwrap();
+    /// ```
+    pub fn from_symphonia_source(
+        mss: ::symphonia::core::io::MediaSourceStream,
+        hint: ::symphonia::core::probe::Hint,
+    ) -> Result<Self, DecoderError> {
+        use ::symphonia::core::io::MediaSource;
+
+        let settings = Settings {
+            byte_len: mss.byte_len(),
+            is_seekable: mss.is_seekable(),
+            ..Settings::default()
+        };
+        let decoder = symphonia::SymphoniaDecoder::with_hint(mss, &hint, &settings)?;
+        let decoder = DecoderImpl::Symphonia(decoder, PhantomData);
+        Ok(Decoder(FoldDown::new(decoder).track_position()))
+    }
+}
+
 impl<R: Read + Seek + Send + Sync + 'static> Decoder<R> {
     /// Returns a builder for creating a new decoder with customizable settings.
     ///
@@ -561,6 +843,188 @@ impl<R: Read + Seek + Send + Sync + 'static> Decoder<R> {
     }
 }
 
+impl<R> Decoder<R>
+where
+    R: Read + Seek,
+{
+    /// Returns the position in the stream, based on the samples returned so far.
+    ///
+    /// The position takes sample rate and channel count changes in the stream into account and
+    /// moves to the requested position on [`try_seek`](Source::try_seek). This is useful to
+    /// show progress when consuming a decoder directly instead of through a
+    /// [`Player`](crate::Player).
+    #[inline]
+    pub fn get_pos(&self) -> Duration {
+        self.0.get_pos()
+    }
+
+    /// Mixes the decoded channels down so the decoder yields at most `max_channels` channels.
+    ///
+    /// Surround streams are folded down to stereo or mono using the usual coefficients: the
+    /// center and surround channels are mixed in at -3 dB and the LFE channel is dropped. This
+    /// avoids a separate conversion step when the output device has fewer channels than the
+    /// file. Folding down to more than two channels keeps the first `max_channels` channels.
+    /// Streams with no more than `max_channels` channels are not changed.
+    ///
+    /// Call this before reading any samples.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use rodio::{math::nz, Decoder};
+    ///
+    /// let file = File::open("surround.flac").unwrap();
+    /// let decoder = Decoder::try_from(file).unwrap().with_max_channels(nz!(2));
+    /// ```
+    pub fn with_max_channels(mut self, max_channels: ChannelCount) -> Self {
+        self.0.inner_mut().set_max_channels(max_channels);
+        self
+    }
+
+    /// Sets how the channels are combined when [`with_max_channels`](Decoder::with_max_channels)
+    /// folds the stream down to mono. Defaults to [`DownmixMode::Average`], which never clips.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use rodio::conversions::DownmixMode;
+    /// use rodio::{math::nz, Decoder};
+    ///
+    /// let file = File::open("music.ogg").unwrap();
+    /// let decoder = Decoder::try_from(file)
+    ///     .unwrap()
+    ///     .with_max_channels(nz!(1))
+    ///     .with_downmix_mode(DownmixMode::PowerPreserving);
+    /// ```
+    pub fn with_downmix_mode(mut self, mode: DownmixMode) -> Self {
+        self.0.inner_mut().set_downmix_mode(mode);
+        self
+    }
+
+    /// Returns the encoder delay and padding found in the file, `None` if there is none.
+    ///
+    /// They are read from the LAME tag of MP3 files and the iTunes `iTunSMPB` tag of AAC files
+    /// in MP4 containers. Unless gapless playback is turned off with
+    /// [`DecoderBuilder::with_gapless`], these frames are not played, so consecutive tracks
+    /// of an album play without a gap. Only the Symphonia decoders read this information.
+    pub fn gapless_info(&self) -> Option<GaplessInfo> {
+        self.0.inner().inner().gapless_info()
+    }
+
+    /// Returns the chapter markers of an audiobook or podcast, in the order they start.
+    ///
+    /// Chapters are read from the `CHAP` frames of ID3v2 tags, used by MP3 files, and from
+    /// `CHAPTER001=00:00:00.000` and `CHAPTER001NAME` Vorbis comments, used by Ogg and FLAC
+    /// files. Use [`try_seek`](Source::try_seek) with their start to jump to a chapter. Returns
+    /// an empty list if the file has no chapters. Only the Symphonia decoders read chapters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use rodio::{Decoder, Source};
+    ///
+    /// let file = File::open("audiobook.mp3").unwrap();
+    /// let mut decoder = Decoder::try_from(file).unwrap();
+    /// for chapter in decoder.chapters() {
+    ///     println!("{:?}: {}", chapter.start, chapter.title);
+    /// }
+    /// if let Some(second) = decoder.chapters().get(1) {
+    ///     decoder.try_seek(second.start).unwrap();
+    /// }
+    /// ```
+    pub fn chapters(&self) -> Vec<Chapter> {
+        self.0.inner().inner().chapters().to_vec()
+    }
+
+    /// Returns the loop of an instrument sample as its first frame and the frame after it,
+    /// `None` if the file has no loop.
+    ///
+    /// The loop is the first one in the `smpl` chunk of a WAV file, where samplers store the
+    /// part of a note to repeat while it is held. Only the Symphonia decoders read loops.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use rodio::{Decoder, Source};
+    ///
+    /// let file = File::open("note.wav").unwrap();
+    /// let decoder = Decoder::try_from(file).unwrap();
+    /// if let Some((start, end)) = decoder.loop_points() {
+    ///     println!("loop from frame {start} until frame {end}");
+    /// }
+    /// ```
+    pub fn loop_points(&self) -> Option<(u64, u64)> {
+        self.0.inner().inner().loop_points()
+    }
+
+    /// Returns the ReplayGain of the track in dB, `None` if the file has no such tag.
+    ///
+    /// Playing every track with its gain applied, for example with
+    /// [`Source::apply_replay_gain`], makes them about equally loud. The gain is read from
+    /// `REPLAYGAIN_TRACK_GAIN` tags in ID3v2, Vorbis comments (FLAC, Ogg) and MP4 metadata, or
+    /// from the `R128_TRACK_GAIN` tag of Opus files, converted to the ReplayGain reference
+    /// level. Only the Symphonia decoders read tags.
+    pub fn replay_gain(&self) -> Option<Float> {
+        self.0.inner().inner().replay_gain()
+    }
+
+    /// Returns the peak sample value of the track from its `REPLAYGAIN_TRACK_PEAK` tag, `1.0`
+    /// being full scale. See [`replay_gain`](Decoder::replay_gain).
+    pub fn replay_gain_peak(&self) -> Option<Float> {
+        self.0.inner().inner().replay_gain_peak()
+    }
+
+    /// Returns the short name of the codec the audio is decoded with, such as `"mp3"`,
+    /// `"flac"`, `"vorbis"`, `"aac"` or `"pcm_s16le"`, or `"unknown"` if the decoder has no
+    /// name for it.
+    ///
+    /// This is meant to be shown to users, the names of the Symphonia decoders are more
+    /// detailed than those of the alternative decoders.
+    pub fn codec_name(&self) -> &'static str {
+        self.0.inner().inner().codec_name()
+    }
+
+    /// Returns the bitrate of the audio in bits per second, `None` if it is not known.
+    ///
+    /// For uncompressed audio this is exact. For other formats it is the average over the
+    /// whole stream, computed from the [byte length](DecoderBuilder::with_byte_len) and the
+    /// [total duration](Source::total_duration), so it is only known if both are. Tags and
+    /// embedded cover art are counted too. Only the Symphonia decoders report a bitrate.
+    pub fn bitrate(&self) -> Option<u32> {
+        self.0.inner().inner().bitrate()
+    }
+
+    /// Decodes the rest of the stream into memory, to play it many times without decoding it
+    /// again.
+    ///
+    /// Every [`SharedBuffer::source`] plays the samples from the start with its own position,
+    /// while they all share the memory. This suits short sounds that are played often, like
+    /// sound effects in games. If the sample rate or channel count change within the stream,
+    /// the samples are converted to those of the start.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use rodio::{Decoder, Player};
+    ///
+    /// let file = File::open("explosion.wav").unwrap();
+    /// let explosion = Decoder::try_from(file).unwrap().into_shared_buffer();
+    ///
+    /// let (player, _output) = Player::new();
+    /// for _ in 0..100 {
+    ///     player.append(explosion.source());
+    /// }
+    /// ```
+    pub fn into_shared_buffer(self) -> SharedBuffer {
+        SamplesBuffer::record_source(self).into()
+    }
+}
+
 impl<R> Iterator for Decoder<R>
 where
     R: Read + Seek,
@@ -601,10 +1065,20 @@ where
         self.0.total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.0.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.0.try_seek(pos)
     }
+
+    #[inline]
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), SeekError> {
+        self.0.try_seek_frame(frame)
+    }
 }
 
 impl<R> Iterator for LoopedDecoder<R>
@@ -747,6 +1221,11 @@ where
         None
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner.as_ref().is_some_and(|inner| inner.is_seekable())
+    }
+
     /// Attempts to seek to a specific position in the audio stream.
     ///
     /// # Errors
@@ -765,8 +1244,8 @@ where
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         match &mut self.inner {
             Some(inner) => inner.try_seek(pos),
-            None => Err(SeekError::Other(Arc::new(DecoderError::IoError(
-                "Looped source ended when it failed to loop back".to_string(),
+            None => Err(SeekError::Other(Arc::new(DecoderError::from(
+                std::io::Error::other("Looped source ended when it failed to loop back"),
             )))),
         }
     }
@@ -781,7 +1260,7 @@ pub enum DecoderError {
 
     /// An IO error occurred while reading, writing, or seeking the stream.
     #[error("An IO error occurred while reading, writing, or seeking the stream.")]
-    IoError(String),
+    IoError(#[source] Arc<std::io::Error>),
 
     /// The stream contained malformed data and could not be decoded or demuxed.
     #[error("The stream contained malformed data and could not be decoded or demuxed: {0}")]
@@ -808,3 +1287,9 @@ pub enum DecoderError {
     NoStreams,
 }
 assert_error_traits!(DecoderError);
+
+impl From<std::io::Error> for DecoderError {
+    fn from(source: std::io::Error) -> Self {
+        DecoderError::IoError(Arc::new(source))
+    }
+}
@@ -0,0 +1,223 @@
+use std::io::{BufReader, Read};
+use std::time::Duration;
+
+use dasp_sample::FromSample;
+
+use crate::common::{ChannelCount, Float, SampleRate};
+use crate::{Sample, Source};
+
+/// The type of a single sample in raw PCM data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcmSampleType {
+    /// Unsigned 8 bit integer, silence is `128`.
+    U8,
+    /// Signed 16 bit integer.
+    I16,
+    /// Signed 24 bit integer packed into 3 bytes.
+    I24,
+    /// Signed 32 bit integer.
+    I32,
+    /// 32 bit float, full scale is `-1.0..=1.0`.
+    F32,
+    /// 64 bit float, full scale is `-1.0..=1.0`.
+    F64,
+}
+
+impl PcmSampleType {
+    /// Returns the number of bytes a sample of this type takes.
+    pub fn bytes(self) -> usize {
+        match self {
+            PcmSampleType::U8 => 1,
+            PcmSampleType::I16 => 2,
+            PcmSampleType::I24 => 3,
+            PcmSampleType::I32 | PcmSampleType::F32 => 4,
+            PcmSampleType::F64 => 8,
+        }
+    }
+}
+
+/// The byte order of the samples in raw PCM data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least significant byte first, used by WAV files and most hardware.
+    Little,
+    /// Most significant byte first, used by AIFF files and network protocols.
+    Big,
+}
+
+/// Describes the layout of raw PCM data, see [`RawPcmSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmFormat {
+    /// The type of every sample.
+    pub sample_type: PcmSampleType,
+    /// The number of interleaved channels.
+    pub channels: ChannelCount,
+    /// The number of frames per second.
+    pub sample_rate: SampleRate,
+    /// The byte order of every sample.
+    pub endianness: Endianness,
+}
+
+/// Source that reads raw interleaved PCM from any reader, such as the output of another program
+/// on a pipe.
+///
+/// The samples are converted to [`Sample`] while playing. Integer samples are scaled so their
+/// full range maps to `-1.0..1.0`. The source ends when the reader does, an incomplete frame at
+/// the end is dropped. It also ends on the first read error. Raw PCM has no header, so the
+/// [`PcmFormat`] must match the data, otherwise only noise is heard.
+///
+/// # Example
+///
+/// ```
+/// use rodio::decoder::{Endianness, PcmFormat, PcmSampleType, RawPcmSource};
+/// use rodio::{nz, Source};
+///
+/// let format = PcmFormat {
+///     sample_type: PcmSampleType::I16,
+///     channels: nz!(1),
+///     sample_rate: nz!(44100),
+///     endianness: Endianness::Little,
+/// };
+/// let bytes: &[u8] = &[0x00, 0x40, 0x00, 0xc0];
+/// let source = RawPcmSource::new(bytes, format);
+/// assert_eq!(source.collect::<Vec<_>>(), [0.5, -0.5]);
+/// ```
+pub struct RawPcmSource<R> {
+    reader: BufReader<R>,
+    format: PcmFormat,
+    // The bytes of the current frame.
+    frame: Vec<u8>,
+    // Index of the next sample to play from `frame`.
+    channel: usize,
+    ended: bool,
+}
+
+impl<R: Read> RawPcmSource<R> {
+    /// Builds a source that reads PCM laid out as in `format` from `reader`.
+    ///
+    /// The reader is buffered, so it does not have to be.
+    pub fn new(reader: R, format: PcmFormat) -> Self {
+        let channels = format.channels.get() as usize;
+        Self {
+            reader: BufReader::new(reader),
+            format,
+            frame: vec![0; channels * format.sample_type.bytes()],
+            channel: channels,
+            ended: false,
+        }
+    }
+
+    /// Returns the layout the data is read with.
+    pub fn format(&self) -> PcmFormat {
+        self.format
+    }
+}
+
+/// Copies the bytes of a sample to an array in little-endian order.
+fn le_bytes<const N: usize>(bytes: &[u8], endianness: Endianness) -> [u8; N] {
+    let mut array: [u8; N] = bytes.try_into().expect("sample has the size of its type");
+    if endianness == Endianness::Big {
+        array.reverse();
+    }
+    array
+}
+
+fn decode(bytes: &[u8], format: &PcmFormat) -> Sample {
+    let endianness = format.endianness;
+    match format.sample_type {
+        PcmSampleType::U8 => Sample::from_sample_(bytes[0]),
+        PcmSampleType::I16 => Sample::from_sample_(i16::from_le_bytes(le_bytes(bytes, endianness))),
+        PcmSampleType::I24 => {
+            let [low, mid, high] = le_bytes(bytes, endianness);
+            // Shifting back sign extends the 24 bit value.
+            let value = i32::from_le_bytes([0, low, mid, high]) >> 8;
+            value as Float / (1 << 23) as Float
+        }
+        PcmSampleType::I32 => Sample::from_sample_(i32::from_le_bytes(le_bytes(bytes, endianness))),
+        PcmSampleType::F32 => Sample::from_sample_(f32::from_le_bytes(le_bytes(bytes, endianness))),
+        PcmSampleType::F64 => Sample::from_sample_(f64::from_le_bytes(le_bytes(bytes, endianness))),
+    }
+}
+
+impl<R: Read> Iterator for RawPcmSource<R> {
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.ended {
+            return None;
+        }
+        if self.channel == self.format.channels.get() as usize {
+            // Also fails on an incomplete frame at the end, which is dropped.
+            if self.reader.read_exact(&mut self.frame).is_err() {
+                self.ended = true;
+                return None;
+            }
+            self.channel = 0;
+        }
+
+        let size = self.format.sample_type.bytes();
+        let start = self.channel * size;
+        self.channel += 1;
+        Some(decode(&self.frame[start..start + size], &self.format))
+    }
+}
+
+impl<R: Read> Source for RawPcmSource<R> {
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.format.channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.format.sample_rate
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::nz;
+
+    fn format(sample_type: PcmSampleType, endianness: Endianness) -> PcmFormat {
+        PcmFormat {
+            sample_type,
+            channels: nz!(2),
+            sample_rate: nz!(8000),
+            endianness,
+        }
+    }
+
+    #[test]
+    fn reads_little_endian_i16() {
+        let mut bytes = Vec::new();
+        for sample in [0i16, 16384, -16384, i16::MIN] {
+            bytes.extend(sample.to_le_bytes());
+        }
+        // Half a frame at the end is dropped.
+        bytes.extend(1000i16.to_le_bytes());
+
+        let format = format(PcmSampleType::I16, Endianness::Little);
+        let samples: Vec<Sample> = RawPcmSource::new(bytes.as_slice(), format).collect();
+        assert_eq!(samples, [0.0, 0.5, -0.5, -1.0]);
+    }
+
+    #[test]
+    fn reads_big_endian_i24() {
+        let bytes: &[u8] = &[0x40, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x7f];
+        let format = format(PcmSampleType::I24, Endianness::Big);
+        let samples: Vec<Sample> = RawPcmSource::new(bytes, format).collect();
+        assert_eq!(samples, [0.5, -0.5]);
+    }
+}
@@ -6,20 +6,21 @@ use std::{
 use symphonia::{
     core::{
         audio::{AudioBufferRef, SampleBuffer, SignalSpec},
-        codecs::{CodecRegistry, Decoder, DecoderOptions, CODEC_TYPE_NULL},
+        codecs::{CodecParameters, CodecRegistry, Decoder, DecoderOptions, CODEC_TYPE_NULL},
         errors::Error,
         formats::{FormatOptions, FormatReader, SeekMode, SeekTo, SeekedTo},
         io::MediaSourceStream,
-        meta::MetadataOptions,
+        meta::{MetadataOptions, StandardTagKey, Tag, Value},
         probe::Hint,
-        units,
+        units::{self, TimeBase},
     },
     default::get_probe,
 };
 
-use super::{DecoderError, Settings};
+use super::{chapters, loop_points, Chapter, DecoderError, GaplessInfo, Settings};
 use crate::{
-    common::{assert_error_traits, ChannelCount, Sample, SampleRate},
+    common::{assert_error_traits, ChannelCount, Float, Sample, SampleRate},
+    math::frame_to_duration,
     source::{self, padding_samples_needed},
     Source,
 };
@@ -59,13 +60,58 @@ pub(crate) struct SymphoniaDecoder {
     selected_track_id: u32,
     samples_in_current_frame: usize,
     silence_samples_remaining: usize,
+    // The part of `buffer` that is played, smaller than the buffer when frames are trimmed.
+    span_start: usize,
+    span_end: usize,
+    gapless_info: Option<GaplessInfo>,
+    trim: Option<Trim>,
+    replay_gain: Option<Float>,
+    replay_gain_peak: Option<Float>,
+    chapters: Vec<Chapter>,
+    loop_points: Option<(u64, u64)>,
+    codec_name: &'static str,
+    bitrate: Option<u32>,
+    seekable: bool,
+}
+
+/// Timestamps of the frames to play, for formats that Symphonia does not trim itself.
+#[derive(Clone, Copy, Debug)]
+struct Trim {
+    start: u64,
+    end: u64,
+}
+
+impl Trim {
+    /// Returns the range of frames to play from a packet of `frames` frames starting at `ts`.
+    fn frames(&self, ts: u64, frames: usize) -> (usize, usize) {
+        let start = self.start.saturating_sub(ts).min(frames as u64) as usize;
+        let end = self.end.saturating_sub(ts).min(frames as u64) as usize;
+        (start, end.max(start))
+    }
 }
 
 impl SymphoniaDecoder {
     pub(crate) fn new(mss: MediaSourceStream, settings: &Settings) -> Result<Self, DecoderError> {
-        match SymphoniaDecoder::init(mss, settings) {
+        let mut hint = Hint::new();
+        if let Some(ext) = settings.hint.as_ref() {
+            hint.with_extension(ext);
+        }
+        if let Some(typ) = settings.mime_type.as_ref() {
+            hint.mime_type(typ);
+        }
+        Self::with_hint(mss, &hint, settings)
+    }
+
+    /// Like [`new`](Self::new), but probes the format with `hint` instead of the hints in the
+    /// settings.
+    pub(crate) fn with_hint(
+        mss: MediaSourceStream,
+        hint: &Hint,
+        settings: &Settings,
+    ) -> Result<Self, DecoderError> {
+        match SymphoniaDecoder::init(mss, hint, settings) {
             Err(e) => match e {
-                Error::IoError(e) => Err(DecoderError::IoError(e.to_string())),
+                Error::IoError(e) => Err(e.into()),
                 Error::DecodeError(e) => Err(DecoderError::DecodeError(e)),
                 Error::SeekError(_) => {
                     unreachable!("Seek errors should not occur during initialization")
@@ -85,16 +131,10 @@ impl SymphoniaDecoder {
     }
 
     fn init(
-        mss: MediaSourceStream,
+        mut mss: MediaSourceStream,
+        hint: &Hint,
         settings: &Settings,
     ) -> symphonia::core::errors::Result<Option<SymphoniaDecoder>> {
-        let mut hint = Hint::new();
-        if let Some(ext) = settings.hint.as_ref() {
-            hint.with_extension(ext);
-        }
-        if let Some(typ) = settings.mime_type.as_ref() {
-            hint.mime_type(typ);
-        }
         let format_opts: FormatOptions = FormatOptions {
             enable_gapless: settings.gapless,
             ..Default::default()
@@ -105,7 +145,33 @@ impl SymphoniaDecoder {
         } else {
             SeekMode::Accurate
         };
-        let mut probed = get_probe().format(&hint, mss, &format_opts, &metadata_opts)?;
+        // Symphonia skips the chapter frames of ID3v2 tags, so they are read separately.
+        let id3_chapters = chapters::read_id3v2_chapters(&mut mss);
+        // The same goes for the `smpl` chunk of WAV files.
+        let loop_points = loop_points::read_wav_loop_points(&mut mss);
+        let mut probed = get_probe().format(hint, mss, &format_opts, &metadata_opts)?;
+
+        // Tags in front of the stream, like ID3v2 in MP3 files, and tags of the container.
+        let mut tags: Vec<Tag> = Vec::new();
+        if let Some(revision) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+            tags.extend_from_slice(revision.tags());
+        }
+        if let Some(revision) = probed.format.metadata().current() {
+            tags.extend_from_slice(revision.tags());
+        }
+
+        let itunsmpb = tags
+            .iter()
+            .find(|tag| tag.key.ends_with("iTunSMPB"))
+            .and_then(|tag| match &tag.value {
+                Value::String(value) => parse_itunsmpb(value),
+                _ => None,
+            });
+        let (replay_gain, replay_gain_peak) = read_replay_gain(&tags);
+        let mut chapters = chapters::read_vorbis_chapters(&tags);
+        if chapters.is_empty() {
+            chapters = id3_chapters;
+        }
 
         let stream = match probed.format.default_track() {
             Some(stream) => stream,
@@ -122,23 +188,60 @@ impl SymphoniaDecoder {
                 "No track with supported codec",
             ))?;
         let track_id = track.id;
+        let codec_name = settings
+            .codec_registry
+            .read()
+            .get_codec(track.codec_params.codec)
+            .map_or("unknown", |codec| codec.short_name);
 
         let mut decoder = settings
             .codec_registry
             .read()
             .make(&track.codec_params, &DecoderOptions::default())?;
 
+        // Symphonia trims MP3 files itself using the delay and padding from the LAME tag. AAC in
+        // MP4 files stores them in an iTunes tag instead, those are trimmed here.
+        let mut gapless_info = match (track.codec_params.delay, track.codec_params.padding) {
+            (None, None) => None,
+            (delay, padding) => Some(GaplessInfo {
+                delay: delay.unwrap_or(0),
+                padding: padding.unwrap_or(0),
+            }),
+        };
+        let mut trim = None;
+        if gapless_info.is_none() {
+            if let Some((info, frames)) = itunsmpb {
+                gapless_info = Some(info);
+                // Timestamps are only frames if the time base is one over the sample rate.
+                let frame_time_base = track
+                    .codec_params
+                    .sample_rate
+                    .map(|rate| TimeBase::new(1, rate));
+                if settings.gapless && track.codec_params.time_base == frame_time_base {
+                    trim = Some(Trim {
+                        start: info.delay.into(),
+                        end: u64::from(info.delay) + frames,
+                    });
+                }
+            }
+        }
+
         let total_duration = track
             .codec_params
             .time_base
-            .zip(stream.codec_params.n_frames)
+            .zip(match trim {
+                Some(trim) => Some(trim.end - trim.start),
+                None => stream.codec_params.n_frames,
+            })
             .map(|(base, spans)| base.calc_time(spans).into())
             .filter(|d: &Duration| !d.is_zero());
+        let bitrate = pcm_bitrate(&track.codec_params, codec_name)
+            .or_else(|| average_bitrate(settings.byte_len?, total_duration?));
 
-        let decoded = loop {
+        let (decoded, ts) = loop {
             let current_span = match probed.format.next_packet() {
                 Ok(packet) => packet,
-                Err(Error::IoError(_)) => break decoder.last_decoded(),
+                Err(Error::IoError(_)) => break (decoder.last_decoded(), 0),
                 Err(e) => return Err(e),
             };
 
@@ -165,13 +268,18 @@ impl SymphoniaDecoder {
             // which case the iterator would otherwise end with `None`.
             // Note: checking `decoded.frames()` is more reliable than `packet.dur()`, which
             // can resturn non-zero durations for packets without audio frames.
-            if decoded.frames() > 0 {
-                break decoded;
+            // Packets that are trimmed completely are skipped too, to not end right away.
+            let trimmed = trim.is_some_and(|trim| {
+                let (start, end) = trim.frames(current_span.ts(), decoded.frames());
+                start == end
+            });
+            if decoded.frames() > 0 && !trimmed {
+                break (decoded, current_span.ts());
             }
         };
         let spec = decoded.spec().to_owned();
         let buffer = SymphoniaDecoder::get_buffer(decoded, &spec);
-        Ok(Some(SymphoniaDecoder {
+        let mut decoder = SymphoniaDecoder {
             decoder,
             current_span_offset: 0,
             format: probed.format,
@@ -182,7 +290,68 @@ impl SymphoniaDecoder {
             selected_track_id: track_id,
             samples_in_current_frame: 0,
             silence_samples_remaining: 0,
-        }))
+            span_start: 0,
+            span_end: 0,
+            gapless_info,
+            trim,
+            replay_gain,
+            replay_gain_peak,
+            chapters,
+            loop_points,
+            codec_name,
+            bitrate,
+            seekable: settings.is_seekable,
+        };
+        decoder.start_span(ts);
+        Ok(Some(decoder))
+    }
+
+    #[inline]
+    pub(crate) fn gapless_info(&self) -> Option<GaplessInfo> {
+        self.gapless_info
+    }
+
+    #[inline]
+    pub(crate) fn chapters(&self) -> &[Chapter] {
+        &self.chapters
+    }
+
+    #[inline]
+    pub(crate) fn loop_points(&self) -> Option<(u64, u64)> {
+        self.loop_points
+    }
+
+    #[inline]
+    pub(crate) fn replay_gain(&self) -> Option<Float> {
+        self.replay_gain
+    }
+
+    #[inline]
+    pub(crate) fn replay_gain_peak(&self) -> Option<Float> {
+        self.replay_gain_peak
+    }
+
+    #[inline]
+    pub(crate) fn codec_name(&self) -> &'static str {
+        self.codec_name
+    }
+
+    #[inline]
+    pub(crate) fn bitrate(&self) -> Option<u32> {
+        self.bitrate
+    }
+
+    /// Plays the new buffer, without the frames that are trimmed.
+    fn start_span(&mut self, ts: u64) {
+        let channels = self.spec.channels.count();
+        let frames = self.buffer.len() / channels;
+        let (start, end) = match self.trim {
+            Some(trim) => trim.frames(ts, frames),
+            None => (0, frames),
+        };
+        self.span_start = start * channels;
+        self.span_end = end * channels;
+        self.current_span_offset = self.span_start;
     }
 
     #[inline]
@@ -197,7 +366,7 @@ impl SymphoniaDecoder {
 impl Source for SymphoniaDecoder {
     #[inline]
     fn current_span_len(&self) -> Option<usize> {
-        Some(self.buffer.len())
+        Some(self.span_end - self.span_start)
     }
 
     #[inline]
@@ -222,15 +391,12 @@ impl Source for SymphoniaDecoder {
         self.total_duration
     }
 
-    fn try_seek(&mut self, pos: Duration) -> Result<(), source::SeekError> {
-        if matches!(self.seek_mode, SeekMode::Accurate)
-            && self.decoder.codec_params().time_base.is_none()
-        {
-            return Err(source::SeekError::SymphoniaDecoder(
-                SeekError::AccurateSeekNotSupported,
-            ));
-        }
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.seekable
+    }
 
+    fn try_seek(&mut self, pos: Duration) -> Result<(), source::SeekError> {
         // Seeking should be "saturating", meaning: target positions beyond the end of the stream
         // are clamped to the end.
         let mut target = pos;
@@ -239,22 +405,95 @@ impl Source for SymphoniaDecoder {
                 target = total_duration;
             }
         }
+        // Timestamps include the trimmed priming frames.
+        if let (Some(trim), Some(time_base)) = (self.trim, self.decoder.codec_params().time_base) {
+            target += Duration::from(time_base.calc_time(trim.start));
+        }
 
-        // Remember the current channel, so we can restore it after seeking.
-        let active_channel = self.current_span_offset % self.channels().get() as usize;
-
-        let seek_res = match self.format.seek(
-            self.seek_mode,
+        self.seek_to(
             SeekTo::Time {
                 time: target.into(),
                 track_id: None,
             },
-        ) {
+            pos,
+        )
+    }
+
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), source::SeekError> {
+        let pos = frame_to_duration(frame, self.sample_rate());
+        // Timestamps are only frames if the time base is one over the sample rate.
+        let frame_time_base = TimeBase::new(1, self.sample_rate().get());
+        if self.decoder.codec_params().time_base != Some(frame_time_base)
+            || self.total_duration.is_some_and(|total| pos > total)
+        {
+            return self.try_seek(pos);
+        }
+
+        // Timestamps include the trimmed priming frames.
+        let ts = frame + self.trim.map_or(0, |trim| trim.start);
+        self.seek_to(
+            SeekTo::TimeStamp {
+                ts,
+                track_id: self.selected_track_id,
+            },
+            pos,
+        )
+    }
+}
+
+/// Error returned when the try_seek implementation of the symphonia decoder fails.
+#[derive(Debug, thiserror::Error, Clone)]
+pub enum SeekError {
+    /// Accurate seeking is not supported
+    ///
+    /// This error occurs when the decoder cannot extract time base information from the source.
+    /// You may catch this error to try a coarse seek instead.
+    #[error("Accurate seeking is not supported on this file/byte stream that lacks time base information")]
+    AccurateSeekNotSupported,
+    /// The decoder does not support random access seeking
+    ///
+    /// This error occurs when the source is not seekable or does not have a known byte length.
+    #[error("The decoder needs to know the length of the file/byte stream to be able to seek backwards. You can set that by using the `DecoderBuilder` or creating a decoder using `Decoder::try_from(some_file)`.")]
+    RandomAccessNotSupported,
+    /// Demuxer failed to seek
+    #[error("Demuxer failed to seek")]
+    Demuxer(#[source] Arc<symphonia::core::errors::Error>),
+}
+assert_error_traits!(SeekError);
+
+impl SymphoniaDecoder {
+    /// Seeks the demuxer and decodes up to the requested position, `pos` is only used for
+    /// errors.
+    fn seek_to(&mut self, to: SeekTo, pos: Duration) -> Result<(), source::SeekError> {
+        if matches!(self.seek_mode, SeekMode::Accurate)
+            && self.decoder.codec_params().time_base.is_none()
+        {
+            return Err(source::SeekError::SymphoniaDecoder(
+                SeekError::AccurateSeekNotSupported,
+            ));
+        }
+
+        // Remember the current channel, so we can restore it after seeking.
+        let active_channel = self.current_span_offset % self.channels().get() as usize;
+
+        let seek_res = match self.format.seek(self.seek_mode, to) {
             Err(Error::SeekError(symphonia::core::errors::SeekErrorKind::ForwardOnly)) => {
                 return Err(source::SeekError::SymphoniaDecoder(
                     SeekError::RandomAccessNotSupported,
                 ));
             }
+            Err(Error::SeekError(symphonia::core::errors::SeekErrorKind::OutOfRange)) => {
+                return Err(source::SeekError::OutOfRange {
+                    requested: pos,
+                    total: self.total_duration,
+                });
+            }
+            Err(Error::SeekError(symphonia::core::errors::SeekErrorKind::Unseekable)) => {
+                return Err(source::SeekError::NotSupported {
+                    underlying_source: std::any::type_name::<Self>(),
+                });
+            }
+            Err(Error::IoError(err)) => return Err(err.into()),
             other => other.map_err(Arc::new).map_err(SeekError::Demuxer),
         }?;
 
@@ -280,43 +519,29 @@ impl Source for SymphoniaDecoder {
 
         Ok(())
     }
-}
 
-/// Error returned when the try_seek implementation of the symphonia decoder fails.
-#[derive(Debug, thiserror::Error, Clone)]
-pub enum SeekError {
-    /// Accurate seeking is not supported
-    ///
-    /// This error occurs when the decoder cannot extract time base information from the source.
-    /// You may catch this error to try a coarse seek instead.
-    #[error("Accurate seeking is not supported on this file/byte stream that lacks time base information")]
-    AccurateSeekNotSupported,
-    /// The decoder does not support random access seeking
-    ///
-    /// This error occurs when the source is not seekable or does not have a known byte length.
-    #[error("The decoder needs to know the length of the file/byte stream to be able to seek backwards. You can set that by using the `DecoderBuilder` or creating a decoder using `Decoder::try_from(some_file)`.")]
-    RandomAccessNotSupported,
-    /// Demuxer failed to seek
-    #[error("Demuxer failed to seek")]
-    Demuxer(#[source] Arc<symphonia::core::errors::Error>),
-}
-assert_error_traits!(SeekError);
-
-impl SymphoniaDecoder {
     /// Note span offset must be set after
     fn refine_position(&mut self, seek_res: SeekedTo) -> Result<(), source::SeekError> {
-        // Calculate the number of samples to skip.
-        let mut samples_to_skip = (Duration::from(
-            self.decoder
-                .codec_params()
-                .time_base
-                .expect("time base availability guaranteed by caller")
-                .calc_time(seek_res.required_ts.saturating_sub(seek_res.actual_ts)),
-        )
-        .as_secs_f32()
-            * self.sample_rate().get() as f32
-            * self.channels().get() as f32)
-            .ceil() as usize;
+        // Calculate the number of samples to skip. Trimmed frames are skipped anyway.
+        let actual_ts = match self.trim {
+            Some(trim) => seek_res.actual_ts.max(trim.start),
+            None => seek_res.actual_ts,
+        };
+        let time_base = self
+            .decoder
+            .codec_params()
+            .time_base
+            .expect("time base availability guaranteed by caller");
+        let ts_to_skip = seek_res.required_ts.saturating_sub(actual_ts);
+        let mut samples_to_skip = if time_base == TimeBase::new(1, self.sample_rate().get()) {
+            // Timestamps are frames, no need to go through a Duration.
+            ts_to_skip as usize * self.channels().get() as usize
+        } else {
+            (Duration::from(time_base.calc_time(ts_to_skip)).as_secs_f32()
+                * self.sample_rate().get() as f32
+                * self.channels().get() as f32)
+                .ceil() as usize
+        };
 
         // Re-align the seek position to the first channel.
         samples_to_skip -= samples_to_skip % self.channels().get() as usize;
@@ -341,7 +566,7 @@ impl Iterator for SymphoniaDecoder {
                 return Some(Sample::EQUILIBRIUM);
             }
 
-            if self.current_span_offset >= self.buffer.len() {
+            if self.current_span_offset >= self.span_end {
                 let decoded = loop {
                     let packet = match self.format.next_packet() {
                         Ok(packet) => {
@@ -390,15 +615,15 @@ impl Iterator for SymphoniaDecoder {
                     // Note: checking `decoded.frames()` is more reliable than `packet.dur()`, which
                     // can resturn non-zero durations for packets without audio frames.
                     if decoded.frames() > 0 {
-                        break Some(decoded);
+                        break Some((decoded, packet.ts()));
                     }
                 };
 
                 match decoded {
-                    Some(decoded) => {
+                    Some((decoded, ts)) => {
                         decoded.spec().clone_into(&mut self.spec);
                         self.buffer = SymphoniaDecoder::get_buffer(decoded, &self.spec);
-                        self.current_span_offset = 0;
+                        self.start_span(ts);
                     }
                     None => {
                         // Break out happened due to exhaustion, continue to emit padding
@@ -407,6 +632,10 @@ impl Iterator for SymphoniaDecoder {
                 }
             }
 
+            if self.current_span_offset >= self.span_end {
+                // Every frame of the packet was trimmed.
+                continue;
+            }
             let sample = *self.buffer.samples().get(self.current_span_offset)?;
             self.current_span_offset += 1;
 
@@ -418,3 +647,116 @@ impl Iterator for SymphoniaDecoder {
         }
     }
 }
+
+/// Parses an iTunes `iTunSMPB` tag, as written into MP4 files by iTunes and most AAC encoders.
+///
+/// The tag holds hexadecimal numbers, the second is the delay, the third the padding and the
+/// fourth the number of frames without delay and padding. Returns the gapless info and the
+/// number of frames.
+fn parse_itunsmpb(value: &str) -> Option<(GaplessInfo, u64)> {
+    let mut fields = value
+        .split_whitespace()
+        .map(|field| u64::from_str_radix(field, 16).ok());
+    let _reserved = fields.next()??;
+    let delay = fields.next()??.try_into().ok()?;
+    let padding = fields.next()??.try_into().ok()?;
+    let frames = fields.next()??;
+    (frames > 0).then_some((GaplessInfo { delay, padding }, frames))
+}
+
+/// Returns the exact bitrate of uncompressed PCM audio in bits per second.
+fn pcm_bitrate(params: &CodecParameters, codec_name: &str) -> Option<u32> {
+    if !codec_name.starts_with("pcm_") {
+        return None;
+    }
+    let bits = params.bits_per_coded_sample.or(params.bits_per_sample)?;
+    let channels = params.channels?.count() as u32;
+    bits.checked_mul(params.sample_rate?)?.checked_mul(channels)
+}
+
+/// Returns the average bitrate of a stream in bits per second. This includes the container and
+/// tags, which makes it slightly too high.
+fn average_bitrate(byte_len: u64, duration: Duration) -> Option<u32> {
+    let bits_per_second = (byte_len * 8) as f64 / duration.as_secs_f64();
+    (bits_per_second.is_finite() && bits_per_second <= u32::MAX as f64)
+        .then_some(bits_per_second.round() as u32)
+}
+
+/// Reads the track gain in dB and the track peak from `REPLAYGAIN_TRACK_GAIN` and
+/// `REPLAYGAIN_TRACK_PEAK` tags, or the gain from an Opus `R128_TRACK_GAIN` tag.
+fn read_replay_gain(tags: &[Tag]) -> (Option<Float>, Option<Float>) {
+    /// R128 gains are relative to -23 LUFS, ReplayGain to about -18 LUFS.
+    const R128_TO_REPLAY_GAIN: Float = 5.0;
+
+    let value = |tag: &Tag| match &tag.value {
+        // Gains are usually written like "-6.50 dB".
+        Value::String(value) => value
+            .trim()
+            .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+            .trim()
+            .parse()
+            .ok(),
+        Value::Float(value) => Some(*value as Float),
+        _ => None,
+    };
+    let find = |key| {
+        tags.iter()
+            .filter(|tag| tag.std_key == Some(key))
+            .find_map(value)
+    };
+
+    let gain = find(StandardTagKey::ReplayGainTrackGain).or_else(|| {
+        tags.iter()
+            .filter(|tag| tag.key.eq_ignore_ascii_case("R128_TRACK_GAIN"))
+            .find_map(value)
+            // Stored in 1/256 dB.
+            .map(|gain| gain / 256.0 + R128_TO_REPLAY_GAIN)
+    });
+    (gain, find(StandardTagKey::ReplayGainTrackPeak))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_itunsmpb() {
+        let tag = " 00000000 00000840 000001CA 0000000000A3B5F6 00000000 00000000 00000000";
+        let (info, frames) = parse_itunsmpb(tag).unwrap();
+        assert_eq!(
+            info,
+            GaplessInfo {
+                delay: 2112,
+                padding: 458
+            }
+        );
+        assert_eq!(frames, 0xA3B5F6);
+
+        assert!(parse_itunsmpb("not a tag").is_none());
+        assert!(parse_itunsmpb(" 00000000 00000840").is_none());
+    }
+
+    #[test]
+    fn reads_replay_gain_tags() {
+        let tag = |key: &str, std_key, value: &str| Tag::new(std_key, key, Value::from(value));
+
+        let tags = [
+            tag(
+                "REPLAYGAIN_TRACK_GAIN",
+                Some(StandardTagKey::ReplayGainTrackGain),
+                "-6.50 dB",
+            ),
+            tag(
+                "REPLAYGAIN_TRACK_PEAK",
+                Some(StandardTagKey::ReplayGainTrackPeak),
+                "0.988",
+            ),
+        ];
+        assert_eq!(read_replay_gain(&tags), (Some(-6.5), Some(0.988)));
+
+        let tags = [tag("R128_TRACK_GAIN", None, "-512")];
+        assert_eq!(read_replay_gain(&tags), (Some(3.0), None));
+
+        assert_eq!(read_replay_gain(&[]), (None, None));
+    }
+}
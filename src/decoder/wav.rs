@@ -204,6 +204,11 @@ where
         Some(self.total_duration)
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         let file_len = self.reader.reader.duration();
@@ -215,11 +220,7 @@ where
         // make sure the next sample is for the right channel
         let to_skip = self.reader.samples_read % self.channels().get() as u32;
 
-        self.reader
-            .reader
-            .seek(new_pos)
-            .map_err(Arc::new)
-            .map_err(SeekError::HoundDecoder)?;
+        self.reader.reader.seek(new_pos)?;
         self.reader.samples_read = new_pos * self.channels().get() as u32;
 
         for _ in 0..to_skip {
@@ -0,0 +1,203 @@
+//! Render sources to audio files without playing them.
+//!
+//! This is useful to bounce a chain of sources offline, as fast as the CPU allows.
+//!
+//! # Example
+//! ```no_run
+//! use std::time::Duration;
+//! use rodio::source::{SineWave, Source};
+//!
+//! let source = SineWave::new(440.0)
+//!     .amplify(0.2)
+//!     .take_duration(Duration::from_secs(2));
+//! rodio::encoder::write_wav(source, "sine.wav")?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, Seek, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use dasp_sample::{Sample as DaspSample, I24};
+use hound::{SampleFormat, WavSpec};
+
+use crate::wav_output::WholeFrames;
+use crate::Source;
+
+/// Writes the source to a 16-bit PCM WAV file at the source's channel count and sample rate.
+///
+/// The source is pulled to completion. To protect against writing forever, the source must
+/// report a [`total_duration`](Source::total_duration). Use [`WavEncoder::with_max_duration`]
+/// to write infinite sources or to pick another sample format.
+///
+/// If the file already exists it will be overwritten.
+///
+/// # Errors
+/// Returns an error with kind [`io::ErrorKind::InvalidInput`] if the source has no known
+/// duration, or any error that occurs while writing the file.
+pub fn write_wav(source: impl Source, path: impl AsRef<Path>) -> io::Result<()> {
+    WavEncoder::new().write_to_file(source, path)
+}
+
+/// Format of the samples in a WAV file written by a [`WavEncoder`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WavSampleFormat {
+    /// 16-bit signed integer PCM.
+    #[default]
+    Int16,
+    /// 24-bit signed integer PCM.
+    Int24,
+    /// 32-bit signed integer PCM.
+    Int32,
+    /// 32-bit IEEE float.
+    Float32,
+}
+
+impl WavSampleFormat {
+    fn spec(self, source: &impl Source) -> WavSpec {
+        let (bits_per_sample, sample_format) = match self {
+            WavSampleFormat::Int16 => (16, SampleFormat::Int),
+            WavSampleFormat::Int24 => (24, SampleFormat::Int),
+            WavSampleFormat::Int32 => (32, SampleFormat::Int),
+            WavSampleFormat::Float32 => (32, SampleFormat::Float),
+        };
+        WavSpec {
+            channels: source.channels().get(),
+            sample_rate: source.sample_rate().get(),
+            bits_per_sample,
+            sample_format,
+        }
+    }
+}
+
+/// Writes sources to WAV files, with control over the sample format and length.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WavEncoder {
+    sample_format: WavSampleFormat,
+    max_duration: Option<Duration>,
+}
+
+impl WavEncoder {
+    /// Creates an encoder that writes 16-bit PCM and requires sources with a known duration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the format of the samples in the file. Defaults to [`WavSampleFormat::Int16`].
+    pub fn with_sample_format(mut self, sample_format: WavSampleFormat) -> Self {
+        self.sample_format = sample_format;
+        self
+    }
+
+    /// Stops writing after this duration, even if the source continues.
+    ///
+    /// This also allows writing sources without a known
+    /// [`total_duration`](Source::total_duration), such as [`SineWave`](crate::source::SineWave).
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Writes the source to a file, overwriting it if it already exists.
+    ///
+    /// # Errors
+    /// See [`write_wav`].
+    pub fn write_to_file(&self, source: impl Source, path: impl AsRef<Path>) -> io::Result<()> {
+        self.check_duration(&source)?;
+        let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_to_writer(source, &mut file)?;
+        file.flush()
+    }
+
+    /// Writes the source to a writer.
+    ///
+    /// # Errors
+    /// See [`write_wav`].
+    pub fn write_to_writer(
+        &self,
+        source: impl Source,
+        writer: &mut (impl Write + Seek),
+    ) -> io::Result<()> {
+        self.check_duration(&source)?;
+
+        let spec = self.sample_format.spec(&source);
+        let max_samples = self.max_duration.map_or(usize::MAX, |max_duration| {
+            let frames = max_duration.as_secs_f64() * spec.sample_rate as f64;
+            frames as usize * spec.channels as usize
+        });
+
+        let mut writer = hound::WavWriter::new(writer, spec).map_err(into_io_error)?;
+        for sample in WholeFrames::new(source).take(max_samples) {
+            let sample = sample.clamp(-1.0, 1.0);
+            match self.sample_format {
+                WavSampleFormat::Int16 => writer.write_sample(sample.to_sample::<i16>()),
+                WavSampleFormat::Int24 => writer.write_sample(sample.to_sample::<I24>().inner()),
+                WavSampleFormat::Int32 => writer.write_sample(sample.to_sample::<i32>()),
+                WavSampleFormat::Float32 => writer.write_sample(sample.to_sample::<f32>()),
+            }
+            .map_err(into_io_error)?;
+        }
+        writer.finalize().map_err(into_io_error)
+    }
+
+    fn check_duration(&self, source: &impl Source) -> io::Result<()> {
+        if self.max_duration.is_none() && source.total_duration().is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "source has no known duration, set a maximum duration to write it",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn into_io_error(error: hound::Error) -> io::Error {
+    match error {
+        hound::Error::IoError(error) => error,
+        error => io::Error::other(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::source::SineWave;
+    use crate::{nz, Sample};
+
+    #[test]
+    fn rejects_infinite_sources() {
+        let mut writer = io::Cursor::new(Vec::new());
+        let error = WavEncoder::new()
+            .write_to_writer(SineWave::new(440.0), &mut writer)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        WavEncoder::new()
+            .with_max_duration(Duration::from_millis(10))
+            .write_to_writer(SineWave::new(440.0), &mut writer)
+            .unwrap();
+        let reader = hound::WavReader::new(io::Cursor::new(writer.into_inner())).unwrap();
+        assert_eq!(reader.len(), 480);
+    }
+
+    #[cfg(any(feature = "hound", feature = "wav"))]
+    #[test]
+    fn round_trip() {
+        let samples: Vec<Sample> = SineWave::new(440.0).amplify(0.5).take(2 * 4800).collect();
+        let source = SamplesBuffer::new(nz!(2), nz!(48000), samples.clone());
+
+        let path = "target/tmp/encoder-round-trip.wav";
+        write_wav(source, path).unwrap();
+
+        let decoder = crate::Decoder::try_from(std::fs::File::open(path).unwrap()).unwrap();
+        assert_eq!(decoder.channels(), nz!(2));
+        assert_eq!(decoder.sample_rate(), nz!(48000));
+
+        let decoded: Vec<Sample> = decoder.collect();
+        assert_eq!(decoded.len(), samples.len());
+        for (decoded, expected) in decoded.iter().zip(&samples) {
+            assert!((decoded - expected).abs() <= 1.0 / 32768.0);
+        }
+    }
+}
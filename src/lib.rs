@@ -164,6 +164,23 @@
 //! when chaining many audio operations together and in long-running signal generators where
 //! phase errors compound over time.
 //!
+//! ### Feature "simd"
+//!
+//! The "simd" feature converts samples to the format of the audio device several at a time
+//! using vector instructions, which lowers the CPU use of the audio callback for streams with
+//! many channels. It currently speeds up conversion to 16-bit integers on x86_64, other targets
+//! and formats use the regular conversion.
+//!
+//! ### Feature "mmap"
+//!
+//! The "mmap" feature adds `Decoder::new_mmap`, which decodes a file through a memory mapping
+//! instead of reading it into memory. This feature requires the "memmap2" crate.
+//!
+//! ### Feature "test-util"
+//!
+//! The "test-util" feature adds the `source::test_util` module with helpers to render a source
+//! to a buffer and compare samples in tests.
+//!
 //! ## How it works under the hood
 //!
 //! Rodio spawns a background thread that is dedicated to reading from the sources and sending
@@ -204,6 +221,8 @@ mod wav_output;
 pub mod buffer;
 pub mod conversions;
 pub mod decoder;
+#[cfg(feature = "wav_output")]
+pub mod encoder;
 #[cfg(feature = "experimental")]
 pub mod fixed_source;
 pub mod math;
@@ -219,7 +238,8 @@ pub use crate::common::{BitDepth, ChannelCount, Float, Sample, SampleRate};
 pub use crate::decoder::Decoder;
 #[cfg(feature = "experimental")]
 pub use crate::fixed_source::FixedSource;
-pub use crate::player::Player;
+pub use crate::mixer::{mixer, Mixer, MixerSource};
+pub use crate::player::{NotConnected, Player, SourceController};
 pub use crate::source::Source;
 pub use crate::spatial_player::SpatialPlayer;
 #[cfg(feature = "playback")]
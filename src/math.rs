@@ -9,9 +9,13 @@ pub(crate) const NANOS_PER_SEC: u64 = 1_000_000_000;
 // Re-export float constants with appropriate precision for the Float type.
 // This centralizes all cfg gating for constants in one place.
 #[cfg(not(feature = "64bit"))]
-pub use std::f32::consts::{E, LN_10, LN_2, LOG10_2, LOG10_E, LOG2_10, LOG2_E, PI, TAU};
+pub use std::f32::consts::{
+    E, FRAC_1_SQRT_2, LN_10, LN_2, LOG10_2, LOG10_E, LOG2_10, LOG2_E, PI, TAU,
+};
 #[cfg(feature = "64bit")]
-pub use std::f64::consts::{E, LN_10, LN_2, LOG10_2, LOG10_E, LOG2_10, LOG2_E, PI, TAU};
+pub use std::f64::consts::{
+    E, FRAC_1_SQRT_2, LN_10, LN_2, LOG10_2, LOG10_E, LOG2_10, LOG2_E, PI, TAU,
+};
 
 /// Linear interpolation between two samples.
 ///
@@ -126,6 +130,21 @@ pub(crate) fn duration_to_float(duration: Duration) -> Float {
     }
 }
 
+/// Returns the start of a frame as Duration.
+///
+/// Rounds up to the next nanosecond, so converting the result back to frames by rounding down
+/// gives exactly `frame` again for any sample rate up to one GHz.
+#[inline]
+#[must_use]
+pub(crate) fn frame_to_duration(frame: u64, sample_rate: SampleRate) -> Duration {
+    let rate = u128::from(sample_rate.get());
+    let nanos = (u128::from(frame) * u128::from(NANOS_PER_SEC)).div_ceil(rate);
+    Duration::new(
+        (nanos / u128::from(NANOS_PER_SEC)) as u64,
+        (nanos % u128::from(NANOS_PER_SEC)) as u32,
+    )
+}
+
 #[must_use]
 pub(crate) fn nearest_multiple_of_two(n: u32) -> u32 {
     if n <= 1 {
@@ -213,6 +232,16 @@ mod test {
             let tolerance = 1e-6;
             TestResult::from_bool((x as f64 - reference).abs() < tolerance)
         }
+
+        fn frame_to_duration_round_trips(frame: u32, sample_rate: u32) -> TestResult {
+            // Above one GHz frames are shorter than a nanosecond.
+            let Some(sample_rate) = SampleRate::new(sample_rate).filter(|r| u64::from(r.get()) <= NANOS_PER_SEC) else {
+                return TestResult::discard();
+            };
+            let duration = frame_to_duration(frame.into(), sample_rate);
+            let back = duration.as_nanos() * u128::from(sample_rate.get()) / u128::from(NANOS_PER_SEC);
+            TestResult::from_bool(back == u128::from(frame))
+        }
     }
 
     /// Tolerance values for precision tests, derived from empirical measurement
@@ -1,8 +1,10 @@
 //! Mixer that plays multiple sounds at the same time.
 
 use crate::common::{ChannelCount, SampleRate};
+use crate::math::duration_to_coefficient;
 use crate::source::{SeekError, Source, UniformSourceIterator};
-use crate::Sample;
+use crate::{Float, Sample};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -11,10 +13,13 @@ use crossbeam_channel::{unbounded as channel, Receiver, Sender};
 #[cfg(not(feature = "crossbeam-channel"))]
 use std::sync::mpsc::{channel, Receiver, Sender};
 
+/// How quickly the master volume follows a change, to avoid clicks.
+const VOLUME_SMOOTHING: Duration = Duration::from_millis(10);
+
 /// Builds a new mixer.
 ///
 /// You can choose the characteristics of the output thanks to this constructor. All the sounds
-/// added to the mixer will be converted to these values.
+/// added to the mixer will be converted to these values, see [`Mixer::add`] for how.
 ///
 /// After creating a mixer, you can add new sounds with the controller.
 ///
@@ -24,11 +29,18 @@ use std::sync::mpsc::{channel, Receiver, Sender};
 /// Add `Zero` source to prevent detaching the mixer from player.
 pub fn mixer(channels: ChannelCount, sample_rate: SampleRate) -> (Mixer, MixerSource) {
     let (tx, rx) = channel();
+    let (stop_tx, stop_rx) = channel();
 
     let input = Mixer(Arc::new(Inner {
         pending_tx: tx,
+        stop_tx,
+        next_voice: AtomicU64::new(0),
         channels,
         sample_rate,
+        frames_mixed: AtomicU64::new(0),
+        exact_timing: AtomicUsize::new(0),
+        volume: AtomicU32::new(1.0f32.to_bits()),
+        voice_fade_in_nanos: AtomicU64::new(0),
     }));
 
     let output = MixerSource {
@@ -37,6 +49,10 @@ pub fn mixer(channels: ChannelCount, sample_rate: SampleRate) -> (Mixer, MixerSo
         current_channel: 0,
         still_pending: Vec::new(),
         pending_rx: rx,
+        stop_rx,
+        buffer: Vec::new(),
+        gain: 1.0,
+        gain_coeff: duration_to_coefficient(VOLUME_SMOOTHING, sample_rate),
     };
 
     (input, output)
@@ -46,30 +62,171 @@ pub fn mixer(channels: ChannelCount, sample_rate: SampleRate) -> (Mixer, MixerSo
 #[derive(Clone)]
 pub struct Mixer(Arc<Inner>);
 
+/// Identifies a sound added to a mixer with [`Mixer::add_voice`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VoiceId(u64);
+
+struct Voice {
+    id: VoiceId,
+    source: Box<dyn Source + Send>,
+}
+
 struct Inner {
-    pending_tx: Sender<Box<dyn Source + Send>>,
+    pending_tx: Sender<Voice>,
+    stop_tx: Sender<VoiceId>,
+    next_voice: AtomicU64,
     channels: ChannelCount,
     sample_rate: SampleRate,
+    frames_mixed: AtomicU64,
+    // Number of sources waiting to read `frames_mixed` on their first sample.
+    exact_timing: AtomicUsize,
+    // Master volume as `f32` bits.
+    volume: AtomicU32,
+    // Fade-in applied to every source added from now on, zero for none.
+    voice_fade_in_nanos: AtomicU64,
 }
 
 impl Mixer {
     /// Adds a new source to mix to the existing ones.
+    ///
+    /// The source does not need to match the channel count or sample rate of the mixer. It is
+    /// wrapped in a [`UniformSourceIterator`] which resamples it and adapts its channel count,
+    /// re-evaluating both at every span boundary of the source. Channels are adapted as follows:
+    ///
+    /// - Mono sources are upmixed by playing the channel at unity gain on the first two mixer
+    ///   channels, so a mono source is centered in a stereo mixer. Any further channels are
+    ///   silent.
+    /// - Other sources with fewer channels than the mixer play on the first channels of the
+    ///   mixer, the remaining channels are silent.
+    /// - Sources with more channels than a mono mixer are downmixed by averaging all their
+    ///   channels ([`DownmixMode::Average`](crate::conversions::DownmixMode::Average)). Both channels of a stereo source are heard at half
+    ///   their level, so the downmix never exceeds the level of the loudest channel.
+    /// - Sources with more channels than a mixer of two or more channels play their first
+    ///   channels, the extra channels are dropped.
+    ///
+    /// All sources are then summed without any gain compensation.
     #[inline]
     pub fn add<T>(&self, source: T)
     where
         T: Source + Send + 'static,
     {
+        self.add_voice(source);
+    }
+
+    /// Adds a new source to mix like [`add`](Mixer::add), and returns an id to
+    /// [`stop`](Mixer::stop) it with.
+    ///
+    /// This makes the mixer a submix whose sounds can change while it plays, for example all
+    /// sound effects of a game appended to a single [`Player`](crate::Player). Sources that end
+    /// are removed from the mix by themselves, stopping them is not needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::source::{SineWave, Source};
+    /// use rodio::nz;
+    ///
+    /// let (mixer, mut output) = rodio::mixer::mixer(nz!(2), nz!(44_100));
+    /// mixer.add(SamplesBuffer::new(nz!(2), nz!(44_100), vec![0.0; 8]));
+    /// let hum = mixer.add_voice(SineWave::new(50.0).amplify(0.1));
+    /// output.by_ref().take(4).for_each(drop);
+    ///
+    /// mixer.stop(hum);
+    /// assert_eq!(output.next(), Some(0.0));
+    /// ```
+    pub fn add_voice<T>(&self, source: T) -> VoiceId
+    where
+        T: Source + Send + 'static,
+    {
+        let id = VoiceId(self.0.next_voice.fetch_add(1, Ordering::Relaxed));
         let uniform_source =
             UniformSourceIterator::new(source, self.0.channels, self.0.sample_rate);
+        let fade_in = Duration::from_nanos(self.0.voice_fade_in_nanos.load(Ordering::Relaxed));
+        let source: Box<dyn Source + Send> = if fade_in.is_zero() {
+            Box::new(uniform_source)
+        } else {
+            Box::new(uniform_source.fade_in(fade_in))
+        };
         // Ignore send errors (channel dropped means MixerSource was dropped)
-        let _ = self.0.pending_tx.send(Box::new(uniform_source));
+        let _ = self.0.pending_tx.send(Voice { id, source });
+        id
+    }
+
+    /// Fades in every source added from now on over `duration`. Off by default, pass
+    /// [`Duration::ZERO`] to turn it off again.
+    ///
+    /// A source that does not start at silence clicks when it starts playing. A fade-in of a
+    /// few milliseconds avoids this without being heard as a fade, which helps for sound
+    /// effects triggered at arbitrary times. Sources already added are not affected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::SineWave;
+    /// use rodio::nz;
+    /// use std::time::Duration;
+    ///
+    /// let (mixer, _output) = rodio::mixer::mixer(nz!(2), nz!(44_100));
+    /// mixer.set_voice_fade_in(Duration::from_millis(5));
+    /// mixer.add(SineWave::new(440.0));
+    /// ```
+    pub fn set_voice_fade_in(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.0.voice_fade_in_nanos.store(nanos, Ordering::Relaxed);
+    }
+
+    /// Removes a source added with [`add_voice`](Mixer::add_voice) from the mix.
+    ///
+    /// The source stops within one block of the output and is dropped on the audio thread.
+    /// Does nothing if it already ended or was stopped.
+    pub fn stop(&self, voice: VoiceId) {
+        let _ = self.0.stop_tx.send(voice);
+    }
+
+    pub(crate) fn channels(&self) -> ChannelCount {
+        self.0.channels
+    }
+
+    pub(crate) fn sample_rate(&self) -> SampleRate {
+        self.0.sample_rate
+    }
+
+    /// Number of whole frames the mixer has output so far.
+    pub(crate) fn frames_mixed(&self) -> u64 {
+        self.0.frames_mixed.load(Ordering::Acquire)
+    }
+
+    /// Makes the mixer count `frames_mixed` sample by sample until
+    /// [`release_exact_timing`](Self::release_exact_timing) is called. Otherwise
+    /// [`MixerSource::fill_buffer`] only updates it once per block.
+    pub(crate) fn hold_exact_timing(&self) {
+        self.0.exact_timing.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(crate) fn release_exact_timing(&self) {
+        self.0.exact_timing.fetch_sub(1, Ordering::AcqRel);
+    }
+
+    /// Volume the sum of all sources is multiplied with.
+    pub(crate) fn master_volume(&self) -> Float {
+        f32::from_bits(self.0.volume.load(Ordering::Relaxed)) as Float
+    }
+
+    /// The output moves to the new volume within a few milliseconds.
+    // `Float` is `f64` with the `64bit` feature.
+    #[allow(clippy::unnecessary_cast)]
+    pub(crate) fn set_master_volume(&self, volume: Float) {
+        self.0
+            .volume
+            .store((volume as f32).to_bits(), Ordering::Relaxed);
     }
 }
 
 /// The output of the mixer. Implements `Source`.
 pub struct MixerSource {
     // The current iterator that produces samples.
-    current_sources: Vec<Box<dyn Source + Send>>,
+    current_sources: Vec<Voice>,
 
     // The pending sounds.
     input: Mixer,
@@ -78,10 +235,20 @@ pub struct MixerSource {
     current_channel: u16,
 
     // A temporary vec used in start_pending_sources.
-    still_pending: Vec<Box<dyn Source + Send>>,
+    still_pending: Vec<Voice>,
 
     // Receiver for pending sources from the channel.
-    pending_rx: Receiver<Box<dyn Source + Send>>,
+    pending_rx: Receiver<Voice>,
+
+    // Receiver for sources to remove.
+    stop_rx: Receiver<VoiceId>,
+
+    // Holds the samples of one source in `fill_buffer`.
+    buffer: Vec<Sample>,
+
+    // Master volume applied right now, smoothly following `Inner::volume`.
+    gain: Float,
+    gain_coeff: Float,
 }
 
 impl Source for MixerSource {
@@ -105,6 +272,46 @@ impl Source for MixerSource {
         None
     }
 
+    /// Mixes whole blocks, so sources added while this runs start at the next call.
+    fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+        if self.input.0.exact_timing.load(Ordering::Acquire) > 0 {
+            for (written, slot) in out.iter_mut().enumerate() {
+                match self.next() {
+                    Some(sample) => *slot = sample,
+                    None => return written,
+                }
+            }
+            return out.len();
+        }
+
+        let channels = self.input.0.channels.get() as usize;
+        let mut written = 0;
+        while written < out.len() {
+            self.start_pending_sources();
+            if self.current_sources.is_empty() {
+                // Same as `next` returning `None`.
+                self.advance(1);
+                return written;
+            }
+
+            // Pending sources only start on a frame boundary, so first finish the frame.
+            let mut len = out.len() - written;
+            if self.current_channel != 0 {
+                len = len.min(channels - self.current_channel as usize);
+            }
+            let mixed = self.mix_block(&mut out[written..written + len]);
+            self.apply_gain(&mut out[written..written + mixed]);
+            self.advance(mixed);
+            written += mixed;
+            if mixed < len {
+                // All sources ended, `next` would have returned `None` here.
+                self.advance(1);
+                return written;
+            }
+        }
+        written
+    }
+
     #[inline]
     fn try_seek(&mut self, _: Duration) -> Result<(), SeekError> {
         Err(SeekError::NotSupported {
@@ -120,13 +327,12 @@ impl Iterator for MixerSource {
     fn next(&mut self) -> Option<Self::Item> {
         self.start_pending_sources();
 
-        let sum = self.sum_current_sources();
+        let mut sum = [self.sum_current_sources()];
+        self.apply_gain(&mut sum);
+        let [sum] = sum;
 
         // Advance frame position (wraps at channel count, never overflows)
-        self.current_channel += 1;
-        if self.current_channel >= self.input.0.channels.get() {
-            self.current_channel = 0;
-        }
+        self.advance(1);
 
         if self.current_sources.is_empty() {
             None
@@ -146,8 +352,8 @@ impl Iterator for MixerSource {
         let mut min = 0;
         let mut max: Option<usize> = Some(0);
 
-        for source in &self.current_sources {
-            let (source_min, source_max) = source.size_hint();
+        for voice in &self.current_sources {
+            let (source_min, source_max) = voice.source.size_hint();
             // Lower bound: guaranteed to produce at least until longest source's lower bound
             min = min.max(source_min);
 
@@ -168,6 +374,42 @@ impl Iterator for MixerSource {
 }
 
 impl MixerSource {
+    /// Fills `out` with interleaved frames of the mix, padding with silence when no source is
+    /// playing. Returns how many samples came from sources, the rest of `out` is silence.
+    ///
+    /// Use this to drive the mixer from the audio callback of another engine instead of an
+    /// output device. `out` should hold whole frames. This is real-time safe as long as the
+    /// sources are: it does not lock, and only allocates when `out` is longer than ever before.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::nz;
+    ///
+    /// let (mixer, mut source) = rodio::mixer::mixer(nz!(2), nz!(48_000));
+    /// mixer.add(SamplesBuffer::new(nz!(2), nz!(48_000), vec![0.5; 4]));
+    ///
+    /// // In the host's audio callback:
+    /// let mut out = [1.0; 8];
+    /// assert_eq!(source.pull_frames(&mut out), 4);
+    /// assert_eq!(out, [0.5, 0.5, 0.5, 0.5, 0.0, 0.0, 0.0, 0.0]);
+    /// ```
+    pub fn pull_frames(&mut self, out: &mut [Sample]) -> usize {
+        let mut written = 0;
+        let mut mixed = 0;
+        while written < out.len() {
+            let filled = self.fill_buffer(&mut out[written..]);
+            written += filled;
+            mixed += filled;
+            // Nothing played this sample, the mixer moved on to the next one.
+            if written < out.len() {
+                out[written] = 0.0;
+                written += 1;
+            }
+        }
+        mixed
+    }
+
     // Samples from the `next()` function are interlaced for each of the channels.
     // New sources are held in `still_pending` until a frame boundary so their
     // samples stay in-step with the channel layout. Otherwise the sound will
@@ -176,6 +418,11 @@ impl MixerSource {
         while let Ok(source) = self.pending_rx.try_recv() {
             self.still_pending.push(source);
         }
+        // After the pending sources, so a source added and stopped right away is found.
+        while let Ok(id) = self.stop_rx.try_recv() {
+            self.current_sources.retain(|voice| voice.id != id);
+            self.still_pending.retain(|voice| voice.id != id);
+        }
 
         if self.current_channel == 0 {
             self.current_sources.append(&mut self.still_pending);
@@ -184,8 +431,8 @@ impl MixerSource {
 
     fn sum_current_sources(&mut self) -> Sample {
         let mut sum = 0.0;
-        self.current_sources.retain_mut(|source| {
-            match source.next() {
+        self.current_sources.retain_mut(|voice| {
+            match voice.source.next() {
                 Some(value) => {
                     sum += value;
                     true // Keep this source
@@ -196,6 +443,72 @@ impl MixerSource {
 
         sum
     }
+
+    /// Sums a block of every current source into `out`. Returns the length of the longest
+    /// source's block, the part of `out` after it is left as is.
+    fn mix_block(&mut self, out: &mut [Sample]) -> usize {
+        if self.buffer.len() < out.len() {
+            self.buffer.resize(out.len(), 0.0);
+        }
+        let buffer = &mut self.buffer[..out.len()];
+        out.fill(0.0);
+
+        let mut mixed = 0;
+        self.current_sources.retain_mut(|voice| {
+            let written = voice.source.fill_buffer(buffer);
+            for (sum, sample) in out.iter_mut().zip(&buffer[..written]) {
+                *sum += sample;
+            }
+            mixed = mixed.max(written);
+            // A short block means the source ended.
+            written == buffer.len()
+        });
+        mixed
+    }
+
+    /// Applies the master volume to samples that start at the current frame position.
+    fn apply_gain(&mut self, out: &mut [Sample]) {
+        let target = self.input.master_volume();
+        if self.current_sources.is_empty() {
+            // Nothing can be heard, so there is no click to avoid.
+            self.gain = target;
+        }
+        if self.gain == target {
+            if target != 1.0 {
+                out.iter_mut().for_each(|sample| *sample *= target);
+            }
+            return;
+        }
+
+        let channels = self.input.0.channels.get() as usize;
+        let mut channel = self.current_channel as usize;
+        for sample in out {
+            *sample *= self.gain;
+            channel += 1;
+            if channel == channels {
+                channel = 0;
+                self.gain = target + self.gain_coeff * (self.gain - target);
+                // The last step is inaudible and `f32` might never reach the target.
+                if (self.gain - target).abs() < 1e-4 {
+                    self.gain = target;
+                }
+            }
+        }
+    }
+
+    /// Moves the frame position forward by `samples`.
+    fn advance(&mut self, samples: usize) {
+        let channels = self.input.0.channels.get() as usize;
+        let position = self.current_channel as usize + samples;
+        self.current_channel = (position % channels) as u16;
+        let frames = (position / channels) as u64;
+        if frames > 0 {
+            self.input
+                .0
+                .frames_mixed
+                .fetch_add(frames, Ordering::Release);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -204,6 +517,7 @@ mod tests {
     use crate::math::nz;
     use crate::mixer;
     use crate::source::Source;
+    use std::time::Duration;
 
     #[test]
     fn basic() {
@@ -229,6 +543,22 @@ mod tests {
         assert_eq!(rx.next(), None);
     }
 
+    #[test]
+    fn pull_frames_pads_with_silence() {
+        let (tx, mut rx) = mixer::mixer(nz!(2), nz!(48000));
+        tx.add(SamplesBuffer::new(nz!(2), nz!(48000), vec![0.25; 4]));
+        tx.add(SamplesBuffer::new(nz!(2), nz!(48000), vec![0.5; 2]));
+
+        let mut out = [1.0; 8];
+        assert_eq!(rx.pull_frames(&mut out), 4);
+        assert_eq!(out, [0.75, 0.75, 0.25, 0.25, 0.0, 0.0, 0.0, 0.0]);
+
+        // Sources added while idle play from the next pull on, on the right channels.
+        tx.add(SamplesBuffer::new(nz!(2), nz!(48000), vec![1.0, -1.0]));
+        assert_eq!(rx.pull_frames(&mut out), 2);
+        assert_eq!(out, [1.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
     #[test]
     fn channels_conv() {
         let (tx, mut rx) = mixer::mixer(nz!(2), nz!(48000));
@@ -257,6 +587,49 @@ mod tests {
         assert_eq!(rx.next(), None);
     }
 
+    #[test]
+    fn mixed_channel_counts() {
+        let (tx, rx) = mixer::mixer(nz!(2), nz!(48000));
+
+        tx.add(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0, 2.0]));
+        tx.add(SamplesBuffer::new(
+            nz!(2),
+            nz!(48000),
+            vec![10.0, 20.0, 30.0, 40.0],
+        ));
+
+        assert_eq!(rx.collect::<Vec<_>>(), vec![11.0, 21.0, 32.0, 42.0]);
+    }
+
+    #[test]
+    fn stereo_into_mono() {
+        let (tx, rx) = mixer::mixer(nz!(1), nz!(48000));
+
+        tx.add(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0, 2.0]));
+        tx.add(SamplesBuffer::new(
+            nz!(2),
+            nz!(48000),
+            vec![10.0, 20.0, 30.0, 40.0],
+        ));
+
+        // Both channels of the stereo source are averaged.
+        assert_eq!(rx.collect::<Vec<_>>(), vec![16.0, 37.0]);
+    }
+
+    #[test]
+    fn both_stereo_channels_reach_mono() {
+        let (tx, rx) = mixer::mixer(nz!(1), nz!(48000));
+
+        // Only the right channel has sound.
+        tx.add(SamplesBuffer::new(
+            nz!(2),
+            nz!(48000),
+            vec![0.0, 1.0, 0.0, -1.0],
+        ));
+
+        assert_eq!(rx.collect::<Vec<_>>(), vec![0.5, -0.5]);
+    }
+
     #[test]
     fn rate_conv() {
         let (tx, mut rx) = mixer::mixer(nz!(1), nz!(96000));
@@ -284,6 +657,68 @@ mod tests {
         assert_eq!(rx.next(), None);
     }
 
+    #[test]
+    fn mixed_sample_rates_keep_pitch() {
+        use crate::math::TAU;
+        use crate::source::{Function, SignalGenerator};
+        use crate::Float;
+        use std::time::Duration;
+
+        /// Amplitude of a sine at `freq` in one second of audio at 48kHz.
+        fn amplitude_at(samples: &[crate::Sample], freq: Float) -> Float {
+            let (re, im) = samples
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (n, &sample)| {
+                    let angle = TAU * freq * n as Float / 48000.0;
+                    (re + sample * angle.cos(), im - sample * angle.sin())
+                });
+            2.0 * (re * re + im * im).sqrt() / 48000.0
+        }
+
+        let (tx, rx) = mixer::mixer(nz!(1), nz!(48000));
+
+        // A music bed at the mixer rate and an effect at a lower rate, both one second long.
+        let bed = SignalGenerator::new(nz!(48000), 100.0, Function::Sine)
+            .take_duration(Duration::from_secs(1))
+            .amplify(0.5);
+        let effect = SignalGenerator::new(nz!(22050), 1000.0, Function::Sine)
+            .take_duration(Duration::from_secs(1))
+            .amplify(0.5);
+
+        tx.add(bed);
+        tx.add(effect);
+        let mut mixed: Vec<_> = rx.collect();
+
+        let tolerance = 48000 / 100;
+        assert!(mixed.len().abs_diff(48000) <= tolerance, "{}", mixed.len());
+        // Both sources are heard at their own pitch in the mix. A pitch error moves the energy
+        // of a source away from its frequency, for example to where the effect would end up
+        // without resampling.
+        mixed.resize(48000, 0.0);
+        for freq in [100.0, 1000.0] {
+            let amplitude = amplitude_at(&mixed, freq);
+            assert!(amplitude > 0.45, "{freq}Hz at {amplitude}");
+        }
+        assert!(amplitude_at(&mixed, 1000.0 * 48000.0 / 22050.0) < 0.05);
+    }
+
+    #[test]
+    fn rate_change_within_source() {
+        let (tx, rx) = mixer::mixer(nz!(1), nz!(48000));
+        let (queue_tx, queue_rx) = crate::queue::queue(false);
+        queue_tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0; 4]));
+        queue_tx.append(SamplesBuffer::new(nz!(1), nz!(24000), vec![2.0; 4]));
+
+        tx.add(queue_rx);
+        let mixed: Vec<_> = rx.collect();
+
+        // The second part is resampled to twice its length.
+        assert_eq!(&mixed[..4], &[1.0; 4]);
+        assert!(mixed[4..].len() >= 7, "{mixed:?}");
+        assert!(mixed[4..].iter().all(|sample| *sample != 1.0));
+    }
+
     #[test]
     fn start_afterwards() {
         let (tx, mut rx) = mixer::mixer(nz!(1), nz!(48000));
@@ -339,4 +774,98 @@ mod tests {
         assert_eq!(rx.next(), Some(-10.0)); // not yet mixed (out of phase)
         assert_eq!(rx.next(), Some(15.0)); // mixing starts
     }
+
+    #[test]
+    fn fill_buffer_matches_next() {
+        let sources = || {
+            [
+                SamplesBuffer::new(nz!(2), nz!(48000), vec![1.0, -1.0, 2.0, -2.0]),
+                SamplesBuffer::new(nz!(2), nz!(48000), vec![10.0; 10]),
+            ]
+        };
+
+        let (tx, mut rx) = mixer::mixer(nz!(2), nz!(48000));
+        sources().into_iter().for_each(|source| tx.add(source));
+        let expected: Vec<_> = rx.by_ref().collect();
+        let frames_mixed = tx.frames_mixed();
+
+        let (tx, mut rx) = mixer::mixer(nz!(2), nz!(48000));
+        sources().into_iter().for_each(|source| tx.add(source));
+        let mut out = [0.0; 16];
+        assert_eq!(rx.fill_buffer(&mut out[..3]), 3);
+        assert_eq!(rx.fill_buffer(&mut out[3..]), expected.len() - 3);
+        assert_eq!(out[..expected.len()], expected);
+        assert_eq!(tx.frames_mixed(), frames_mixed);
+    }
+
+    #[test]
+    fn fill_buffer_starts_sources_on_frame_boundary() {
+        let (tx, mut rx) = mixer::mixer(nz!(2), nz!(48000));
+        tx.add(SamplesBuffer::new(
+            nz!(2),
+            nz!(48000),
+            vec![1.0, -1.0, 1.0, -1.0],
+        ));
+
+        let mut out = [0.0; 4];
+        assert_eq!(rx.fill_buffer(&mut out[..1]), 1);
+        tx.add(SamplesBuffer::new(nz!(2), nz!(48000), vec![5.0, -5.0]));
+        assert_eq!(rx.fill_buffer(&mut out[1..]), 3);
+        assert_eq!(out, [1.0, -1.0, 6.0, -6.0]);
+    }
+
+    #[test]
+    fn master_volume_ramps_to_target() {
+        let (tx, mut rx) = mixer::mixer(nz!(1), nz!(48000));
+        tx.add(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0; 48000]));
+        assert_eq!(rx.next(), Some(1.0));
+
+        tx.set_master_volume(0.5);
+        let ramp: Vec<_> = rx.by_ref().take(9600).collect();
+        assert!(ramp[0] > 0.9, "volume changed abruptly");
+        assert!(ramp.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert_eq!(ramp.last(), Some(&0.5));
+
+        tx.set_master_volume(0.25);
+        let mut out = [0.0; 9600];
+        assert_eq!(rx.fill_buffer(&mut out), out.len());
+        assert!(out[0] > 0.45);
+        assert_eq!(out.last(), Some(&0.25));
+    }
+
+    #[test]
+    fn voice_fade_in() {
+        let (tx, mut rx) = mixer::mixer(nz!(1), nz!(1000));
+        tx.set_voice_fade_in(Duration::from_millis(10));
+        tx.add(SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 20]));
+
+        let output: Vec<_> = rx.by_ref().collect();
+        assert_eq!(output[0], 0.0);
+        assert!(output[..10].windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(output[..10].iter().all(|sample| *sample < 1.0));
+        assert_eq!(output[10..], [1.0; 10]);
+
+        tx.set_voice_fade_in(Duration::ZERO);
+        tx.add(SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 2]));
+        assert_eq!(rx.collect::<Vec<_>>(), [1.0; 2]);
+    }
+
+    #[test]
+    fn stop_removes_voice() {
+        let (tx, mut rx) = mixer::mixer(nz!(2), nz!(48000));
+        let first = tx.add_voice(SamplesBuffer::new(nz!(2), nz!(48000), vec![1.0; 8]));
+        let second = tx.add_voice(SamplesBuffer::new(nz!(1), nz!(24000), vec![2.0; 4]));
+        assert_ne!(first, second);
+
+        let mixed: Vec<_> = rx.by_ref().take(4).collect();
+        assert_eq!(mixed, [3.0; 4]);
+
+        tx.stop(second);
+        let mixed: Vec<_> = rx.by_ref().collect();
+        assert_eq!(mixed, [1.0; 4]);
+
+        // Stopping a voice that ended does nothing.
+        tx.stop(first);
+        assert_eq!(rx.next(), None);
+    }
 }
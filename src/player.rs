@@ -1,5 +1,10 @@
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{fence, AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, Thread};
 use std::time::Duration;
 
 #[cfg(feature = "crossbeam-channel")]
@@ -8,7 +13,9 @@ use dasp_sample::FromSample;
 #[cfg(not(feature = "crossbeam-channel"))]
 use std::sync::mpsc::{Receiver, Sender};
 
+use crate::common::{ChannelCount, SampleRate};
 use crate::mixer::Mixer;
+use crate::queue::QueueFull;
 use crate::source::SeekError;
 use crate::Float;
 use crate::{queue, source::Done, Source};
@@ -19,14 +26,41 @@ use crate::{queue, source::Done, Source};
 /// playing.
 pub struct Player {
     queue_tx: Arc<queue::SourcesQueueInput>,
-    sleep_until_end: Mutex<Option<Receiver<()>>>,
+    end: Arc<EndSignal>,
 
     controls: Arc<Controls>,
     sound_count: Arc<AtomicUsize>,
+    // The mixer the player was connected to, used as clock by `append_at`.
+    mixer: Option<Mixer>,
 
     detached: bool,
 }
 
+/// Error returned by [`Player::append_at`] for a player that is not connected to a mixer.
+/// Contains the sound that could not be appended.
+pub struct NotConnected<S>(pub S);
+
+impl<S> NotConnected<S> {
+    /// Returns the sound that could not be appended.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> fmt::Debug for NotConnected<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NotConnected(..)")
+    }
+}
+
+impl<S> fmt::Display for NotConnected<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the player is not connected to a mixer")
+    }
+}
+
+impl<S> std::error::Error for NotConnected<S> {}
+
 struct SeekOrder {
     pos: Duration,
     feedback: Sender<Result<(), SeekError>>,
@@ -57,22 +91,158 @@ impl SeekOrder {
     }
 }
 
+/// Wakes everyone waiting for the appended sounds to end. Fed by the end signal of each sound
+/// in the queue, so any number of threads and futures can wait at the same time.
+///
+/// The end signal may fire on the audio thread, so it only publishes the sound that ended and
+/// unparks a notifier thread, which wakes the waiters. That thread is started by the first
+/// waiter and ends together with the player and its futures.
+#[derive(Default)]
+struct EndSignal {
+    // Sounds are numbered in the order they are appended, starting at one.
+    appended: AtomicU64,
+    // The highest number of a sound that ended, was cleared or was dropped. Sounds end in the
+    // order they were appended, so every sound up to it is done.
+    ended: AtomicU64,
+    wakers: Mutex<Vec<Waker>>,
+    woken: Condvar,
+    notifier: OnceLock<Thread>,
+}
+
+impl EndSignal {
+    /// Numbers a sound that is appended, and returns the callback that reports its end.
+    fn next_sound(self: &Arc<Self>) -> impl Fn() + Send + Sync + 'static {
+        let sound = self.appended.fetch_add(1, Ordering::Relaxed) + 1;
+        let signal = Arc::downgrade(self);
+        move || {
+            if let Some(signal) = signal.upgrade() {
+                signal.end(sound);
+            }
+        }
+    }
+
+    /// Returns the number of the last appended sound, which is done once `ended` reaches it.
+    fn last_appended(&self) -> u64 {
+        self.appended.load(Ordering::Relaxed)
+    }
+
+    fn has_ended(&self, sound: u64) -> bool {
+        self.ended.load(Ordering::SeqCst) >= sound
+    }
+
+    /// Takes no locks, it may run on the audio thread.
+    fn end(&self, sound: u64) {
+        self.ended.fetch_max(sound, Ordering::SeqCst);
+        // Pairs with the fence in `start_notifier`, so either the notifier is seen here or the
+        // waiter sees the sound ended.
+        fence(Ordering::SeqCst);
+        if let Some(notifier) = self.notifier.get() {
+            notifier.unpark();
+        }
+    }
+
+    fn start_notifier(self: &Arc<Self>) {
+        self.notifier.get_or_init(|| {
+            let signal = Arc::downgrade(self);
+            thread::Builder::new()
+                .name("rodio player end".to_owned())
+                .spawn(move || loop {
+                    thread::park();
+                    let Some(signal) = signal.upgrade() else {
+                        return;
+                    };
+                    // Taking the lock after the sound ended means every waiter either saw it
+                    // end or is registered and gets woken here.
+                    for waker in signal.wakers.lock().unwrap().drain(..) {
+                        waker.wake();
+                    }
+                    signal.woken.notify_all();
+                })
+                .expect("failed to spawn player end thread")
+                .thread()
+                .clone()
+        });
+        fence(Ordering::SeqCst);
+    }
+
+    fn wait(self: &Arc<Self>, sound: u64, timeout: Option<Duration>) -> bool {
+        if self.has_ended(sound) {
+            return true;
+        }
+        self.start_notifier();
+        let wakers = self.wakers.lock().unwrap();
+        let not_ended = |_: &mut Vec<Waker>| !self.has_ended(sound);
+        match timeout {
+            None => {
+                drop(self.woken.wait_while(wakers, not_ended).unwrap());
+                true
+            }
+            Some(timeout) => {
+                let (_wakers, result) = self
+                    .woken
+                    .wait_timeout_while(wakers, timeout, not_ended)
+                    .unwrap();
+                !result.timed_out()
+            }
+        }
+    }
+}
+
+impl Drop for EndSignal {
+    fn drop(&mut self) {
+        // Lets the notifier thread see that it is no longer needed.
+        if let Some(notifier) = self.notifier.get() {
+            notifier.unpark();
+        }
+    }
+}
+
+/// Future returned by [`Player::completion`].
+struct Completion {
+    signal: Arc<EndSignal>,
+    sound: u64,
+}
+
+impl Future for Completion {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.signal.has_ended(self.sound) {
+            return Poll::Ready(());
+        }
+        self.signal.start_notifier();
+        let mut wakers = self.signal.wakers.lock().unwrap();
+        if self.signal.has_ended(self.sound) {
+            return Poll::Ready(());
+        }
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
 struct Controls {
     pause: AtomicBool,
     volume: Mutex<Float>,
     stopped: AtomicBool,
     speed: Mutex<f32>,
+    resume_fade: Mutex<Option<Duration>>,
     to_clear: Mutex<u32>,
     seek: Mutex<Option<SeekOrder>>,
     position: Mutex<Duration>,
+    format: Mutex<Option<(ChannelCount, SampleRate)>>,
+    label: Mutex<Option<String>>,
+    on_empty: Mutex<Option<Box<dyn FnOnce() + Send>>>,
 }
 
 impl Player {
     /// Builds a new `Player`, beginning playback on a stream.
     #[inline]
     pub fn connect_new(mixer: &Mixer) -> Player {
-        let (sink, source) = Player::new();
+        let (mut sink, source) = Player::new();
         mixer.add(source);
+        sink.mixer = Some(mixer.clone());
         sink
     }
 
@@ -83,17 +253,22 @@ impl Player {
 
         let sink = Player {
             queue_tx,
-            sleep_until_end: Mutex::new(None),
+            end: Arc::default(),
             controls: Arc::new(Controls {
                 pause: AtomicBool::new(false),
                 volume: Mutex::new(1.0),
                 stopped: AtomicBool::new(false),
                 speed: Mutex::new(1.0),
+                resume_fade: Mutex::new(None),
                 to_clear: Mutex::new(0),
                 seek: Mutex::new(None),
                 position: Mutex::new(Duration::ZERO),
+                format: Mutex::new(None),
+                label: Mutex::new(None),
+                on_empty: Mutex::new(None),
             }),
             sound_count: Arc::new(AtomicUsize::new(0)),
+            mixer: None,
             detached: false,
         };
         (sink, queue_rx)
@@ -106,20 +281,162 @@ impl Player {
         S: Source + Send + 'static,
         f32: FromSample<S::Item>,
     {
-        // Wait for the queue to flush then resume stopped playback
+        self.resume_if_stopped();
+        let source = self.prepare(source, None);
+
+        self.sound_count.fetch_add(1, Ordering::Relaxed);
+        self.queue_tx
+            .append_with_callback(source, self.end.next_sound());
+    }
+
+    /// Appends a sound to the queue of sounds to play with a label, for example the title of a
+    /// song.
+    ///
+    /// While the sound plays, [`current_label`](Player::current_label) returns the label.
+    #[inline]
+    pub fn append_labeled<S>(&self, source: S, label: String)
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+    {
+        self.resume_if_stopped();
+        let source = self.prepare(source, Some(label));
+
+        self.sound_count.fetch_add(1, Ordering::Relaxed);
+        self.queue_tx
+            .append_with_callback(source, self.end.next_sound());
+    }
+
+    /// Appends a sound to the queue of sounds to play if fewer than the
+    /// [capacity](Player::set_capacity) are waiting to be played. Otherwise the sound is handed
+    /// back in the error.
+    #[inline]
+    pub fn try_append<S>(&self, source: S) -> Result<(), QueueFull<S>>
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+    {
+        self.append_within_capacity(source, false)
+    }
+
+    /// Appends a sound that starts exactly at frame `start_frame` of the mixer.
+    ///
+    /// Frames are counted like [`MixerDeviceSink::frames_played`](crate::MixerDeviceSink::frames_played):
+    /// the first frame the mixer outputs is frame zero. The sound is padded with silence from
+    /// the moment it would otherwise start until `start_frame`, so it starts on that exact
+    /// frame as long as it has the sample rate of the mixer and nothing is queued before it
+    /// that is still playing at `start_frame`. Sounds with another sample rate go through the
+    /// mixer's resampler and may start a few frames off. If `start_frame` has already passed
+    /// when the sound would start, it starts right away.
+    ///
+    /// The device plays the frame roughly one [buffer](crate::DeviceSinkBuilder::with_buffer_size)
+    /// after the mixer outputs it, with the same delay for every frame.
+    ///
+    /// # Errors
+    /// If the player was not created with [`Player::connect_new`], as only then the player
+    /// knows the mixer's clock. The sound is handed back in the error.
+    #[inline]
+    pub fn append_at<S>(&self, source: S, start_frame: u64) -> Result<(), NotConnected<S>>
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+    {
+        let Some(mixer) = self.mixer.clone() else {
+            return Err(NotConnected(source));
+        };
+        mixer.hold_exact_timing();
+        self.append(StartAt {
+            input: source,
+            mixer,
+            start_frame,
+            padding: None,
+        });
+        Ok(())
+    }
+
+    /// Appends a sound to the queue of sounds to play, first waiting until fewer than the
+    /// [capacity](Player::set_capacity) are waiting to be played.
+    ///
+    /// Use this to keep a producer that decodes or generates sounds faster than they are played
+    /// from using unbounded memory. Never call this from the thread that pulls samples from the
+    /// player's output, it would wait forever.
+    #[inline]
+    pub fn append_blocking<S>(&self, source: S)
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+    {
+        let _ = self.append_within_capacity(source, true);
+    }
+
+    /// Sets the maximum number of sounds that can wait to be played, not counting the one that
+    /// is currently playing. `None` removes the limit, which is the default.
+    ///
+    /// Only [`try_append`](Player::try_append) and [`append_blocking`](Player::append_blocking)
+    /// respect the capacity, [`append`](Player::append) always adds the sound.
+    #[inline]
+    pub fn set_capacity(&self, capacity: Option<usize>) {
+        self.queue_tx.set_capacity(capacity);
+    }
+
+    /// Sets the capacity of the player, see [`set_capacity`](Player::set_capacity).
+    #[inline]
+    pub fn with_capacity(self, capacity: usize) -> Self {
+        self.set_capacity(Some(capacity));
+        self
+    }
+
+    fn append_within_capacity<S>(&self, source: S, block: bool) -> Result<(), QueueFull<S>>
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+    {
+        self.resume_if_stopped();
+
+        let mut source = Some(source);
+        let appended = self
+            .queue_tx
+            .append_with_callback_within_capacity(block, || {
+                let source = self.prepare(source.take().expect("only called once"), None);
+                self.sound_count.fetch_add(1, Ordering::Relaxed);
+                // Only numbered once appended, a rejected sound would never end.
+                (source, self.end.next_sound())
+            });
+        if appended {
+            Ok(())
+        } else {
+            Err(QueueFull(source.take().expect("not appended")))
+        }
+    }
+
+    /// Wait for the queue to flush then resume stopped playback.
+    fn resume_if_stopped(&self) {
         if self.controls.stopped.load(Ordering::SeqCst) {
             if self.sound_count.load(Ordering::SeqCst) > 0 {
                 self.sleep_until_end();
             }
             self.controls.stopped.store(false, Ordering::SeqCst);
         }
+    }
+
+    /// Wraps the source in the adapters that apply the player's controls.
+    fn prepare<S>(&self, source: S, label: Option<String>) -> impl Source + Send + 'static
+    where
+        S: Source + Send + 'static,
+    {
+        // Until the sound starts, the format is known from the sound that is appended to an
+        // empty player. Must be called before the sound is counted.
+        if self.empty() {
+            *self.controls.format.lock().unwrap() = Some((source.channels(), source.sample_rate()));
+        }
 
         let controls = self.controls.clone();
+        let done_controls = self.controls.clone();
 
         let start_played = AtomicBool::new(false);
         let sound_count_clone = self.sound_count.clone();
 
-        let source = Done::new(
+        Done::new(
             source
                 .speed(1.0)
                 // Must be placed before pausable but after speed & delay
@@ -132,10 +449,19 @@ impl Player {
                 if !src.inner().skipped() {
                     sound_count_clone.fetch_sub(1, Ordering::Relaxed);
                 }
+                if sound_count_clone.load(Ordering::SeqCst) == 0 {
+                    let on_empty = done_controls.on_empty.lock().unwrap().take();
+                    if let Some(on_empty) = on_empty {
+                        on_empty();
+                    }
+                }
             },
         )
         // If you change the duration update the docs for try_seek!
         .periodic_access(Duration::from_millis(5), move |src| {
+            if !start_played.load(Ordering::SeqCst) {
+                controls.label.lock().unwrap().clone_from(&label);
+            }
             if controls.stopped.load(Ordering::SeqCst) {
                 src.inner_mut().stop();
                 *controls.position.lock().unwrap() = Duration::ZERO;
@@ -151,10 +477,14 @@ impl Player {
                         src.inner().inner().inner().inner().inner().get_pos();
                 }
             }
+            *controls.format.lock().unwrap() = Some((src.channels(), src.sample_rate()));
             let amp = src.inner_mut().inner_mut().inner_mut();
             amp.set_factor(*controls.volume.lock().unwrap());
-            amp.inner_mut()
-                .set_paused(controls.pause.load(Ordering::SeqCst));
+            let paused = controls.pause.load(Ordering::SeqCst);
+            match controls.resume_fade.lock().unwrap().take() {
+                Some(fade) if !paused => amp.inner_mut().resume_with_fade(fade),
+                _ => amp.inner_mut().set_paused(paused),
+            }
             amp.inner_mut()
                 .inner_mut()
                 .inner_mut()
@@ -163,10 +493,7 @@ impl Player {
                 seek.attempt(amp)
             }
             start_played.store(true, Ordering::SeqCst);
-        });
-
-        self.sound_count.fetch_add(1, Ordering::Relaxed);
-        *self.sleep_until_end.lock().unwrap() = Some(self.queue_tx.append_with_signal(source));
+        })
     }
 
     /// Gets the volume of the sound.
@@ -221,9 +548,24 @@ impl Player {
         self.controls.pause.store(false, Ordering::SeqCst);
     }
 
+    /// Resumes playback of a paused player, fading the volume in over `duration`.
+    ///
+    /// The volume ramps up from silence to the player's [`volume`](Player::volume), including
+    /// any change made to it while paused. This prevents the click that can occur when
+    /// playback resumes in the middle of a waveform.
+    ///
+    /// No effect if not paused.
+    pub fn play_with_fade(&self, duration: Duration) {
+        if self.controls.pause.load(Ordering::SeqCst) {
+            *self.controls.resume_fade.lock().unwrap() = Some(duration);
+            self.play();
+        }
+    }
+
     // There is no `can_seek()` method as it is impossible to use correctly. Between
     // checking if a source supports seeking and actually seeking the sink can
-    // switch to a new source.
+    // switch to a new source. For the same reason `Source::is_seekable` of the
+    // queue is only a hint.
 
     /// Attempts to seek to a given position in the current source.
     ///
@@ -241,8 +583,8 @@ impl Player {
     /// It will return an error if an implementation ran
     /// into one during the seek.
     ///
-    /// When seeking beyond the end of a source this
-    /// function might return an error if the duration of the source is not known.
+    /// When seeking beyond the end of a source this function might return
+    /// [`SeekError::OutOfRange`] if the duration of the source is not known.
     pub fn try_seek(&self, pos: Duration) -> Result<(), SeekError> {
         let (order, feedback) = SeekOrder::new(pos);
         *self.controls.seek.lock().unwrap() = Some(order);
@@ -312,16 +654,70 @@ impl Player {
     }
 
     /// Destroys the sink without stopping the sounds that are still playing.
+    ///
+    /// The sounds stay owned by the mixer or queue the player was connected to and play until
+    /// they end. A detached player can no longer be controlled: there is no way to pause, stop
+    /// or change the volume of its sounds afterwards. Sounds that were already queued are still
+    /// played one after the other.
     #[inline]
     pub fn detach(mut self) {
         self.detached = true;
     }
 
-    /// Sleeps the current thread until the sound ends.
+    /// Destroys the sink without stopping the sounds that are still playing, like
+    /// [`detach`](Player::detach), but keeps a [`SourceController`] to change their volume and
+    /// speed.
+    ///
+    /// Useful for "fire and forget" sounds such as an ambience track that should keep playing
+    /// on its own but still follow a volume slider.
+    #[inline]
+    pub fn into_controller(mut self) -> SourceController {
+        self.detached = true;
+        SourceController {
+            controls: self.controls.clone(),
+        }
+    }
+
+    /// Sleeps the current thread until the sounds appended so far have ended.
+    ///
+    /// Returns right away if they already have. Any number of threads can wait at the same
+    /// time, together with [`completion`](Player::completion).
     #[inline]
     pub fn sleep_until_end(&self) {
-        if let Some(sleep_until_end) = self.sleep_until_end.lock().unwrap().take() {
-            let _ = sleep_until_end.recv();
+        self.end.wait(self.end.last_appended(), None);
+    }
+
+    /// Sleeps the current thread until the sound ends or the timeout expires.
+    ///
+    /// Returns `true` if the sound ended, or `false` if it was still playing when the timeout
+    /// expired. Unlike [`sleep_until_end`](Player::sleep_until_end) this can not block forever
+    /// on an endless sound, which makes it safe to use when shutting down.
+    pub fn sleep_until_end_timeout(&self, timeout: Duration) -> bool {
+        self.end.wait(self.end.last_appended(), Some(timeout))
+    }
+
+    /// Returns a future that resolves when the sounds queued so far have ended.
+    ///
+    /// This is the async counterpart of [`sleep_until_end`](Player::sleep_until_end) and works
+    /// with any executor. It only covers the sounds in the queue when it is called, sounds
+    /// appended later are not waited for. It resolves right away if the queue is empty, and
+    /// when the sounds are cleared or the player is dropped.
+    ///
+    /// It does not interfere with other futures or with threads in `sleep_until_end` waiting for
+    /// the same sounds, all of them are woken. The audio thread does not wake them itself, a
+    /// single helper thread per player does, started when something first waits.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # async fn example(player: rodio::Player) {
+    /// player.completion().await;
+    /// println!("done playing");
+    /// # }
+    /// ```
+    pub fn completion(&self) -> impl Future<Output = ()> + Send + 'static {
+        Completion {
+            signal: Arc::clone(&self.end),
+            sound: self.end.last_appended(),
         }
     }
 
@@ -338,6 +734,66 @@ impl Player {
         self.sound_count.load(Ordering::Relaxed)
     }
 
+    /// Sets a callback that is called once when the player runs out of sounds.
+    ///
+    /// The callback runs on the audio thread right after the last sound in the queue ends, is
+    /// skipped or is cleared. It is only called the next time that happens, so set it after
+    /// appending. Sounds appended after it was called do not call it again, set a new callback
+    /// for those. Setting a new callback replaces one that was not called yet.
+    ///
+    /// This avoids polling [`empty`](Player::empty) to find out when playback is done.
+    pub fn on_empty<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        *self.controls.on_empty.lock().unwrap() = Some(Box::new(callback));
+    }
+
+    /// Sets a callback that is called every time the player starts playing the next sound.
+    ///
+    /// The callback receives the index of the sound that started, where the first sound ever
+    /// appended to this player has index zero. This makes it possible to keep a "now playing"
+    /// display up to date without polling [`len`](Player::len).
+    ///
+    /// The callback is called exactly once per sound, including sounds that are skipped or
+    /// cleared before they are heard: those start and are skipped right away. It is not called
+    /// when the player runs out of sounds and starts playing silence. Setting a new callback
+    /// replaces the previous one from the next sound on.
+    ///
+    /// The callback runs on the audio thread, so it must be short and must not block. It may
+    /// call methods of the player, including setting a new callback.
+    pub fn on_track_change<F>(&self, callback: F)
+    where
+        F: FnMut(usize) + Send + 'static,
+    {
+        self.queue_tx.set_on_track_change(callback);
+    }
+
+    /// Returns the channel count and sample rate of the sound that is playing, or of the first
+    /// sound waiting to be played. Returns `None` if the player is [empty](Player::empty).
+    ///
+    /// Use this to pick an output configuration that matches the sounds, so no conversion is
+    /// needed. The format changes when the next sound starts or a sound changes its format
+    /// while playing, it is updated every few milliseconds.
+    pub fn format(&self) -> Option<(ChannelCount, SampleRate)> {
+        if self.empty() {
+            return None;
+        }
+        *self.controls.format.lock().unwrap()
+    }
+
+    /// Returns the label of the sound that is playing, if it was appended with
+    /// [`append_labeled`](Player::append_labeled). Returns `None` if the player is
+    /// [empty](Player::empty).
+    ///
+    /// The label changes as soon as the next sound starts, also when sounds are skipped.
+    pub fn current_label(&self) -> Option<String> {
+        if self.empty() {
+            return None;
+        }
+        self.controls.label.lock().unwrap().clone()
+    }
+
     /// Returns the position of the sound that's being played.
     ///
     /// This takes into account any speedup or delay applied.
@@ -351,6 +807,145 @@ impl Player {
     }
 }
 
+/// Changes the volume and speed of the sounds of a [`Player`] after it was turned into a
+/// controller with [`Player::into_controller`].
+///
+/// The controller shares its settings with the sounds, which are owned by the mixer or queue
+/// the player was connected to. It can be cloned and sent to other threads. Changes are picked
+/// up by the sounds within a few milliseconds. Once all the sounds have ended the controller
+/// has no effect anymore, and dropping it does not stop the sounds.
+#[derive(Clone)]
+pub struct SourceController {
+    controls: Arc<Controls>,
+}
+
+impl SourceController {
+    /// Gets the volume of the sounds, see [`Player::volume`].
+    #[inline]
+    pub fn volume(&self) -> Float {
+        *self.controls.volume.lock().unwrap()
+    }
+
+    /// Changes the volume of the sounds, see [`Player::set_volume`].
+    #[inline]
+    pub fn set_volume(&self, value: Float) {
+        *self.controls.volume.lock().unwrap() = value;
+    }
+
+    /// Gets the speed of the sounds, see [`Player::speed`].
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        *self.controls.speed.lock().unwrap()
+    }
+
+    /// Changes the play speed of the sounds, see [`Player::set_speed`].
+    #[inline]
+    pub fn set_speed(&self, value: f32) {
+        *self.controls.speed.lock().unwrap() = value;
+    }
+}
+
+impl std::fmt::Debug for SourceController {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceController")
+            .field("volume", &self.volume())
+            .field("speed", &self.speed())
+            .finish()
+    }
+}
+
+/// Pads a source with silence so it starts at a given frame of a mixer.
+struct StartAt<S> {
+    input: S,
+    mixer: Mixer,
+    start_frame: u64,
+    // Samples of silence left, decided when the source is first pulled.
+    padding: Option<usize>,
+}
+
+impl<S: Source> StartAt<S> {
+    fn padding(&mut self) -> usize {
+        *self.padding.get_or_insert_with(|| {
+            let frames = self.start_frame.saturating_sub(self.mixer.frames_mixed());
+            self.mixer.release_exact_timing();
+            let frames = frames as u128 * self.input.sample_rate().get() as u128
+                / self.mixer.sample_rate().get() as u128;
+            frames as usize * self.input.channels().get() as usize
+        })
+    }
+}
+
+impl<S> Drop for StartAt<S> {
+    fn drop(&mut self) {
+        if self.padding.is_none() {
+            self.mixer.release_exact_timing();
+        }
+    }
+}
+
+impl<S: Source> Iterator for StartAt<S> {
+    type Item = crate::Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<crate::Sample> {
+        let padding = self.padding();
+        if padding > 0 {
+            self.padding = Some(padding - 1);
+            return Some(0.0);
+        }
+        self.input.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let padding = self.padding.unwrap_or(0);
+        let (lower, upper) = self.input.size_hint();
+        (
+            lower.saturating_add(padding),
+            upper.and_then(|upper| upper.checked_add(padding)),
+        )
+    }
+}
+
+impl<S: Source> Source for StartAt<S> {
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        // Like `Delay`, the silence is part of the first span.
+        self.input
+            .current_span_len()
+            .map(|len| len + self.padding.unwrap_or(0))
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        if self.padding.replace(0).is_none() {
+            self.mixer.release_exact_timing();
+        }
+        Ok(())
+    }
+}
+
 impl Drop for Player {
     #[inline]
     fn drop(&mut self) {
@@ -364,7 +959,13 @@ impl Drop for Player {
 
 #[cfg(test)]
 mod tests {
+    use std::future::Future;
+    use std::pin::pin;
     use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread;
+    use std::time::Duration;
 
     use crate::buffer::SamplesBuffer;
     use crate::math::nz;
@@ -423,6 +1024,24 @@ mod tests {
         assert!(player.empty());
     }
 
+    #[test]
+    fn test_play_with_fade() {
+        let (player, source) = Player::new();
+        let mut source = source.skip_while(|x| *x == 0.0);
+
+        // Low rate to ensure immediate control.
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![1.0; 10]));
+        assert_eq!(source.next(), Some(1.0));
+
+        player.pause();
+        assert_eq!(source.next(), Some(0.0));
+
+        player.set_volume(0.5);
+        player.play_with_fade(Duration::from_secs(4));
+        let resumed: Vec<_> = source.take(6).collect();
+        assert_eq!(resumed, vec![0.0, 0.125, 0.25, 0.375, 0.5, 0.5]);
+    }
+
     #[test]
     fn test_stop_and_start() {
         let (player, mut queue_rx) = Player::new();
@@ -451,6 +1070,215 @@ mod tests {
         assert_eq!(queue_rx.next(), src.next());
     }
 
+    #[test]
+    fn test_on_track_change() {
+        let (player, source) = Player::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        player.on_track_change(move |index| tx.send(index).unwrap());
+
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![1.0, 1.0]));
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![2.0, 2.0]));
+
+        let played: Vec<_> = source.take(10).collect();
+        assert_eq!(&played[..4], &[1.0, 1.0, 2.0, 2.0]);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_on_track_change_cleared() {
+        let (player, mut source) = Player::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        player.on_track_change(move |index| tx.send(index).unwrap());
+
+        for _ in 0..3 {
+            player.append(SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 100]));
+        }
+        assert_eq!(source.next(), Some(1.0));
+        player.clear();
+        source.by_ref().take(1000).for_each(drop);
+        assert!(player.empty());
+        // Cleared sounds start and are skipped right away.
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_on_track_change_replaced_from_callback() {
+        let (player, source) = Player::new();
+        let player = Arc::new(player);
+        let (tx, rx) = std::sync::mpsc::channel();
+        let weak_player = Arc::downgrade(&player);
+        player.on_track_change(move |index| {
+            tx.send(index).unwrap();
+            let tx = tx.clone();
+            if let Some(player) = weak_player.upgrade() {
+                player.on_track_change(move |index| tx.send(index + 100).unwrap());
+            }
+        });
+
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![1.0]));
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![2.0]));
+        source.take(10).for_each(drop);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![0, 101]);
+    }
+
+    #[test]
+    fn test_format() {
+        let (player, mut output) = Player::new();
+        assert_eq!(player.format(), None);
+
+        player.append(SamplesBuffer::new(nz!(2), nz!(44100), vec![0.5; 4]));
+        player.append(SamplesBuffer::new(nz!(1), nz!(8000), vec![0.5; 2]));
+        assert_eq!(player.format(), Some((nz!(2), nz!(44100))));
+
+        // Play the first sound and start the second.
+        output.by_ref().take(5).for_each(drop);
+        assert_eq!(player.format(), Some((nz!(1), nz!(8000))));
+
+        // The sound is done once the output reads past its end.
+        output.by_ref().take(2).for_each(drop);
+        assert!(player.empty());
+        assert_eq!(player.format(), None);
+    }
+
+    #[test]
+    fn test_current_label() {
+        let (player, mut output) = Player::new();
+        player.append_labeled(
+            SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 2]),
+            "intro".to_owned(),
+        );
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 2]));
+        player.append_labeled(
+            SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 2]),
+            "outro".to_owned(),
+        );
+        assert_eq!(player.current_label(), None);
+
+        output.next();
+        assert_eq!(player.current_label().as_deref(), Some("intro"));
+        output.by_ref().take(2).for_each(drop);
+        assert_eq!(player.current_label(), None);
+
+        output.by_ref().take(2).for_each(drop);
+        assert_eq!(player.current_label().as_deref(), Some("outro"));
+
+        player.clear();
+        assert_eq!(player.current_label(), None);
+    }
+
+    #[test]
+    fn test_on_empty() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        let (player, mut output) = Player::new();
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 2]));
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 2]));
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        player.on_empty(move || {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Play the first sound and start the second.
+        output.by_ref().take(3).for_each(drop);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        // The last sound is done once the output reads past its end.
+        output.by_ref().take(2).for_each(drop);
+        assert!(player.empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // The callback is used up.
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 2]));
+        output.by_ref().take(3).for_each(drop);
+        assert!(player.empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// Polls `future` on the current thread until it is ready, parking in between.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct Unpark(thread::Thread);
+
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_completion() {
+        let (player, mut output) = Player::new();
+        // Nothing is queued, so there is nothing to wait for.
+        block_on(player.completion());
+
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 4]));
+        let completion = player.completion();
+        let audio_thread = thread::spawn(move || {
+            output.by_ref().take(10).for_each(drop);
+        });
+
+        block_on(completion);
+        assert!(player.empty());
+        audio_thread.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_completion_on_tokio() {
+        let (player, mut output) = Player::new();
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 4]));
+        let completion = player.completion();
+        let audio_thread = thread::spawn(move || {
+            output.by_ref().take(10).for_each(drop);
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), completion)
+            .await
+            .expect("the queued sound ended");
+        assert!(player.empty());
+        audio_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_completion_with_concurrent_waiters() {
+        let (player, mut output) = Player::new();
+        let player = Arc::new(player);
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 4]));
+
+        let first = player.completion();
+        let second = player.completion();
+        let sleeper = {
+            let player = Arc::clone(&player);
+            thread::spawn(move || player.sleep_until_end())
+        };
+        let waiter = thread::spawn(move || block_on(first));
+        // Start playing once the others wait, so the end of the sound has to wake them.
+        thread::sleep(Duration::from_millis(50));
+        let audio_thread = thread::spawn(move || {
+            output.by_ref().take(10).for_each(drop);
+        });
+
+        block_on(second);
+        waiter.join().unwrap();
+        sleeper.join().unwrap();
+        audio_thread.join().unwrap();
+        // All of them waited for the same sound.
+        assert!(player.empty());
+        assert!(player.sleep_until_end_timeout(Duration::ZERO));
+    }
+
     #[test]
     fn test_volume() {
         let (player, mut queue_rx) = Player::new();
@@ -468,4 +1296,174 @@ mod tests {
             assert_eq!(queue_rx.next(), src.next());
         }
     }
+
+    #[test]
+    fn test_detach_plays_to_completion() {
+        let (player, source) = Player::new();
+
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![1.0, 2.0]));
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![3.0, 4.0]));
+        player.detach();
+
+        // Without the handle the queue ends after the last sound instead of playing silence.
+        let played: Vec<_> = source.skip_while(|x| *x == 0.0).collect();
+        assert_eq!(played, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_controller_outlives_player() {
+        let (player, source) = Player::new();
+
+        // At 1 Hz the controls are applied before every sample.
+        player.append(SamplesBuffer::new(nz!(1), nz!(1), vec![1.0, 1.0, 1.0]));
+        let controller = player.into_controller();
+        controller.set_volume(0.5);
+
+        let mut source = source.skip_while(|x| *x == 0.0);
+        assert_eq!(source.next(), Some(0.5));
+        controller.set_volume(2.0);
+        assert_eq!(source.next(), Some(2.0));
+        assert_eq!(controller.volume(), 2.0);
+
+        // Dropping the controller does not stop the sound.
+        drop(controller);
+        assert_eq!(source.next(), Some(2.0));
+        assert_eq!(source.next(), None);
+    }
+
+    #[test]
+    fn test_append_at_starts_on_frame() {
+        let (mixer, mut output) = crate::mixer::mixer(nz!(2), nz!(1000));
+        let player = Player::connect_new(&mixer);
+
+        // Let the mixer run for a while before scheduling.
+        output.by_ref().take(2 * 30).for_each(drop);
+        player
+            .append_at(SamplesBuffer::new(nz!(2), nz!(1000), vec![1.0; 20]), 100)
+            .unwrap();
+
+        let frames: Vec<_> = output.by_ref().take(2 * 100).collect();
+        let first = frames.iter().position(|s| *s != 0.0).unwrap() / 2;
+        assert_eq!(30 + first, 100);
+
+        // Mono sounds are upmixed by the mixer and start on the frame too.
+        player
+            .append_at(SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 10]), 200)
+            .unwrap();
+        let frames: Vec<_> = output.by_ref().take(2 * 100).collect();
+        let first = frames.iter().position(|s| *s != 0.0).unwrap() / 2;
+        assert_eq!(130 + first, 200);
+    }
+
+    #[test]
+    fn test_append_at_with_fill_buffer() {
+        let (mixer, mut output) = crate::mixer::mixer(nz!(2), nz!(1000));
+        let player = Player::connect_new(&mixer);
+
+        let mut block = [0.0; 2 * 64];
+        output.fill_buffer(&mut block);
+        player
+            .append_at(SamplesBuffer::new(nz!(2), nz!(1000), vec![1.0; 20]), 100)
+            .unwrap();
+
+        let mut frames = Vec::new();
+        for _ in 0..3 {
+            assert_eq!(output.fill_buffer(&mut block), block.len());
+            frames.extend_from_slice(&block);
+        }
+        let first = frames.iter().position(|s| *s != 0.0).unwrap() / 2;
+        assert_eq!(64 + first, 100);
+    }
+
+    #[test]
+    fn test_append_at_needs_mixer() {
+        let (player, _output) = Player::new();
+        let sound = SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 4]);
+        let rejected = player.append_at(sound, 100).unwrap_err();
+        assert_eq!(rejected.into_inner().count(), 4);
+        assert!(player.empty());
+    }
+
+    #[cfg(feature = "playback")]
+    #[test]
+    fn test_append_at_on_null_sink() {
+        let sink = crate::MixerDeviceSink::null(nz!(2), nz!(1000));
+        let player = Player::connect_new(sink.mixer());
+
+        // Records the frame of the mixer on which the sound starts.
+        let mixer = sink.mixer().clone();
+        let started = Arc::new(std::sync::Mutex::new(None));
+        let sound = {
+            let started = Arc::clone(&started);
+            SamplesBuffer::new(nz!(2), nz!(1000), vec![1.0; 20]).periodic_access(
+                Duration::from_secs(1),
+                move |_| {
+                    started.lock().unwrap().get_or_insert(mixer.frames_mixed());
+                },
+            )
+        };
+        let start_frame = sink.mixer().frames_mixed() + 50;
+        player.append_at(sound, start_frame).unwrap();
+
+        assert!(player.sleep_until_end_timeout(Duration::from_secs(5)));
+        assert_eq!(*started.lock().unwrap(), Some(start_frame));
+    }
+
+    #[test]
+    fn test_capacity() {
+        let (player, source) = Player::new();
+        let player = player.with_capacity(1);
+
+        assert!(player
+            .try_append(SamplesBuffer::new(nz!(1), nz!(1), vec![1.0]))
+            .is_ok());
+        let rejected = player
+            .try_append(SamplesBuffer::new(nz!(1), nz!(1), vec![2.0]))
+            .unwrap_err();
+        assert_eq!(rejected.into_inner().collect::<Vec<_>>(), vec![2.0]);
+        assert_eq!(player.len(), 1);
+
+        let mut source = source.skip_while(|x| *x == 0.0);
+        assert_eq!(source.next(), Some(1.0));
+        assert!(player
+            .try_append(SamplesBuffer::new(nz!(1), nz!(1), vec![3.0]))
+            .is_ok());
+        assert_eq!(source.next(), Some(3.0));
+    }
+
+    #[test]
+    fn test_sleep_until_end_after_queue_full() {
+        let (player, mut output) = Player::new();
+        let player = player.with_capacity(1);
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 4]));
+        player.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 4]));
+        assert!(player
+            .try_append(SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 4]))
+            .is_err());
+
+        let audio_thread = thread::spawn(move || {
+            output.by_ref().take(20).for_each(drop);
+        });
+        // The rejected sound is not waited for.
+        assert!(player.sleep_until_end_timeout(Duration::from_secs(5)));
+        block_on(player.completion());
+        audio_thread.join().unwrap();
+    }
+
+    #[cfg(feature = "playback")]
+    #[test]
+    fn test_sleep_until_end_timeout() {
+        let sink = crate::MixerDeviceSink::null(nz!(1), nz!(1000));
+        let player = Player::connect_new(sink.mixer());
+        assert!(player.sleep_until_end_timeout(Duration::ZERO));
+
+        // 50ms of sound.
+        player.append(SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5; 50]));
+        assert!(player.sleep_until_end_timeout(Duration::from_secs(5)));
+
+        // 10s of sound.
+        player.append(SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5; 10_000]));
+        assert!(!player.sleep_until_end_timeout(Duration::from_millis(50)));
+        assert!(!player.sleep_until_end_timeout(Duration::from_millis(50)));
+    }
 }
@@ -1,8 +1,10 @@
 //! Queue that plays sounds one after the other.
 
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
 use std::time::Duration;
 
 use dasp_sample::Sample as _;
@@ -12,9 +14,11 @@ use crate::Sample;
 
 use crate::common::{ChannelCount, SampleRate};
 #[cfg(feature = "crossbeam-channel")]
-use crossbeam_channel::{unbounded as channel, Receiver, Sender};
+use crossbeam_channel::{
+    bounded as sync_channel, unbounded as channel, Receiver, Sender, Sender as SyncSender,
+};
 #[cfg(not(feature = "crossbeam-channel"))]
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::mpsc::{channel, sync_channel, Receiver, Sender, SyncSender};
 
 /// Builds a new queue. It consists of an input and an output.
 ///
@@ -28,16 +32,42 @@ use std::sync::mpsc::{channel, Receiver, Sender};
 /// - If you pass `false`, then the queue will report that it has finished playing.
 ///
 pub fn queue(keep_alive_if_empty: bool) -> (Arc<SourcesQueueInput>, SourcesQueueOutput) {
+    let (sender, receiver) = channel();
+    let (callback_sender, callback_receiver) = channel();
+    let (garbage_sender, garbage_receiver) = sync_channel(GARBAGE_CAPACITY);
     let input = Arc::new(SourcesQueueInput {
-        next_sounds: Mutex::new(VecDeque::new()),
+        sender: Mutex::new(sender),
+        appended: AtomicUsize::new(0),
+        taken: AtomicUsize::new(0),
+        cleared_until: AtomicUsize::new(0),
+        clears: AtomicUsize::new(0),
+        pending_signals: Mutex::new(VecDeque::new()),
         keep_alive_if_empty: AtomicBool::new(keep_alive_if_empty),
+        idle_chunk: AtomicUsize::new(1),
+        loop_all: AtomicBool::new(false),
+        on_track_change: Mutex::new(callback_sender),
+        garbage: Mutex::new(garbage_receiver),
+        capacity: AtomicUsize::new(usize::MAX),
+        space_available: Condvar::new(),
     });
 
+    let current = Empty::new();
     let output = SourcesQueueOutput {
-        current: Box::new(Empty::new()) as Box<_>,
+        silence_channels: current.channels(),
+        silence_sample_rate: current.sample_rate(),
+        current: Box::new(current) as Box<_>,
         signal_after_end: None,
+        receiver,
+        peeked: RefCell::new(None),
         input: input.clone(),
         silence_samples_remaining: 0,
+        tracks_started: 0,
+        current_label: None,
+        playlist: RefCell::new(VecDeque::new()),
+        clears_seen: Cell::new(0),
+        on_track_change: None,
+        callback_receiver,
+        garbage: garbage_sender,
     };
 
     (input, output)
@@ -46,16 +76,158 @@ pub fn queue(keep_alive_if_empty: bool) -> (Arc<SourcesQueueInput>, SourcesQueue
 // TODO: consider reimplementing this with `from_factory`
 
 type Sound = Box<dyn Source + Send>;
-type SignalDone = Option<Sender<()>>;
+type SignalDone = Option<Arc<DoneSignal>>;
+type TrackChangeCallback = Box<dyn FnMut(usize) + Send>;
+type SoundFactory = Box<dyn FnMut() -> Sound + Send>;
+
+/// Signals the `Receiver` returned by `append_with_signal` once, either when the sound finished
+/// playing or when it was cleared with `clear_and_signal`.
+struct DoneSignal {
+    sent: AtomicBool,
+    notify: Notify,
+}
+
+enum Notify {
+    Channel(Sender<()>),
+    /// Also called when the sound is dropped without being signalled, like a channel
+    /// disconnects.
+    Callback(Box<dyn Fn() + Send + Sync>),
+}
+
+impl DoneSignal {
+    fn new(notify: Notify) -> Arc<Self> {
+        Arc::new(Self {
+            sent: AtomicBool::new(false),
+            notify,
+        })
+    }
+
+    fn send(&self) {
+        if !self.sent.swap(true, Ordering::AcqRel) {
+            match &self.notify {
+                // Fails only if nobody waits for the signal.
+                Notify::Channel(sender) => {
+                    let _ = sender.send(());
+                }
+                Notify::Callback(callback) => callback(),
+            }
+        }
+    }
+}
+
+impl Drop for DoneSignal {
+    fn drop(&mut self) {
+        if let Notify::Callback(_) = self.notify {
+            self.send();
+        }
+    }
+}
+
+/// How much garbage the output can hand back before the input drops it. The channel is
+/// allocated up front, so handing garbage back never allocates on the audio thread.
+const GARBAGE_CAPACITY: usize = 64;
+
+/// Something the output no longer needs. It is sent back to the input to be dropped by the
+/// appending threads, freeing decoders and buffers takes too long for the audio thread.
+// The values are only held to be dropped.
+#[allow(dead_code)]
+enum Garbage {
+    Sound(QueuedSound),
+    Factory(SoundFactory),
+    Callback(TrackChangeCallback),
+}
+
+/// A sound sent from the input to the output.
+struct QueuedSound {
+    // Position in the order of appends, used to skip cleared sounds. `None` for sounds that are
+    // played again because the queue loops.
+    index: Option<usize>,
+    // `None` until the output is about to play a sound appended with a factory.
+    sound: Option<Sound>,
+    signal_after_end: SignalDone,
+    // Builds the sound when it is about to play, and again when the queue loops.
+    factory: Option<SoundFactory>,
+    label: Option<String>,
+}
+
+impl QueuedSound {
+    fn new(sound: Option<Sound>) -> Self {
+        Self {
+            index: None,
+            sound,
+            signal_after_end: None,
+            factory: None,
+            label: None,
+        }
+    }
+}
 
 /// The input of the queue.
+///
+/// Sounds are handed to the output through a lock-free channel, so appending never blocks the
+/// thread that plays the queue. The output takes no locks, and the sounds it skips after a
+/// clear are sent back to be dropped by the threads that append.
 pub struct SourcesQueueInput {
-    next_sounds: Mutex<VecDeque<(Sound, SignalDone)>>,
+    // Held by the appending threads only, so that sounds are numbered in the order they are sent
+    // and the capacity is respected. The output never takes this lock.
+    sender: Mutex<Sender<QueuedSound>>,
+    // Number of sounds ever appended.
+    appended: AtomicUsize,
+    // Number of sounds the output moved past, either by playing them or by dropping them after
+    // a clear.
+    taken: AtomicUsize,
+    // Sounds with a lower index were cleared, the output drops them instead of playing them.
+    cleared_until: AtomicUsize,
+    // Number of calls to `clear`, so the output knows to forget the sounds it loops over.
+    clears: AtomicUsize,
+    // The done signals of the sounds appended with a signal by index, so that clearing can send
+    // them. Only held weakly, dropping a sound still disconnects its `Receiver`. The output never
+    // takes this lock, signals of sounds it took are removed by the appending threads.
+    pending_signals: Mutex<VecDeque<(usize, Weak<DoneSignal>)>>,
 
     // See constructor.
     keep_alive_if_empty: AtomicBool,
+    // Frames of keep-alive silence played at once, see `set_idle_chunk`.
+    idle_chunk: AtomicUsize,
+    // See `set_loop`.
+    loop_all: AtomicBool,
+
+    // Hands a new track change callback to the output, which owns and calls it.
+    on_track_change: Mutex<Sender<TrackChangeCallback>>,
+    // What the output no longer needs, see `Garbage`.
+    garbage: Mutex<Receiver<Garbage>>,
+
+    // Maximum number of sounds waiting to be played, `usize::MAX` if unbounded.
+    capacity: AtomicUsize,
+    // Notified every time the output takes a sound while the queue has a capacity.
+    space_available: Condvar,
+}
+
+/// Error returned when appending to a queue that is full. Contains the source that could not be
+/// appended.
+pub struct QueueFull<T>(pub T);
+
+impl<T> QueueFull<T> {
+    /// Returns the source that could not be appended.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for QueueFull<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("QueueFull(..)")
+    }
+}
+
+impl<T> fmt::Display for QueueFull<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the queue is full")
+    }
 }
 
+impl<T> std::error::Error for QueueFull<T> {}
+
 impl SourcesQueueInput {
     /// Adds a new source to the end of the queue.
     #[inline]
@@ -63,10 +235,49 @@ impl SourcesQueueInput {
     where
         T: Source + Send + 'static,
     {
-        self.next_sounds
-            .lock()
-            .unwrap()
-            .push_back((Box::new(source) as Box<_>, None));
+        let sender = self.sender.lock().unwrap();
+        self.send(&sender, QueuedSound::new(Some(Box::new(source) as Box<_>)));
+    }
+
+    /// Adds a new source to the end of the queue with a label, for example the title of a song.
+    ///
+    /// While the source plays, [`SourcesQueueOutput::current_label`] returns the label.
+    #[inline]
+    pub fn append_labeled<T>(&self, source: T, label: String)
+    where
+        T: Source + Send + 'static,
+    {
+        let sender = self.sender.lock().unwrap();
+        let sound = QueuedSound {
+            label: Some(label),
+            ..QueuedSound::new(Some(Box::new(source) as Box<_>))
+        };
+        self.send(&sender, sound);
+    }
+
+    /// Adds a source to the end of the queue that is only built by `factory` once it is about
+    /// to play.
+    ///
+    /// This defers expensive work like opening a file and creating a decoder until the sound is
+    /// needed. When the queue [loops](Self::set_loop), `factory` is called again to play the
+    /// sound once more, without keeping the decoded audio around. Sources that can be cloned can
+    /// use a factory like `move || source.clone()`.
+    ///
+    /// The factory runs on the thread that plays the queue, just before the previous sound
+    /// ends. It should be fast, otherwise the output has a gap. Do slow work on another thread
+    /// ahead of time and only hand over the result in the factory.
+    pub fn append_factory<F, T>(&self, mut factory: F)
+    where
+        F: FnMut() -> T + Send + 'static,
+        T: Source + Send + 'static,
+    {
+        let factory = Box::new(move || Box::new(factory()) as Sound);
+        let sender = self.sender.lock().unwrap();
+        let sound = QueuedSound {
+            factory: Some(factory),
+            ..QueuedSound::new(None)
+        };
+        self.send(&sender, sound);
     }
 
     /// Adds a new source to the end of the queue.
@@ -81,13 +292,166 @@ impl SourcesQueueInput {
         T: Source + Send + 'static,
     {
         let (tx, rx) = channel();
-        self.next_sounds
-            .lock()
-            .unwrap()
-            .push_back((Box::new(source) as Box<_>, Some(tx)));
+        let sender = self.sender.lock().unwrap();
+        let sound = QueuedSound {
+            signal_after_end: Some(DoneSignal::new(Notify::Channel(tx))),
+            ..QueuedSound::new(Some(Box::new(source) as Box<_>))
+        };
+        self.send(&sender, sound);
         rx
     }
 
+    /// Adds a new source to the end of the queue if fewer than [`capacity`](Self::capacity)
+    /// sources are waiting to be played. Otherwise the source is handed back in the error.
+    #[inline]
+    pub fn try_append<T>(&self, source: T) -> Result<(), QueueFull<T>>
+    where
+        T: Source + Send + 'static,
+    {
+        let mut source = Some(source);
+        if self.push_within_capacity(false, || {
+            let source = source.take().expect("only called once");
+            (Box::new(source) as Box<_>, None)
+        }) {
+            Ok(())
+        } else {
+            Err(QueueFull(source.take().expect("not appended")))
+        }
+    }
+
+    /// Adds a new source to the end of the queue, first waiting until fewer than
+    /// [`capacity`](Self::capacity) sources are waiting to be played.
+    ///
+    /// This blocks until the output has moved on to the next sound, so the output must be
+    /// played from another thread or this never returns.
+    #[inline]
+    pub fn append_blocking<T>(&self, source: T)
+    where
+        T: Source + Send + 'static,
+    {
+        self.push_within_capacity(true, || (Box::new(source) as Box<_>, None));
+    }
+
+    /// Like [`append_with_signal`](Self::append_with_signal), but calls `on_end` instead of
+    /// signalling a channel. `on_end` is called once, also when the sound is dropped without
+    /// being signalled, and may run on the audio thread.
+    pub(crate) fn append_with_callback<T, F>(&self, source: T, on_end: F)
+    where
+        T: Source + Send + 'static,
+        F: Fn() + Send + Sync + 'static,
+    {
+        let sender = self.sender.lock().unwrap();
+        let sound = QueuedSound {
+            signal_after_end: Some(DoneSignal::new(Notify::Callback(Box::new(on_end)))),
+            ..QueuedSound::new(Some(Box::new(source) as Box<_>))
+        };
+        self.send(&sender, sound);
+    }
+
+    /// Same as [`append_with_callback`](Self::append_with_callback) but respects the capacity
+    /// like [`try_append`](Self::try_append) or [`append_blocking`](Self::append_blocking)
+    /// depending on `block`. `make_sound` builds the source and its `on_end` callback, and is
+    /// only called if the source can be appended. Returns `false` otherwise.
+    pub(crate) fn append_with_callback_within_capacity<T, F, E>(
+        &self,
+        block: bool,
+        make_sound: F,
+    ) -> bool
+    where
+        T: Source + Send + 'static,
+        F: FnOnce() -> (T, E),
+        E: Fn() + Send + Sync + 'static,
+    {
+        self.push_within_capacity(block, || {
+            let (source, on_end) = make_sound();
+            let signal = DoneSignal::new(Notify::Callback(Box::new(on_end)));
+            (Box::new(source) as Box<_>, Some(signal))
+        })
+    }
+
+    /// Pushes the sound built by `make_sound` once there is room for it. If `block` is false
+    /// and the queue is full, returns `false` without calling `make_sound`.
+    fn push_within_capacity<F>(&self, block: bool, make_sound: F) -> bool
+    where
+        F: FnOnce() -> (Sound, SignalDone),
+    {
+        let mut sender = self.sender.lock().unwrap();
+        while self.waiting() >= self.capacity.load(Ordering::Acquire) {
+            if !block {
+                return false;
+            }
+            // The output notifies without taking the lock so it never blocks. That means a
+            // notification can slip in between the check and the wait, so check again regularly.
+            sender = self
+                .space_available
+                .wait_timeout(sender, Duration::from_millis(10))
+                .unwrap()
+                .0;
+        }
+        let (sound, signal_after_end) = make_sound();
+        let sound = QueuedSound {
+            signal_after_end,
+            ..QueuedSound::new(Some(sound))
+        };
+        self.send(&sender, sound);
+        true
+    }
+
+    /// Drops what the output sent back, see `Garbage`.
+    fn drop_garbage(&self) {
+        let garbage = self.garbage.lock().unwrap();
+        while garbage.try_recv().is_ok() {}
+    }
+
+    /// Sends a sound to the output. Must be called with the `sender` lock held.
+    fn send(&self, sender: &Sender<QueuedSound>, mut sound: QueuedSound) {
+        self.drop_garbage();
+        let index = self.appended.load(Ordering::Relaxed);
+        sound.index = Some(index);
+        if let Some(signal) = &sound.signal_after_end {
+            let taken = self.taken.load(Ordering::Acquire);
+            let mut pending = self.pending_signals.lock().unwrap();
+            while pending.front().is_some_and(|(index, _)| *index < taken) {
+                pending.pop_front();
+            }
+            pending.push_back((index, Arc::downgrade(signal)));
+        }
+        // Fails only if the output was dropped, then nobody is going to play the sound anyway.
+        let _ = sender.send(sound);
+        self.appended.store(index + 1, Ordering::Release);
+    }
+
+    /// Number of sounds appended that the output has not moved past yet.
+    fn waiting(&self) -> usize {
+        let skipped = self
+            .taken
+            .load(Ordering::Acquire)
+            .max(self.cleared_until.load(Ordering::Acquire));
+        self.appended
+            .load(Ordering::Acquire)
+            .saturating_sub(skipped)
+    }
+
+    /// Sets the maximum number of sources that can wait to be played, not counting the one
+    /// that is currently playing. `None` removes the limit.
+    ///
+    /// Only [`try_append`](Self::try_append) and [`append_blocking`](Self::append_blocking)
+    /// respect the capacity, [`append`](Self::append) always adds the source. Lowering the
+    /// capacity does not remove sources that are already queued.
+    pub fn set_capacity(&self, capacity: Option<usize>) {
+        self.capacity
+            .store(capacity.unwrap_or(usize::MAX), Ordering::Release);
+        self.space_available.notify_all();
+    }
+
+    /// Returns the maximum number of sources that can wait to be played, `None` if unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        match self.capacity.load(Ordering::Acquire) {
+            usize::MAX => None,
+            capacity => Some(capacity),
+        }
+    }
+
     /// Sets whether the queue stays alive if there's no more sound to play.
     ///
     /// See also the constructor.
@@ -101,12 +465,118 @@ impl SourcesQueueInput {
         self.keep_alive_if_empty.load(Ordering::Acquire)
     }
 
+    /// Sets how many frames of silence the queue plays at once while it is kept alive without
+    /// sounds to play. Defaults to a single frame.
+    ///
+    /// A sound appended while the queue is idle starts after the chunk of silence that is
+    /// playing, so larger chunks add up to that much delay. In exchange the queue checks for
+    /// new sounds less often, and reports longer spans, which lets the sources after it do less
+    /// work while idle. Zero is treated as one frame.
+    pub fn set_idle_chunk(&self, frames: usize) {
+        self.idle_chunk.store(frames.max(1), Ordering::Release);
+    }
+
+    /// Returns how many frames of silence the queue plays at once while idle.
+    pub fn idle_chunk(&self) -> usize {
+        self.idle_chunk.load(Ordering::Acquire)
+    }
+
+    /// Sets whether the queue starts over once it played every sound, like a looping playlist.
+    ///
+    /// Only sounds appended with [`append_factory`](Self::append_factory) that start
+    /// playing while looping is enabled are played again, other sources can not be rebuilt.
+    /// Sounds appended while the queue loops play after the current sound. Every time a sound
+    /// plays again it counts as a new track for the
+    /// [track change callback](Self::set_on_track_change). Disabling looping or
+    /// [clearing](Self::clear) the queue forgets the sounds to loop over.
+    pub fn set_loop(&self, loop_all: bool) {
+        self.loop_all.store(loop_all, Ordering::Release);
+    }
+
+    /// Returns whether the queue starts over once it played every sound.
+    pub fn is_looping(&self) -> bool {
+        self.loop_all.load(Ordering::Acquire)
+    }
+
+    /// Sets a callback that is called every time the queue starts playing the next sound.
+    ///
+    /// The callback receives the index of the sound that started, counting every sound that
+    /// started playing in this queue from zero. It is called exactly once per sound, from the
+    /// thread that is pulling samples from the queue output, so keep it short and do not block.
+    /// It is not called when the queue runs out of sounds and starts playing keep-alive silence,
+    /// nor for sounds removed with [`clear`](Self::clear) before they started, which do not
+    /// count either.
+    ///
+    /// Replaces any previously set callback from the next sound on. The callback may set a new
+    /// callback itself.
+    pub fn set_on_track_change<F>(&self, callback: F)
+    where
+        F: FnMut(usize) + Send + 'static,
+    {
+        let callback = Box::new(callback) as TrackChangeCallback;
+        // Fails only if the output was dropped, then the callback would never be called.
+        let _ = self.on_track_change.lock().unwrap().send(callback);
+    }
+
+    /// Returns the number of waiting sounds that were appended with
+    /// [`append_with_signal`](Self::append_with_signal), not counting the one that is playing.
+    pub fn pending_signals(&self) -> usize {
+        let taken = self.taken.load(Ordering::Acquire);
+        let pending = self.pending_signals.lock().unwrap();
+        pending
+            .iter()
+            .filter(|(index, signal)| {
+                *index >= taken
+                    && signal.upgrade().is_some_and(|signal| {
+                        // Sounds with an end callback are not waited for through a `Receiver`.
+                        matches!(signal.notify, Notify::Channel(_))
+                            && !signal.sent.load(Ordering::Acquire)
+                    })
+            })
+            .count()
+    }
+
     /// Removes all the sounds from the queue. Returns the number of sounds cleared.
+    ///
+    /// The output skips the cleared sounds the next time it looks for a sound to play, and
+    /// hands them back to be dropped by the next call that appends to or clears the queue. That
+    /// keeps freeing their memory off the thread that plays the queue. Only once they are
+    /// dropped the `Receiver`s of sounds appended with
+    /// [`append_with_signal`](Self::append_with_signal) disconnect, see
+    /// [`clear_and_signal`](Self::clear_and_signal) to notify them right away.
     pub fn clear(&self) -> usize {
-        let mut sounds = self.next_sounds.lock().unwrap();
-        let len = sounds.len();
-        sounds.clear();
-        len
+        self.clear_signalling(false)
+    }
+
+    /// Removes all the sounds from the queue like [`clear`](Self::clear), and signals the
+    /// `Receiver` of every cleared sound appended with
+    /// [`append_with_signal`](Self::append_with_signal) as if it had finished playing.
+    ///
+    /// Unlike the cleared sounds the signals are sent right away, so threads waiting for them
+    /// do not depend on the output being played. Returns the number of sounds cleared.
+    pub fn clear_and_signal(&self) -> usize {
+        self.clear_signalling(true)
+    }
+
+    fn clear_signalling(&self, signal: bool) -> usize {
+        self.drop_garbage();
+        let _sender = self.sender.lock().unwrap();
+        let appended = self.appended.load(Ordering::Relaxed);
+        let cleared = self.waiting();
+        self.cleared_until.store(appended, Ordering::Release);
+        self.clears.fetch_add(1, Ordering::Release);
+        self.space_available.notify_all();
+
+        let pending = std::mem::take(&mut *self.pending_signals.lock().unwrap());
+        if signal {
+            let taken = self.taken.load(Ordering::Acquire);
+            for (_, signal) in pending.into_iter().filter(|(index, _)| *index >= taken) {
+                if let Some(signal) = signal.upgrade() {
+                    signal.send();
+                }
+            }
+        }
+        cleared
     }
 }
 
@@ -115,14 +585,40 @@ pub struct SourcesQueueOutput {
     // The current iterator that produces samples.
     current: Box<dyn Source + Send>,
 
-    // Signal this sender before picking from `next`.
-    signal_after_end: Option<Sender<()>>,
+    // Signal this before picking from `next`.
+    signal_after_end: SignalDone,
 
     // The next sounds.
+    receiver: Receiver<QueuedSound>,
+
+    // The next sound, received early to report its parameters before it starts playing.
+    peeked: RefCell<Option<QueuedSound>>,
+
     input: Arc<SourcesQueueInput>,
 
     // This counts how many silence samples to inject for keep-alive behavior.
     silence_samples_remaining: usize,
+
+    // Format of the keep-alive silence, that of the last sound that started playing.
+    silence_channels: ChannelCount,
+    silence_sample_rate: SampleRate,
+
+    // Number of sounds taken from the queue so far.
+    tracks_started: usize,
+
+    // Label of the sound that is playing.
+    current_label: Option<String>,
+
+    // Factories of the sounds that played while looping, in the order they play next.
+    playlist: RefCell<VecDeque<SoundFactory>>,
+    // Value of `SourcesQueueInput::clears` when the playlist was last checked.
+    clears_seen: Cell<usize>,
+
+    // Owned by the output so calling it takes no lock, replaced through `callback_receiver`.
+    on_track_change: Option<TrackChangeCallback>,
+    callback_receiver: Receiver<TrackChangeCallback>,
+    // See `Garbage`.
+    garbage: SyncSender<Garbage>,
 }
 
 impl Source for SourcesQueueOutput {
@@ -138,24 +634,24 @@ impl Source for SourcesQueueOutput {
                 // - Current source is exhausted, and won't output silence after it: end of queue.
                 len
             }
-            _ => {
-                // - Current source is not exhausted, and is reporting no span length, or
+            Some(_) => {
                 // - Current source is exhausted, and will output silence after it.
+                self.channels().get() as usize * self.input.idle_chunk()
+            }
+            None => {
+                // - Current source is not exhausted, and is reporting no span length.
                 self.channels().get() as usize
             }
         };
 
-        // Special case: if the current source is `Empty` and there are queued sounds after it.
-        if len == 0
-            && self
-                .current
-                .total_duration()
-                .is_some_and(|duration| duration.is_zero())
-        {
-            if let Some((next, _)) = self.input.next_sounds.lock().unwrap().front() {
-                return next
-                    .current_span_len()
-                    .or_else(|| Some(next.channels().get() as usize));
+        // Special case: if the current source is exhausted and there are queued sounds after it,
+        // report the span of the next sound, just like `channels` and `sample_rate` do.
+        if len == 0 {
+            if let Some(len) = self.peek_next(|next| {
+                next.current_span_len()
+                    .unwrap_or(next.channels().get() as usize)
+            }) {
+                return Some(len);
             }
         }
 
@@ -166,13 +662,17 @@ impl Source for SourcesQueueOutput {
 
     #[inline]
     fn channels(&self) -> ChannelCount {
-        if self.current.is_exhausted() && self.silence_samples_remaining == 0 {
-            if let Some((next, _)) = self.input.next_sounds.lock().unwrap().front() {
+        if self.silence_samples_remaining > 0 {
+            return self.silence_channels;
+        }
+        if self.current.is_exhausted() {
+            if let Some(channels) = self.peek_next(|next| next.channels()) {
                 // Current source exhausted, peek at next queued source
                 // This is critical: UniformSourceIterator queries metadata during append,
                 // before any samples are pulled. We must report the next source's metadata.
-                return next.channels();
+                return channels;
             }
+            return self.silence_channels;
         }
 
         self.current.channels()
@@ -180,12 +680,16 @@ impl Source for SourcesQueueOutput {
 
     #[inline]
     fn sample_rate(&self) -> SampleRate {
-        if self.current.is_exhausted() && self.silence_samples_remaining == 0 {
-            if let Some((next, _)) = self.input.next_sounds.lock().unwrap().front() {
+        if self.silence_samples_remaining > 0 {
+            return self.silence_sample_rate;
+        }
+        if self.current.is_exhausted() {
+            if let Some(sample_rate) = self.peek_next(|next| next.sample_rate()) {
                 // Current source exhausted, peek at next queued source
                 // This prevents wrong resampling setup in UniformSourceIterator
-                return next.sample_rate();
+                return sample_rate;
             }
+            return self.silence_sample_rate;
         }
 
         self.current.sample_rate()
@@ -196,6 +700,15 @@ impl Source for SourcesQueueOutput {
         None
     }
 
+    /// Returns whether the source playing right now can seek.
+    ///
+    /// The queue can move on to the next source between this check and a call to
+    /// [`try_seek`](Source::try_seek), so the seek can still fail.
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.current.is_seekable()
+    }
+
     /// Only seeks within the current source.
     // We can not go back to previous sources. We could implement seek such
     // that it advances the queue if the position is beyond the current song.
@@ -231,8 +744,10 @@ impl Iterator for SourcesQueueOutput {
             // Current source is exhausted. Move to next sound, play silence, or end.
             // In order to avoid inlining that expensive operation, the code is in another function.
             if self.go_next().is_err() {
+                self.current_label = None;
                 if self.input.keep_alive_if_empty() {
-                    self.silence_samples_remaining = self.current.channels().get() as usize;
+                    self.silence_samples_remaining =
+                        self.silence_channels.get() as usize * self.input.idle_chunk();
                     continue;
                 } else {
                     return None;
@@ -248,24 +763,125 @@ impl Iterator for SourcesQueueOutput {
 }
 
 impl SourcesQueueOutput {
+    /// Returns the label of the sound that is playing, if it was appended with
+    /// [`SourcesQueueInput::append_labeled`].
+    ///
+    /// Returns `None` once the queue ran out of sounds.
+    pub fn current_label(&self) -> Option<&str> {
+        self.current_label.as_deref()
+    }
+
     // Called when `current` is empty, and we must jump to the next element.
     // Returns `Ok` if there is another sound should continue playing, or `Err` when there is not.
     //
     // This method is separate so that it is not inlined.
     fn go_next(&mut self) -> Result<(), ()> {
         if let Some(signal_after_end) = self.signal_after_end.take() {
-            let _ = signal_after_end.send(());
+            signal_after_end.send();
         }
 
-        let (next, signal_after_end) = {
-            let mut next = self.input.next_sounds.lock().unwrap();
-            next.pop_front().ok_or(())?
+        let next = {
+            let mut peeked = self.peeked.borrow_mut();
+            self.receive_next(&mut peeked);
+            peeked.take().ok_or(())?
         };
+        if let Some(index) = next.index {
+            self.input.taken.store(index + 1, Ordering::Release);
+            if self.input.capacity.load(Ordering::Relaxed) != usize::MAX {
+                self.input.space_available.notify_all();
+            }
+        }
+        if let Some(factory) = next.factory {
+            if self.input.is_looping() {
+                self.playlist.get_mut().push_back(factory);
+            }
+        }
+
+        self.current = next.sound.expect("built when received");
+        self.current_label = next.label;
+        self.signal_after_end = next.signal_after_end;
+        self.silence_channels = self.current.channels();
+        self.silence_sample_rate = self.current.sample_rate();
 
-        self.current = next;
-        self.signal_after_end = signal_after_end;
+        while let Ok(callback) = self.callback_receiver.try_recv() {
+            if let Some(previous) = self.on_track_change.replace(callback) {
+                self.discard(Garbage::Callback(previous));
+            }
+        }
+        if let Some(on_track_change) = self.on_track_change.as_mut() {
+            on_track_change(self.tracks_started);
+        }
+        self.tracks_started = self.tracks_started.wrapping_add(1);
         Ok(())
     }
+
+    /// Hands something back to the input to be dropped there.
+    fn discard(&self, garbage: Garbage) {
+        // Fails if the input has not dropped the earlier garbage yet, because nothing was
+        // appended or cleared in a while. Then it is dropped here after all. Also fails if the
+        // input was dropped, which the output holds on to.
+        let _ = self.garbage.try_send(garbage);
+    }
+
+    /// Calls `f` with the sound that plays after the current one, if any was appended yet.
+    fn peek_next<R>(&self, f: impl FnOnce(&Sound) -> R) -> Option<R> {
+        let mut peeked = self.peeked.borrow_mut();
+        self.receive_next(&mut peeked);
+        peeked
+            .as_ref()
+            .map(|next| f(next.sound.as_ref().expect("built when received")))
+    }
+
+    /// Makes sure `peeked` holds the next sound that was not cleared, if there is one. When
+    /// looping and nothing was appended, that is the first sound of the playlist. Sounds
+    /// appended with a factory are built here.
+    fn receive_next(&self, peeked: &mut Option<QueuedSound>) {
+        let looping = self.input.is_looping();
+        let clears = self.input.clears.load(Ordering::Acquire);
+        if !looping || clears != self.clears_seen.get() {
+            self.clears_seen.set(clears);
+            for factory in self.playlist.borrow_mut().drain(..) {
+                self.discard(Garbage::Factory(factory));
+            }
+            if peeked.as_ref().is_some_and(|next| next.index.is_none()) {
+                self.discard(Garbage::Sound(peeked.take().expect("checked above")));
+            }
+        }
+
+        loop {
+            match peeked.as_ref().map(|next| next.index) {
+                Some(Some(index)) if index < self.input.cleared_until.load(Ordering::Acquire) => {
+                    self.input.taken.store(index + 1, Ordering::Release);
+                    self.discard(Garbage::Sound(peeked.take().expect("checked above")));
+                }
+                Some(_) => break,
+                None => {}
+            }
+            match self.receiver.try_recv() {
+                Ok(next) => *peeked = Some(next),
+                Err(_) => break,
+            }
+        }
+
+        if peeked.is_none() {
+            if let Some(factory) = self.playlist.borrow_mut().pop_front() {
+                *peeked = Some(QueuedSound {
+                    factory: Some(factory),
+                    ..QueuedSound::new(None)
+                });
+            }
+        }
+
+        if let Some(next) = peeked {
+            if next.sound.is_none() {
+                let factory = next
+                    .factory
+                    .as_mut()
+                    .expect("sounds without factory are built");
+                next.sound = Some(factory());
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -273,8 +889,16 @@ mod tests {
     use crate::buffer::SamplesBuffer;
     use crate::math::nz;
     use crate::queue;
+    use crate::source::test_util::collect;
     use crate::source::test_utils::TestSource;
-    use crate::source::Source;
+    use crate::source::{SeekError, Source};
+    use crate::Sample;
+    #[cfg(feature = "crossbeam-channel")]
+    use crossbeam_channel::TryRecvError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    #[cfg(not(feature = "crossbeam-channel"))]
+    use std::sync::mpsc::TryRecvError;
+    use std::sync::Arc;
 
     #[test]
     fn basic() {
@@ -306,6 +930,201 @@ mod tests {
         assert_eq!(rx.next(), None);
     }
 
+    #[test]
+    fn boxed_sources() {
+        let (tx, rx) = queue::queue(false);
+
+        let playlist: Vec<Box<dyn Source + Send>> = vec![
+            Box::new(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0, 2.0])),
+            Box::new(SamplesBuffer::new(nz!(1), nz!(48000), vec![3.0, 4.0]).amplify(2.0)),
+        ];
+        for source in playlist {
+            tx.append(source);
+        }
+
+        let (samples, channels, sample_rate) = collect(rx);
+        assert_eq!(samples, [1.0, 2.0, 6.0, 8.0]);
+        assert_eq!((channels, sample_rate), (nz!(1), nz!(48000)));
+    }
+
+    #[test]
+    fn boxed_source_forwards_seek() {
+        let mut source: Box<dyn Source + Send> =
+            Box::new(SamplesBuffer::new(nz!(1), nz!(2), vec![1.0, 2.0, 3.0]));
+        source.try_seek(std::time::Duration::from_secs(1)).unwrap();
+        assert_eq!(source.next(), Some(3.0));
+    }
+
+    #[test]
+    fn seek_without_source_is_not_supported() {
+        let (_tx, mut rx) = queue::queue(true);
+        let error = rx.try_seek(std::time::Duration::from_secs(1)).unwrap_err();
+        assert!(matches!(error, SeekError::NotSupported { .. }));
+        assert!(error.source_intact());
+    }
+
+    #[test]
+    fn track_change_callback() {
+        let (tx, rx) = queue::queue(true);
+        let (changes_tx, changes_rx) = std::sync::mpsc::channel();
+        tx.set_on_track_change(move |index| changes_tx.send(index).unwrap());
+
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0, 1.0]));
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+
+        // Keep-alive silence afterwards does not count as a track.
+        let _: Vec<_> = rx.take(100).collect();
+        assert_eq!(changes_rx.try_iter().collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn track_change_callback_skips_cleared_sounds() {
+        let (tx, mut rx) = queue::queue(true);
+        let (changes_tx, changes_rx) = std::sync::mpsc::channel();
+        tx.set_on_track_change(move |index| changes_tx.send(index).unwrap());
+
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]));
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+        assert_eq!(rx.next(), Some(1.0));
+        tx.clear();
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![3.0]));
+        assert_eq!(rx.next(), Some(3.0));
+        assert_eq!(changes_rx.try_iter().collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn replaced_callback_is_dropped_by_input() {
+        let (tx, mut rx) = queue::queue(true);
+        let first = Arc::new(());
+        let in_callback = first.clone();
+        tx.set_on_track_change(move |_| {
+            let _ = &in_callback;
+        });
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]));
+        assert_eq!(rx.next(), Some(1.0));
+
+        tx.set_on_track_change(|_| {});
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+        assert_eq!(rx.next(), Some(2.0));
+        // The output handed the first callback back instead of dropping it.
+        assert_eq!(Arc::strong_count(&first), 2);
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![3.0]));
+        assert_eq!(Arc::strong_count(&first), 1);
+    }
+
+    #[test]
+    fn cleared_sounds_are_dropped_by_input() {
+        let (tx, mut rx) = queue::queue(true);
+        let sound = Arc::new(());
+        let in_factory = sound.clone();
+        tx.append_factory(move || {
+            let _ = &in_factory;
+            SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0])
+        });
+        tx.clear();
+
+        assert_eq!(rx.next(), Some(0.0));
+        assert_eq!(Arc::strong_count(&sound), 2);
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+        assert_eq!(Arc::strong_count(&sound), 1);
+    }
+
+    #[test]
+    fn loops_playlist() {
+        let (tx, mut rx) = queue::queue(false);
+        tx.set_loop(true);
+        tx.append_factory(|| SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0, 1.0]));
+        let second = SamplesBuffer::new(nz!(2), nz!(44100), vec![2.0, 2.0]);
+        tx.append_factory(move || second.clone());
+        // Can not be rebuilt, so it only plays once.
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![3.0]));
+
+        let played: Vec<_> = rx.by_ref().take(7).collect();
+        assert_eq!(played, [1.0, 1.0, 2.0, 2.0, 3.0, 1.0, 1.0]);
+        // The format of the next sound is known before it plays again.
+        assert_eq!(rx.channels(), nz!(2));
+        assert_eq!(rx.sample_rate(), nz!(44100));
+        let played: Vec<_> = rx.by_ref().take(4).collect();
+        assert_eq!(played, [2.0, 2.0, 1.0, 1.0]);
+
+        tx.set_loop(false);
+        assert_eq!(rx.next(), None);
+    }
+
+    #[test]
+    fn factory_builds_sound_when_played() {
+        let (tx, mut rx) = queue::queue(false);
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]));
+        let built = Arc::new(AtomicUsize::new(0));
+        let counter = built.clone();
+        tx.append_factory(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Box::new(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0])) as Box<dyn Source + Send>
+        });
+
+        assert_eq!(rx.next(), Some(1.0));
+        assert_eq!(built.load(Ordering::Relaxed), 0);
+        assert_eq!(rx.next(), Some(2.0));
+        assert_eq!(built.load(Ordering::Relaxed), 1);
+
+        tx.set_loop(true);
+        // Only sounds that start while looping are played again.
+        assert_eq!(rx.next(), None);
+        tx.append_factory(|| SamplesBuffer::new(nz!(1), nz!(48000), vec![3.0]));
+        let looped: Vec<_> = rx.by_ref().take(3).collect();
+        assert_eq!(looped, [3.0, 3.0, 3.0]);
+        assert_eq!(built.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn clear_stops_loop() {
+        let (tx, mut rx) = queue::queue(false);
+        tx.set_loop(true);
+        tx.append_factory(|| SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]));
+        assert_eq!(rx.next(), Some(1.0));
+        assert_eq!(rx.next(), Some(1.0));
+
+        tx.clear();
+        assert_eq!(rx.next(), None);
+    }
+
+    #[test]
+    fn labels_follow_playing_sound() {
+        let (tx, mut rx) = queue::queue(true);
+        tx.append_labeled(
+            SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]),
+            "first".to_owned(),
+        );
+        tx.append_labeled(
+            SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]),
+            "second".to_owned(),
+        );
+        assert_eq!(rx.current_label(), None);
+
+        assert_eq!(rx.next(), Some(1.0));
+        assert_eq!(rx.current_label(), Some("first"));
+        assert_eq!(rx.next(), Some(2.0));
+        assert_eq!(rx.current_label(), Some("second"));
+        assert_eq!(rx.next(), Some(0.0));
+        assert_eq!(rx.current_label(), None);
+    }
+
+    #[test]
+    fn idle_chunk_sets_silence_length() {
+        let (tx, mut rx) = queue::queue(true);
+        tx.append(SamplesBuffer::new(nz!(2), nz!(48000), vec![1.0; 2]));
+        tx.set_idle_chunk(3);
+        assert_eq!(rx.by_ref().take(2).count(), 2);
+
+        assert_eq!(rx.current_span_len(), Some(6));
+        assert_eq!(rx.next(), Some(0.0));
+
+        // Appended while idle, plays after the chunk of silence.
+        tx.append(SamplesBuffer::new(nz!(2), nz!(48000), vec![2.0; 2]));
+        let played: Vec<_> = rx.by_ref().take(7).collect();
+        assert_eq!(played, [0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 2.0]);
+    }
+
     #[test]
     fn immediate_end() {
         let (_, mut rx) = queue::queue(false);
@@ -331,6 +1150,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keep_alive_silence_matches_last_sound() {
+        let (tx, mut rx) = queue::queue(true);
+        tx.append(SamplesBuffer::new(nz!(2), nz!(48000), vec![1.0; 4]));
+        assert_eq!(rx.by_ref().take(4).collect::<Vec<_>>(), vec![1.0; 4]);
+
+        // The silence comes in whole stereo frames.
+        assert_eq!(rx.current_span_len(), Some(2));
+        for _ in 0..10 {
+            assert_eq!(rx.channels(), nz!(2));
+            assert_eq!(rx.sample_rate(), nz!(48000));
+            assert_eq!(rx.next(), Some(0.0));
+        }
+    }
+
     #[test]
     fn no_delay_when_added() {
         let (tx, mut rx) = queue::queue(true);
@@ -441,4 +1275,127 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn try_append_rejects_when_full() {
+        let (tx, mut rx) = queue::queue(false);
+        tx.set_capacity(Some(2));
+        assert_eq!(tx.capacity(), Some(2));
+
+        let sound = |value| SamplesBuffer::new(nz!(1), nz!(48000), vec![value]);
+        assert!(tx.try_append(sound(1.0)).is_ok());
+        assert!(tx.try_append(sound(2.0)).is_ok());
+        let rejected = tx.try_append(sound(3.0)).unwrap_err().into_inner();
+        assert_eq!(rejected.collect::<Vec<_>>(), vec![3.0]);
+
+        // Playing the first sound makes room for one more.
+        assert_eq!(rx.next(), Some(1.0));
+        assert!(tx.try_append(sound(3.0)).is_ok());
+        assert_eq!(rx.collect::<Vec<_>>(), vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn append_blocking_waits_for_space() {
+        let (tx, mut rx) = queue::queue(false);
+        tx.set_capacity(Some(1));
+        tx.append_blocking(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]));
+
+        let producer = {
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                tx.append_blocking(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+            })
+        };
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!producer.is_finished(), "append should block while full");
+
+        assert_eq!(rx.next(), Some(1.0));
+        producer.join().unwrap();
+        assert_eq!(rx.next(), Some(2.0));
+    }
+
+    #[test]
+    fn clear_and_signal_notifies_waiting_sounds() {
+        let (tx, mut rx) = queue::queue(false);
+        let playing = tx.append_with_signal(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]));
+        let first = tx.append_with_signal(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+        let second = tx.append_with_signal(SamplesBuffer::new(nz!(1), nz!(48000), vec![3.0]));
+        assert_eq!(tx.pending_signals(), 3);
+        assert_eq!(rx.next(), Some(1.0));
+        assert_eq!(tx.pending_signals(), 2);
+
+        assert_eq!(tx.clear_and_signal(), 2);
+        assert_eq!(tx.pending_signals(), 0);
+        assert_eq!(first.try_recv(), Ok(()));
+        assert_eq!(second.try_recv(), Ok(()));
+        assert!(playing.try_recv().is_err());
+
+        assert_eq!(rx.next(), None);
+        assert_eq!(playing.try_recv(), Ok(()));
+    }
+
+    #[test]
+    fn pending_signals_skips_end_callbacks() {
+        let (tx, _rx) = queue::queue(false);
+        tx.append_with_callback(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]), || {});
+        let _signal = tx.append_with_signal(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+        assert_eq!(tx.pending_signals(), 1);
+    }
+
+    #[test]
+    fn clear_disconnects_dropped_sounds() {
+        let (tx, mut rx) = queue::queue(false);
+        let cleared = tx.append_with_signal(SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0]));
+        assert_eq!(tx.clear(), 1);
+        assert_eq!(tx.pending_signals(), 0);
+
+        assert_eq!(rx.next(), None);
+        assert_eq!(cleared.try_recv(), Err(TryRecvError::Empty));
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+        assert_eq!(cleared.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn clear_drops_peeked_sound() {
+        let (tx, rx) = queue::queue(false);
+        tx.append(SamplesBuffer::new(nz!(2), nz!(48000), vec![1.0, 1.0]));
+        // Reporting the channels receives the sound ahead of playing it.
+        assert_eq!(rx.channels(), nz!(2));
+
+        assert_eq!(tx.clear(), 1);
+        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![2.0]));
+        assert_eq!(rx.channels(), nz!(1));
+        assert_eq!(rx.collect::<Vec<_>>(), vec![2.0]);
+    }
+
+    #[test]
+    fn append_from_many_threads_keeps_order_per_thread() {
+        let (tx, rx) = queue::queue(false);
+        let producers: Vec<_> = (0..4)
+            .map(|thread| {
+                let tx = tx.clone();
+                std::thread::spawn(move || {
+                    for i in 0..100 {
+                        let value = (thread * 1000 + i) as Sample;
+                        tx.append(SamplesBuffer::new(nz!(1), nz!(48000), vec![value]));
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let played: Vec<Sample> = rx.collect();
+        assert_eq!(played.len(), 400);
+        for thread in 0..4 {
+            let from_thread: Vec<Sample> = played
+                .iter()
+                .copied()
+                .filter(|value| (*value as usize) / 1000 == thread)
+                .collect();
+            let expected: Vec<Sample> = (0..100).map(|i| (thread * 1000 + i) as Sample).collect();
+            assert_eq!(from_thread, expected);
+        }
+    }
 }
@@ -3,7 +3,7 @@ use std::time::Duration;
 use super::SeekError;
 use crate::{
     common::{ChannelCount, Float, SampleRate},
-    math, Source,
+    math, Sample, Source,
 };
 
 /// Internal function that builds a `Amplify` object.
@@ -96,6 +96,20 @@ where
         self.input.total_duration()
     }
 
+    #[inline]
+    fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+        let written = self.input.fill_buffer(out);
+        for sample in &mut out[..written] {
+            *sample *= self.factor;
+        }
+        written
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.input.try_seek(pos)
@@ -0,0 +1,207 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, Float, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds an `AmplifyChecked` object and its `ClipFlag`.
+pub(crate) fn amplify_checked<I>(input: I, factor: Float) -> (AmplifyChecked<I>, ClipFlag)
+where
+    I: Source,
+{
+    let flag = ClipFlag {
+        clipped: Arc::new(AtomicBool::new(false)),
+    };
+    let source = AmplifyChecked {
+        input,
+        factor,
+        flag: flag.clone(),
+    };
+    (source, flag)
+}
+
+/// Filter that modifies each sample by a given value and reports when the result clips.
+///
+/// Works like [`Amplify`](super::Amplify), but raises its [`ClipFlag`] whenever a sample ends
+/// up outside of ±1.0. The samples themselves are not changed, they are only clipped when
+/// converted for the output device.
+#[derive(Clone, Debug)]
+pub struct AmplifyChecked<I> {
+    input: I,
+    factor: Float,
+    flag: ClipFlag,
+}
+
+impl<I> AmplifyChecked<I> {
+    /// Returns the amplification factor.
+    #[inline]
+    pub fn factor(&self) -> Float {
+        self.factor
+    }
+
+    /// Modifies the amplification factor.
+    #[inline]
+    pub fn set_factor(&mut self, factor: Float) {
+        self.factor = factor;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for AmplifyChecked<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()? * self.factor;
+        if sample.abs() > 1.0 {
+            self.flag.raise();
+        }
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for AmplifyChecked<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for AmplifyChecked<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+        let written = self.input.fill_buffer(out);
+        let mut clipped = false;
+        for sample in &mut out[..written] {
+            *sample *= self.factor;
+            clipped |= sample.abs() > 1.0;
+        }
+        if clipped {
+            self.flag.raise();
+        }
+        written
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+/// Tells whether an [`AmplifyChecked`] source produced samples outside of ±1.0.
+///
+/// The flag stays raised until [`reset`](ClipFlag::reset) is called, so a UI can poll it to
+/// light a clip indicator. Can be cloned and sent to other threads.
+#[derive(Clone, Debug)]
+pub struct ClipFlag {
+    clipped: Arc<AtomicBool>,
+}
+
+impl ClipFlag {
+    /// Returns `true` if a sample clipped since the source was created or the flag was reset.
+    #[inline]
+    pub fn clipped(&self) -> bool {
+        self.clipped.load(Ordering::Relaxed)
+    }
+
+    /// Lowers the flag.
+    #[inline]
+    pub fn reset(&self) {
+        self.clipped.store(false, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn raise(&self) {
+        // Avoid writing to the shared cache line on every clipped sample.
+        if !self.clipped() {
+            self.clipped.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn flag_trips_on_clipping() {
+        let source = || SamplesBuffer::new(nz!(1), nz!(1000), vec![0.4, 0.8, -0.2]);
+
+        let (quiet, flag) = source().amplify_checked(1.2);
+        assert_eq!(quiet.count(), 3);
+        assert!(!flag.clipped());
+
+        let (mut loud, flag) = source().amplify_checked(2.0);
+        assert_eq!(loud.next(), Some(0.8));
+        assert!(!flag.clipped());
+        assert_eq!(loud.next(), Some(1.6));
+        assert!(flag.clipped());
+
+        flag.reset();
+        assert!(!flag.clipped());
+        assert_eq!(loud.next(), Some(-0.4));
+        assert!(!flag.clipped());
+    }
+
+    #[test]
+    fn fill_buffer_trips_flag() {
+        let (mut loud, flag) =
+            SamplesBuffer::new(nz!(1), nz!(1000), vec![0.4, 0.8, -0.2]).amplify_checked(2.0);
+        let mut out = [0.0; 4];
+        assert_eq!(loud.fill_buffer(&mut out), 3);
+        assert_eq!(out[..3], [0.8, 1.6, -0.4]);
+        assert!(flag.clipped());
+    }
+}
@@ -0,0 +1,217 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, Float, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds a `BitCrush` object.
+pub(crate) fn bit_crush<I>(input: I, bits: u8, downsample_factor: u32) -> BitCrush<I>
+where
+    I: Source,
+{
+    assert_valid_bits(bits);
+    assert_valid_downsample_factor(downsample_factor);
+    BitCrush {
+        input,
+        bits,
+        downsample_factor,
+        held: Vec::new(),
+        channel: 0,
+        channels: 0,
+        hold_frames: 0,
+    }
+}
+
+fn assert_valid_bits(bits: u8) {
+    assert!(bits > 0, "bits must be at least 1");
+}
+
+fn assert_valid_downsample_factor(downsample_factor: u32) {
+    assert!(
+        downsample_factor > 0,
+        "downsample factor must be at least 1"
+    );
+}
+
+/// Filter that reduces the resolution and the sample rate of a sound, for a lo-fi effect.
+///
+/// Every sample is rounded to one of the levels a signed integer of `bits` bits can take. Every
+/// `downsample_factor` frames one frame is kept and repeated in place of the frames that follow.
+/// This is done without any filtering, so the aliasing of the lower rate can be heard, which is
+/// part of the effect.
+#[derive(Clone, Debug)]
+pub struct BitCrush<I> {
+    input: I,
+    bits: u8,
+    downsample_factor: u32,
+    // The quantized frame that is repeated, filled while its first frame plays.
+    held: Vec<Sample>,
+    // Position in the current frame and the number of channels it has.
+    channel: usize,
+    channels: usize,
+    // Frames left to play from `held`, including the current one.
+    hold_frames: u32,
+}
+
+impl<I> BitCrush<I> {
+    /// Returns the resolution samples are rounded to.
+    #[inline]
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Changes the resolution samples are rounded to, starting with the next held frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is zero.
+    #[inline]
+    pub fn set_bits(&mut self, bits: u8) {
+        assert_valid_bits(bits);
+        self.bits = bits;
+    }
+
+    /// Returns for how many frames every kept frame is played.
+    #[inline]
+    pub fn downsample_factor(&self) -> u32 {
+        self.downsample_factor
+    }
+
+    /// Changes for how many frames every kept frame is played, starting with the next held
+    /// frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `downsample_factor` is zero.
+    #[inline]
+    pub fn set_downsample_factor(&mut self, downsample_factor: u32) {
+        assert_valid_downsample_factor(downsample_factor);
+        self.downsample_factor = downsample_factor;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    fn quantize(&self, sample: Sample) -> Sample {
+        let steps = Float::powi(2.0, i32::from(self.bits) - 1);
+        (sample * steps).round() / steps
+    }
+}
+
+impl<I> Iterator for BitCrush<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.channel == 0 {
+            self.channels = self.input.channels().get() as usize;
+            if self.hold_frames == 0 || self.held.len() != self.channels {
+                self.held.clear();
+                self.hold_frames = self.downsample_factor;
+            }
+            self.hold_frames -= 1;
+        }
+
+        let sample = self.input.next()?;
+        let channel = self.channel;
+        self.channel = (self.channel + 1) % self.channels;
+        if channel == self.held.len() {
+            let quantized = self.quantize(sample);
+            self.held.push(quantized);
+        }
+        Some(self.held[channel])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for BitCrush<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for BitCrush<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.held.clear();
+        self.channel = 0;
+        self.hold_frames = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn one_bit_has_few_levels() {
+        let samples: Vec<Sample> = (0..100).map(|i| (i as Float / 50.0) - 1.0).collect();
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), samples);
+        let mut levels: Vec<Sample> = source.bit_crush(1, 1).collect();
+        levels.dedup();
+        assert_eq!(levels, [-1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn holds_frames() {
+        let samples = vec![
+            0.25, -0.25, 0.5, -0.5, 0.75, -0.75, 1.0, -1.0, 0.125, -0.125,
+        ];
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), samples);
+        let output: Vec<Sample> = source.bit_crush(8, 2).collect();
+        assert_eq!(
+            output,
+            [0.25, -0.25, 0.25, -0.25, 0.75, -0.75, 0.75, -0.75, 0.125, -0.125]
+        );
+    }
+}
@@ -39,6 +39,36 @@ where
     blt_filter(input, BltFormula::HighPass { freq, q })
 }
 
+/// Builds a `BltFilter` object with a low-shelf filter.
+pub fn low_shelf<I>(input: I, freq: u32, gain_db: Float, slope: Float) -> BltFilter<I>
+where
+    I: Source<Item = Sample>,
+{
+    blt_filter(
+        input,
+        BltFormula::LowShelf {
+            freq,
+            gain_db,
+            slope,
+        },
+    )
+}
+
+/// Builds a `BltFilter` object with a high-shelf filter.
+pub fn high_shelf<I>(input: I, freq: u32, gain_db: Float, slope: Float) -> BltFilter<I>
+where
+    I: Source<Item = Sample>,
+{
+    blt_filter(
+        input,
+        BltFormula::HighShelf {
+            freq,
+            gain_db,
+            slope,
+        },
+    )
+}
+
 /// Common constructor for BLT filters
 fn blt_filter<I>(input: I, formula: BltFormula) -> BltFilter<I>
 where
@@ -53,7 +83,7 @@ where
     }
 }
 
-/// This applies an audio filter, it can be a high or low pass filter.
+/// This applies an audio filter, it can be a high or low pass filter or a high or low shelf.
 #[derive(Clone, Debug)]
 pub struct BltFilter<I> {
     inner: Option<BltInner<I>>,
@@ -90,6 +120,30 @@ where
             .set_formula(BltFormula::HighPass { freq, q });
     }
 
+    /// Modifies this filter so that it becomes a low-shelf filter.
+    pub fn to_low_shelf(&mut self, freq: u32, gain_db: Float, slope: Float) {
+        self.inner
+            .as_mut()
+            .unwrap()
+            .set_formula(BltFormula::LowShelf {
+                freq,
+                gain_db,
+                slope,
+            });
+    }
+
+    /// Modifies this filter so that it becomes a high-shelf filter.
+    pub fn to_high_shelf(&mut self, freq: u32, gain_db: Float, slope: Float) {
+        self.inner
+            .as_mut()
+            .unwrap()
+            .set_formula(BltFormula::HighShelf {
+                freq,
+                gain_db,
+                slope,
+            });
+    }
+
     /// Returns a reference to the inner source.
     #[inline]
     pub fn inner(&self) -> &I {
@@ -175,6 +229,11 @@ where
         self.inner.as_ref().unwrap().total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner.as_ref().unwrap().is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.inner.as_mut().unwrap().try_seek(pos)?;
@@ -349,6 +408,11 @@ where
         self.inner().total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner().is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         match self {
@@ -497,8 +561,24 @@ where
 
 #[derive(Clone, Debug)]
 enum BltFormula {
-    LowPass { freq: u32, q: Float },
-    HighPass { freq: u32, q: Float },
+    LowPass {
+        freq: u32,
+        q: Float,
+    },
+    HighPass {
+        freq: u32,
+        q: Float,
+    },
+    LowShelf {
+        freq: u32,
+        gain_db: Float,
+        slope: Float,
+    },
+    HighShelf {
+        freq: u32,
+        gain_db: Float,
+        slope: Float,
+    },
 }
 
 impl BltFormula {
@@ -535,6 +615,52 @@ impl BltFormula {
                 let a1 = -2.0 * cos_w0;
                 let a2 = 1.0 - alpha;
 
+                BltApplier {
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                }
+            }
+            BltFormula::LowShelf {
+                freq,
+                gain_db,
+                slope,
+            } => {
+                let (a, cos_w0, two_sqrt_a_alpha) =
+                    shelf_terms(freq, gain_db, slope, sampling_frequency);
+
+                let b0 = a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha);
+                let b1 = 2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0);
+                let b2 = a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha);
+                let a0 = (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+                let a1 = -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0);
+                let a2 = (a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha;
+
+                BltApplier {
+                    b0: b0 / a0,
+                    b1: b1 / a0,
+                    b2: b2 / a0,
+                    a1: a1 / a0,
+                    a2: a2 / a0,
+                }
+            }
+            BltFormula::HighShelf {
+                freq,
+                gain_db,
+                slope,
+            } => {
+                let (a, cos_w0, two_sqrt_a_alpha) =
+                    shelf_terms(freq, gain_db, slope, sampling_frequency);
+
+                let b0 = a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha);
+                let b1 = -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0);
+                let b2 = a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha);
+                let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+                let a1 = 2.0 * ((a - 1.0) - (a + 1.0) * cos_w0);
+                let a2 = (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha;
+
                 BltApplier {
                     b0: b0 / a0,
                     b1: b1 / a0,
@@ -547,6 +673,22 @@ impl BltFormula {
     }
 }
 
+/// Terms shared by both shelves: `A`, `cos(w0)` and `2 * sqrt(A) * alpha`.
+fn shelf_terms(
+    freq: u32,
+    gain_db: Float,
+    slope: Float,
+    sampling_frequency: u32,
+) -> (Float, Float, Float) {
+    let a = Float::powf(10.0, gain_db / 40.0);
+    let w0 = 2.0 * PI * freq as Float / sampling_frequency as Float;
+    // Slopes above 1.0 make the response overshoot around the corner frequency.
+    let slope = slope.clamp(Float::EPSILON, 1.0);
+    let alpha = w0.sin() / 2.0 * ((a + 1.0 / a) * (1.0 / slope - 1.0) + 2.0).sqrt();
+
+    (a, w0.cos(), 2.0 * a.sqrt() * alpha)
+}
+
 #[derive(Clone, Debug)]
 struct BltApplier {
     b0: Float,
@@ -568,4 +710,30 @@ mod tests {
     use super::*;
     use crate::math::nz;
     use crate::source::test_utils::TestSource;
+    use crate::source::SineWave;
+
+    /// Gain in dB of a sine wave at `freq` after the filter settled.
+    fn gain_db(freq: f32, filter: impl Fn(SineWave) -> BltFilter<SineWave>) -> Float {
+        let rms = |source: &mut dyn Iterator<Item = Sample>| {
+            let sum: Float = source.skip(4800).take(48000).map(|s| s * s).sum();
+            (sum / 48000.0).sqrt()
+        };
+        let dry = rms(&mut SineWave::new(freq));
+        let filtered = rms(&mut filter(SineWave::new(freq)));
+        20.0 * (filtered / dry).log10()
+    }
+
+    #[test]
+    fn low_shelf_boosts_lows() {
+        let shelf = |source: SineWave| source.low_shelf(200, 6.0, 1.0);
+        assert!((gain_db(50.0, shelf) - 6.0).abs() < 0.3);
+        assert!(gain_db(5000.0, shelf).abs() < 0.3);
+    }
+
+    #[test]
+    fn high_shelf_cuts_highs() {
+        let shelf = |source: SineWave| source.high_shelf(4000, -6.0, 1.0);
+        assert!((gain_db(15000.0, shelf) + 6.0).abs() < 0.3);
+        assert!(gain_db(100.0, shelf).abs() < 0.3);
+    }
 }
@@ -236,6 +236,12 @@ where
         self.total_duration
     }
 
+    /// Never seekable, see [`try_seek`](Source::try_seek).
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        false
+    }
+
     /// Can not support seek, in the end state we lose the underlying source
     /// which makes seeking back impossible.
     #[inline]
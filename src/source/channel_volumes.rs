@@ -0,0 +1,205 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Float, Sample, Source};
+
+/// Internal function that builds a `ChannelVolumes` object.
+// `Float` is `f64` with the `64bit` feature.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn channel_volumes<I>(input: I, gains: Vec<Float>) -> ChannelVolumes<I>
+where
+    I: Source,
+{
+    let gains = gains
+        .into_iter()
+        .map(|gain| AtomicU32::new((gain as f32).to_bits()))
+        .collect();
+    ChannelVolumes {
+        input,
+        gains: Arc::new(gains),
+        channel: 0,
+        channels: 0,
+    }
+}
+
+/// Filter that multiplies every channel with its own gain.
+///
+/// Channels without a gain play unchanged, gains for channels the source does not have are
+/// ignored. The gains can be changed while playing through a [`ChannelVolumesHandle`].
+#[derive(Clone, Debug)]
+pub struct ChannelVolumes<I> {
+    input: I,
+    // Gain of every channel as `f32` bits.
+    gains: Arc<Vec<AtomicU32>>,
+    // Position in the current frame and the number of channels it has.
+    channel: usize,
+    channels: usize,
+}
+
+impl<I> ChannelVolumes<I> {
+    /// Returns a handle to change the gains while the source is playing.
+    #[inline]
+    pub fn handle(&self) -> ChannelVolumesHandle {
+        ChannelVolumesHandle {
+            gains: Arc::clone(&self.gains),
+        }
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for ChannelVolumes<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.channel == 0 {
+            self.channels = self.input.channels().get() as usize;
+        }
+        let sample = self.input.next()?;
+        let gain = self.gains.get(self.channel).map_or(1.0, |gain| {
+            f32::from_bits(gain.load(Ordering::Relaxed)) as Float
+        });
+        self.channel = (self.channel + 1) % self.channels;
+        Some(sample * gain)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for ChannelVolumes<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for ChannelVolumes<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.channel = 0;
+        Ok(())
+    }
+}
+
+/// Changes the gains of a [`ChannelVolumes`] filter while it is playing.
+///
+/// Can be cloned and sent to other threads, for example a UI thread.
+#[derive(Clone, Debug)]
+pub struct ChannelVolumesHandle {
+    gains: Arc<Vec<AtomicU32>>,
+}
+
+impl ChannelVolumesHandle {
+    /// Returns the number of gains.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.gains.len()
+    }
+
+    /// Returns `true` if no gains were given.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.gains.is_empty()
+    }
+
+    /// Returns the gain of a channel, or `None` if no gain was given for it.
+    #[inline]
+    pub fn gain(&self, channel: usize) -> Option<Float> {
+        self.gains
+            .get(channel)
+            .map(|gain| f32::from_bits(gain.load(Ordering::Relaxed)) as Float)
+    }
+
+    /// Sets the gain of a channel. Does nothing if no gain was given for it.
+    ///
+    /// The new gain is applied from the next sample of that channel on.
+    // `Float` is `f64` with the `64bit` feature.
+    #[allow(clippy::unnecessary_cast)]
+    #[inline]
+    pub fn set_gain(&self, channel: usize, gain: Float) {
+        if let Some(stored) = self.gains.get(channel) {
+            stored.store((gain as f32).to_bits(), Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn silences_left_channel() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.5, 0.5, -0.25, -0.25]);
+        let output: Vec<Sample> = source.channel_volumes(vec![0.0, 1.0]).collect();
+        assert_eq!(output, [0.0, 0.5, 0.0, -0.25]);
+    }
+
+    #[test]
+    fn handle_changes_gains() {
+        let source = SamplesBuffer::new(nz!(3), nz!(1000), vec![1.0; 6]);
+        let mut source = source.channel_volumes(vec![0.5]);
+        let handle = source.handle();
+        assert_eq!(handle.len(), 1);
+
+        // Missing gains play unchanged.
+        assert_eq!(source.by_ref().take(3).collect::<Vec<_>>(), [0.5, 1.0, 1.0]);
+
+        handle.set_gain(0, 0.25);
+        handle.set_gain(1, 0.0);
+        assert_eq!(handle.gain(0), Some(0.25));
+        assert_eq!(handle.gain(1), None);
+        assert_eq!(source.collect::<Vec<_>>(), [0.25, 1.0, 1.0]);
+    }
+}
@@ -91,6 +91,11 @@ impl Source for Chirp {
         Some(Duration::from_secs_f64(secs))
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         let mut target = (pos.as_secs_f64() * self.sample_rate.get() as f64) as u64;
         if target >= self.total_samples {
@@ -0,0 +1,265 @@
+use std::time::Duration;
+
+use super::fractional_delay::FractionalDelay;
+use super::{SeekError, SpanTracker};
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::{duration_to_float, TAU};
+use crate::{Float, Sample, Source};
+
+/// Center of the delay of every voice, long enough to be heard as separate performers.
+const BASE_DELAY: Duration = Duration::from_millis(20);
+
+/// Longest delay swing around the center.
+const MAX_DEPTH: Duration = Duration::from_millis(10);
+
+/// Internal function that builds a `Chorus` object.
+pub(crate) fn chorus<I>(
+    input: I,
+    voices: usize,
+    rate_hz: Float,
+    depth: Duration,
+    mix: Float,
+) -> Chorus<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let channels = input.channels();
+    let voices = voices.max(1);
+    let depth = depth.min(MAX_DEPTH);
+
+    Chorus {
+        input,
+        voices,
+        rate_hz,
+        depth,
+        mix: mix.clamp(0.0, 1.0),
+        delay_line: DelayLine::new(sample_rate, channels, depth),
+        span: SpanTracker::new(sample_rate, channels),
+    }
+}
+
+/// Filter that mixes the source with several delayed copies of itself, each slightly detuned.
+///
+/// Every voice reads the source around 20 ms later, with the delay slowly swinging by `depth`.
+/// The voices swing out of phase with each other, so at any moment some are a little sharp and
+/// others a little flat, which makes one instrument sound like an ensemble. The depth is
+/// limited to 10 ms.
+#[derive(Clone, Debug)]
+pub struct Chorus<I> {
+    input: I,
+    voices: usize,
+    rate_hz: Float,
+    depth: Duration,
+    mix: Float,
+    delay_line: DelayLine,
+    span: SpanTracker,
+}
+
+impl<I> Chorus<I>
+where
+    I: Source,
+{
+    /// Returns the number of delayed copies.
+    #[inline]
+    pub fn voices(&self) -> usize {
+        self.voices
+    }
+
+    /// Returns how often per second the delay of each voice swings up and down.
+    #[inline]
+    pub fn rate_hz(&self) -> Float {
+        self.rate_hz
+    }
+
+    /// Changes how often per second the delay of each voice swings up and down.
+    #[inline]
+    pub fn set_rate_hz(&mut self, rate_hz: Float) {
+        self.rate_hz = rate_hz;
+    }
+
+    /// Returns how far the delay of each voice swings around its center.
+    #[inline]
+    pub fn depth(&self) -> Duration {
+        self.depth
+    }
+
+    /// Returns the part of the output that is the delayed voices.
+    #[inline]
+    pub fn mix(&self) -> Float {
+        self.mix
+    }
+
+    /// Changes the part of the output that is the delayed voices, from `0.0` (only the source)
+    /// to `1.0` (only the voices).
+    #[inline]
+    pub fn set_mix(&mut self, mix: Float) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for Chorus<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+
+        let input_span_len = self.input.current_span_len();
+        let current_sample_rate = self.input.sample_rate();
+        let current_channels = self.input.channels();
+
+        let detection = self
+            .span
+            .advance(input_span_len, current_sample_rate, current_channels);
+        if detection.at_span_boundary && detection.parameters_changed {
+            self.delay_line = DelayLine::new(current_sample_rate, current_channels, self.depth);
+        }
+
+        let voices = self.delay_line.process(sample, self.voices, self.rate_hz);
+        Some(sample + (voices - sample) * self.mix)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Chorus<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Chorus<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.span.seek(pos, &self.input);
+        self.delay_line =
+            DelayLine::new(self.input.sample_rate(), self.input.channels(), self.depth);
+
+        Ok(())
+    }
+}
+
+/// Delay line read by several taps swinging around the base delay.
+#[derive(Clone, Debug)]
+struct DelayLine {
+    delay: FractionalDelay,
+    sample_rate: Float,
+    // Base delay and delay swing around it, in frames.
+    base: Float,
+    depth: Float,
+    // Position within the modulation period of the first voice, in `[0.0, 1.0)`.
+    phase: Float,
+}
+
+impl DelayLine {
+    fn new(sample_rate: SampleRate, channels: ChannelCount, depth: Duration) -> Self {
+        let sample_rate = sample_rate.get() as Float;
+        let base = duration_to_float(BASE_DELAY) * sample_rate;
+        let depth = duration_to_float(depth) * sample_rate;
+
+        Self {
+            delay: FractionalDelay::new(channels, base + depth),
+            sample_rate,
+            base,
+            depth,
+            phase: 0.0,
+        }
+    }
+
+    /// Returns the average of all voices.
+    #[inline]
+    fn process(&mut self, sample: Sample, voices: usize, rate_hz: Float) -> Sample {
+        self.delay.write(sample);
+        let sum: Sample = (0..voices)
+            .map(|voice| {
+                let phase = self.phase + voice as Float / voices as Float;
+                let delay = self.base + self.depth * (TAU * phase).sin();
+                self.delay.read(delay)
+            })
+            .sum();
+
+        if self.delay.advance() {
+            self.phase = (self.phase + rate_hz / self.sample_rate).rem_euclid(1.0);
+        }
+
+        sum / voices as Sample
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn voices_follow_base_delay() {
+        // A single click comes back from every voice around 20 ms later.
+        let mut samples = vec![0.0; 4800];
+        samples[0] = 1.0;
+        let source = SamplesBuffer::new(nz!(1), nz!(48000), samples);
+
+        let output: Vec<Sample> = source
+            .chorus(3, 1.0, Duration::from_millis(2), 1.0)
+            .collect();
+        assert_eq!(output.len(), 4800);
+
+        let echo_start = output.iter().position(|s| s.abs() > 1e-6).unwrap();
+        let echo_end = output.iter().rposition(|s| s.abs() > 1e-6).unwrap();
+        assert!(echo_start >= 960 - 96 && echo_end <= 960 + 96 + 1);
+
+        // All voices together carry the whole click.
+        let total: Sample = output.iter().sum();
+        assert!((total - 1.0).abs() < 1e-4);
+    }
+}
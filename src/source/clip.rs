@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use super::skip::do_skip_duration;
+use super::take::take_duration;
+use super::{SeekError, TakeDuration};
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds a `Clip` object.
+pub fn clip<I>(mut input: I, start: Duration, end: Duration) -> Clip<I>
+where
+    I: Source,
+{
+    let length = end.saturating_sub(start);
+    let total_duration = input
+        .total_duration()
+        .map_or(length, |total| length.min(total.saturating_sub(start)));
+
+    if !length.is_zero() && input.try_seek(start).is_err() {
+        do_skip_duration(&mut input, start);
+    }
+
+    Clip {
+        input: take_duration(input, length),
+        start,
+        total_duration,
+    }
+}
+
+/// A source that only plays the part of another source between two positions.
+#[derive(Clone, Debug)]
+pub struct Clip<I> {
+    input: TakeDuration<I>,
+    start: Duration,
+    total_duration: Duration,
+}
+
+impl<I> Clip<I>
+where
+    I: Source,
+{
+    /// Returns the position in the inner source where the clip starts.
+    #[inline]
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        self.input.inner()
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        self.input.inner_mut()
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input.into_inner()
+    }
+}
+
+impl<I> Iterator for Clip<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        self.input.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Clip<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Clip<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        Some(self.total_duration)
+    }
+
+    /// Returns whether the inner source can seek.
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    /// Seeks relative to the start of the clip.
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input
+            .try_seek_from(self.start, pos.min(self.total_duration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    // One second of mono audio at 10 Hz where every sample is its own index.
+    fn counting() -> SamplesBuffer {
+        SamplesBuffer::new(
+            nz!(1),
+            nz!(10),
+            (0..10).map(|i| i as Sample).collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn plays_part_of_seekable_source() {
+        let clip = counting().clip(Duration::from_millis(200), Duration::from_millis(500));
+        assert_eq!(clip.total_duration(), Some(Duration::from_millis(300)));
+        assert_eq!(clip.collect::<Vec<_>>(), [2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn plays_part_of_non_seekable_source() {
+        let source = counting().buffered();
+        assert!(source.clone().try_seek(Duration::ZERO).is_err());
+
+        let clip = source.clip(Duration::from_millis(200), Duration::from_millis(500));
+        assert_eq!(clip.total_duration(), Some(Duration::from_millis(300)));
+        assert_eq!(clip.collect::<Vec<_>>(), [2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn end_past_source_clamps() {
+        let clip = counting().clip(Duration::from_millis(700), Duration::from_secs(5));
+        assert_eq!(clip.total_duration(), Some(Duration::from_millis(300)));
+        assert_eq!(clip.collect::<Vec<_>>(), [7.0, 8.0, 9.0]);
+    }
+
+    #[test]
+    fn start_after_end_is_empty() {
+        let clip = counting().clip(Duration::from_millis(500), Duration::from_millis(200));
+        assert_eq!(clip.total_duration(), Some(Duration::ZERO));
+        assert_eq!(clip.count(), 0);
+    }
+
+    #[test]
+    fn seeks_relative_to_start() {
+        let mut clip = counting().clip(Duration::from_millis(200), Duration::from_millis(500));
+        clip.next();
+        clip.try_seek(Duration::from_millis(100)).unwrap();
+        assert_eq!(clip.collect::<Vec<_>>(), [3.0, 4.0]);
+    }
+}
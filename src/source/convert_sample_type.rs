@@ -0,0 +1,176 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use dasp_sample::FromSample;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds a `ConvertSampleType` object.
+pub fn convert_sample_type<I, S>(input: I) -> ConvertSampleType<I, S>
+where
+    I: Source,
+    S: FromSample<Sample>,
+    Sample: FromSample<S>,
+{
+    ConvertSampleType {
+        input,
+        marker: PhantomData,
+    }
+}
+
+/// Source that converts every sample to the type `S` and back.
+///
+/// See [`Source::convert_sample_type`] for details.
+#[derive(Clone, Debug)]
+pub struct ConvertSampleType<I, S> {
+    input: I,
+    // `fn() -> S` keeps this source `Send` and `Sync` whatever `S` is.
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<I, S> ConvertSampleType<I, S> {
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I, S> Iterator for ConvertSampleType<I, S>
+where
+    I: Source,
+    S: FromSample<Sample>,
+    Sample: FromSample<S>,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+        Some(Sample::from_sample_(S::from_sample_(sample)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I, S> ExactSizeIterator for ConvertSampleType<I, S>
+where
+    I: Source + ExactSizeIterator,
+    S: FromSample<Sample>,
+    Sample: FromSample<S>,
+{
+}
+
+impl<I, S> Source for ConvertSampleType<I, S>
+where
+    I: Source,
+    S: FromSample<Sample>,
+    Sample: FromSample<S>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SineWave;
+    use crate::Float;
+
+    const LSB: Float = 1.0 / 32768.0;
+
+    /// RMS of the difference between the converted and the original signal, in LSBs.
+    fn error_rms(converted: impl Iterator<Item = Sample>) -> Float {
+        let original = SineWave::new(440.0).amplify(100.0 * LSB);
+        let (sum, count) = converted.zip(original).take(48000).fold(
+            (0.0, 0),
+            |(sum, count), (converted, original)| {
+                let error = (converted - original) / LSB;
+                (sum + error * error, count + 1)
+            },
+        );
+        (sum / count as Float).sqrt()
+    }
+
+    #[test]
+    fn quantizes_to_target_type() {
+        let mut source = SineWave::new(440.0)
+            .convert_sample_type::<i16>()
+            .take(48000);
+        assert!(source.all(|s| (s / LSB).fract() == 0.0));
+
+        let mut floats = SineWave::new(440.0)
+            .convert_sample_type::<f32>()
+            .zip(SineWave::new(440.0))
+            .take(48000);
+        assert!(floats.all(|(a, b)| a == b));
+    }
+
+    #[cfg(feature = "dither")]
+    #[test]
+    fn quantization_noise_with_and_without_dither() {
+        use crate::source::DitherAlgorithm;
+        use crate::BitDepth;
+
+        let quiet = || SineWave::new(440.0).amplify(100.0 * LSB);
+
+        // Conversion truncates towards zero, so the error is up to one LSB.
+        let plain = error_rms(quiet().convert_sample_type::<i16>());
+        assert!(plain > 0.3 && plain < 1.0, "plain error was {plain} LSB");
+
+        // TPDF dither adds noise of its own on top of the quantization error.
+        let dithered = error_rms(
+            quiet()
+                .dither(BitDepth::new(16).unwrap(), DitherAlgorithm::TPDF)
+                .convert_sample_type::<i16>(),
+        );
+        assert!(
+            dithered > plain && dithered < 1.5,
+            "dithered error was {dithered} LSB"
+        );
+    }
+}
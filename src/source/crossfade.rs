@@ -34,6 +34,7 @@ mod tests {
     use super::*;
     use crate::buffer::SamplesBuffer;
     use crate::math::nz;
+    use crate::source::test_util::{assert_samples_eq, collect};
     use crate::source::Zero;
     use crate::Sample;
 
@@ -46,20 +47,14 @@ mod tests {
     fn test_crossfade_with_self() {
         let source1 = dummy_source(10);
         let source2 = dummy_source(10);
-        let mut mixed = crossfade(
+        let mixed = crossfade(
             source1,
             source2,
             Duration::from_secs(5) + Duration::from_nanos(1),
         );
 
-        // Use approximate equality for floating-point comparisons
-        let eps = 1e-6;
-        assert!((mixed.next().unwrap() - 1.0).abs() < eps);
-        assert!((mixed.next().unwrap() - 2.0).abs() < eps);
-        assert!((mixed.next().unwrap() - 3.0).abs() < eps);
-        assert!((mixed.next().unwrap() - 4.0).abs() < eps);
-        assert!((mixed.next().unwrap() - 5.0).abs() < eps);
-        assert_eq!(mixed.next(), None);
+        let (result, _, _) = collect(mixed);
+        assert_samples_eq(&result, &[1.0, 2.0, 3.0, 4.0, 5.0], 1e-6);
     }
 
     #[test]
@@ -71,11 +66,11 @@ mod tests {
             source2,
             Duration::from_secs(5) + Duration::from_nanos(1),
         );
-        let result = mixed.collect::<Vec<_>>();
-        assert_eq!(result.len(), 5);
-        assert!(result
-            .iter()
-            .zip(vec![1.0, 2.0 * 0.8, 3.0 * 0.6, 4.0 * 0.4, 5.0 * 0.2])
-            .all(|(a, b)| (a - b).abs() < 1e-6));
+        let (result, _, _) = collect(mixed);
+        assert_samples_eq(
+            &result,
+            &[1.0, 2.0 * 0.8, 3.0 * 0.6, 4.0 * 0.4, 5.0 * 0.2],
+            1e-6,
+        );
     }
 }
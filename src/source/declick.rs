@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::NANOS_PER_SEC;
+use crate::{Float, Sample, Source};
+
+/// A ramp for [`Source::declick_on_seek`] that is short enough to not be noticed while it
+/// removes the clicks.
+pub const DEFAULT_DECLICK_RAMP: Duration = Duration::from_millis(5);
+
+/// Internal function that builds a `DeclickOnSeek` object.
+pub(crate) fn declick_on_seek<I>(input: I, ramp: Duration) -> DeclickOnSeek<I>
+where
+    I: Source,
+{
+    let tail = Vec::with_capacity(ramp_samples(ramp, &input));
+    DeclickOnSeek {
+        input,
+        ramp,
+        tail,
+        tail_pos: 0,
+        tail_gain: None,
+        fade_in_len: 0,
+        fade_in_remaining: 0,
+    }
+}
+
+/// Number of samples a ramp takes at the current format of `input`, at least one frame.
+fn ramp_samples(ramp: Duration, input: &impl Source) -> usize {
+    let rate = input.sample_rate().get() as u128;
+    let frames = (ramp.as_nanos() * rate / NANOS_PER_SEC as u128).max(1) as usize;
+    frames * input.channels().get() as usize
+}
+
+/// Filter that fades out before and fades in after every seek to avoid clicks.
+///
+/// Jumping to another position in a waveform usually makes it jump in level too, which is
+/// heard as a click. On a seek this first plays a short fade-out of the audio that would have
+/// played next, then seeks and fades the new position in, each taking the ramp duration. The
+/// fade-out is read from the source before seeking, so the seek itself still happens right
+/// away and reports its errors as usual.
+#[derive(Clone, Debug)]
+pub struct DeclickOnSeek<I> {
+    input: I,
+    ramp: Duration,
+    // Samples read from the old position before the last seek.
+    tail: Vec<Sample>,
+    tail_pos: usize,
+    // Gain at the start of the fade-out, `None` if the seek failed and the tail is played
+    // unchanged.
+    tail_gain: Option<Float>,
+    fade_in_len: usize,
+    fade_in_remaining: usize,
+}
+
+impl<I> DeclickOnSeek<I> {
+    /// Returns the duration of the fades.
+    #[inline]
+    pub fn ramp(&self) -> Duration {
+        self.ramp
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for DeclickOnSeek<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if let Some(&sample) = self.tail.get(self.tail_pos) {
+            self.tail_pos += 1;
+            let Some(tail_gain) = self.tail_gain else {
+                return Some(sample);
+            };
+            let gain = 1.0 - self.tail_pos as Float / self.tail.len() as Float;
+            return Some(sample * gain * tail_gain);
+        }
+
+        let sample = self.input.next()?;
+        if self.fade_in_remaining == 0 {
+            return Some(sample);
+        }
+        let gain = (self.fade_in_len - self.fade_in_remaining + 1) as Float
+            / (self.fade_in_len + 1) as Float;
+        self.fade_in_remaining -= 1;
+        Some(sample * gain)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let tail = self.tail.len() - self.tail_pos;
+        let (lower, upper) = self.input.size_hint();
+        (
+            lower.saturating_add(tail),
+            upper.and_then(|upper| upper.checked_add(tail)),
+        )
+    }
+}
+
+impl<I> Source for DeclickOnSeek<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        // Fade out from the current level, a seek during a fade should not jump to full level.
+        let gain = if self.tail_pos < self.tail.len() {
+            let played = self.tail_pos as Float / self.tail.len() as Float;
+            self.tail_gain.map_or(1.0, |gain| gain * (1.0 - played))
+        } else if self.fade_in_remaining > 0 {
+            (self.fade_in_len - self.fade_in_remaining) as Float / (self.fade_in_len + 1) as Float
+        } else {
+            1.0
+        };
+
+        let tail_len = ramp_samples(self.ramp, &self.input);
+        self.tail.clear();
+        self.tail_pos = 0;
+        self.tail.extend(self.input.by_ref().take(tail_len));
+
+        self.tail_gain = None;
+        self.fade_in_remaining = 0;
+        self.input.try_seek(pos)?;
+        self.tail_gain = Some(gain);
+        self.fade_in_len = ramp_samples(self.ramp, &self.input);
+        self.fade_in_remaining = self.fade_in_len;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn fades_around_seek() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 100]);
+        let mut source = source.declick_on_seek(Duration::from_millis(4));
+        assert_eq!(source.next(), Some(1.0));
+
+        source.try_seek(Duration::from_millis(50)).unwrap();
+        let played: Vec<Sample> = source.by_ref().take(9).collect();
+        // Fade out what would have played, then fade in the new position from near zero.
+        assert_eq!(played[..4], [0.75, 0.5, 0.25, 0.0]);
+        assert_eq!(played[4..], [0.2, 0.4, 0.6, 0.8, 1.0]);
+        assert_eq!(source.size_hint(), (45, Some(45)));
+    }
+
+    #[test]
+    fn failed_seek_plays_on() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5; 10]).buffered();
+        let mut source = source.declick_on_seek(Duration::from_millis(4));
+        assert!(source.try_seek(Duration::ZERO).is_err());
+        assert_eq!(source.collect::<Vec<_>>(), [0.5; 10]);
+    }
+}
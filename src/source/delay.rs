@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use super::SeekError;
 use crate::common::{ChannelCount, SampleRate};
-use crate::math::NANOS_PER_SEC;
+use crate::math::{frame_to_duration, NANOS_PER_SEC};
 use crate::Source;
 
 fn remaining_samples(
@@ -114,6 +114,11 @@ where
             .map(|val| val + self.requested_duration)
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     /// Pos is seen from the perspective of the api user.
     ///
     /// # Example
@@ -139,3 +144,155 @@ where
         self.input.try_seek(compensated_for_delay)
     }
 }
+
+/// Internal function that builds a `DelayFrames` object.
+pub fn delay_frames<I>(input: I, frames: usize) -> DelayFrames<I>
+where
+    I: Source,
+{
+    DelayFrames {
+        remaining_samples: frames * input.channels().get() as usize,
+        duration: frame_to_duration(frames as u64, input.sample_rate()),
+        frames,
+        input,
+    }
+}
+
+/// A source that delays the given source by a number of frames, see
+/// [`Source::delay_frames`].
+#[derive(Clone, Debug)]
+pub struct DelayFrames<I> {
+    input: I,
+    remaining_samples: usize,
+    frames: usize,
+    duration: Duration,
+}
+
+impl<I> DelayFrames<I>
+where
+    I: Source,
+{
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for DelayFrames<I>
+where
+    I: Source,
+{
+    type Item = <I as Iterator>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<<I as Iterator>::Item> {
+        if self.remaining_samples >= 1 {
+            self.remaining_samples -= 1;
+            Some(0.0)
+        } else {
+            self.input.next()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, max) = self.input.size_hint();
+        (
+            min + self.remaining_samples,
+            max.map(|v| v + self.remaining_samples),
+        )
+    }
+}
+
+impl<I> ExactSizeIterator for DelayFrames<I> where I: Iterator + Source + ExactSizeIterator {}
+
+impl<I> Source for DelayFrames<I>
+where
+    I: Iterator + Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input
+            .current_span_len()
+            .map(|val| val + self.remaining_samples)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration().map(|val| val + self.duration)
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    /// Pos is seen from the perspective of the api user, like for [`Delay`].
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        if pos < self.duration {
+            self.input.try_seek(Duration::ZERO)?;
+            let frame = pos.as_nanos() * self.sample_rate().get() as u128 / NANOS_PER_SEC as u128;
+            self.remaining_samples =
+                (self.frames - frame as usize) * self.channels().get() as usize;
+            Ok(())
+        } else {
+            self.input.try_seek(pos - self.duration)?;
+            self.remaining_samples = 0;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn delay_frames_prepends_silence() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.5, -0.5, 0.25, -0.25]);
+        let delayed = delay_frames(source, 3);
+        assert_eq!(delayed.size_hint(), (10, Some(10)));
+        assert_eq!(delayed.total_duration(), Some(Duration::from_millis(5)));
+        assert_eq!(
+            delayed.collect::<Vec<_>>(),
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.5, -0.5, 0.25, -0.25]
+        );
+    }
+
+    #[test]
+    fn delay_frames_seek() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5, 0.25]);
+        let mut delayed = delay_frames(source, 4);
+        delayed.try_seek(Duration::from_millis(2)).unwrap();
+        assert_eq!(delayed.by_ref().collect::<Vec<_>>(), [0.0, 0.0, 0.5, 0.25]);
+
+        delayed.try_seek(Duration::from_millis(5)).unwrap();
+        assert_eq!(delayed.collect::<Vec<_>>(), [0.25]);
+    }
+}
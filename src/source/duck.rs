@@ -0,0 +1,433 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::{self, duration_to_coefficient};
+use crate::{Float, Sample, Source};
+
+/// How quickly the measured level of the key falls, long enough to not follow single periods.
+const KEY_RELEASE: Duration = Duration::from_millis(50);
+
+/// Lowers the volume of a target source while a key source is loud, known as sidechain
+/// ducking.
+///
+/// A typical use is to lower background music while a voice plays. Wrap the voice with
+/// [`Source::duck_key`] to measure its level and the music with [`Source::ducked_by`] to
+/// apply the reduction. The two sources can play in different [`Player`](crate::Player)s or
+/// mixers, the `Ducker` is shared between them and can be cloned.
+///
+/// The level of the key is the peak of its samples. When it rises above the threshold the
+/// target is lowered by `reduction_db` over the attack time, and once it falls below the
+/// threshold the target returns to its normal volume over the release time. When the key
+/// source ends the target returns to its normal volume too. Use one key source per `Ducker`,
+/// several key sources would overwrite each other's level.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use rodio::source::{Ducker, SineWave, Source};
+/// use rodio::Player;
+///
+/// let ducker = Ducker::new(-30.0, 12.0, Duration::from_millis(20), Duration::from_millis(500));
+///
+/// let (music_player, _music_output) = Player::new();
+/// music_player.append(SineWave::new(220.0).amplify(0.2).ducked_by(&ducker));
+///
+/// // The music is 12 dB quieter while the voice plays.
+/// let (voice_player, _voice_output) = Player::new();
+/// let voice = SineWave::new(440.0).take_duration(Duration::from_secs(2));
+/// voice_player.append(voice.duck_key(&ducker));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ducker {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    // Level of the key source as `f32` bits.
+    key_level: AtomicU32,
+    threshold: Float,
+    reduced_gain: Float,
+    attack: Duration,
+    release: Duration,
+}
+
+impl Ducker {
+    /// Builds a ducker that lowers the target by `reduction_db` while the key is above
+    /// `threshold_db`.
+    ///
+    /// Both are in decibels relative to full scale, so a threshold of `-30.0` and a reduction
+    /// of `12.0` lower the target by 12 dB while the key peaks above -30 dBFS. The target
+    /// moves to the lower volume over `attack` and back over `release`.
+    pub fn new(
+        threshold_db: Float,
+        reduction_db: Float,
+        attack: Duration,
+        release: Duration,
+    ) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                key_level: AtomicU32::new(0.0f32.to_bits()),
+                threshold: math::db_to_linear(threshold_db),
+                reduced_gain: math::db_to_linear(-reduction_db.abs()),
+                attack,
+                release,
+            }),
+        }
+    }
+
+    /// Returns `true` while the key source is above the threshold.
+    #[inline]
+    pub fn is_ducking(&self) -> bool {
+        self.key_level() >= self.shared.threshold
+    }
+
+    #[inline]
+    fn key_level(&self) -> Float {
+        f32::from_bits(self.shared.key_level.load(Ordering::Relaxed)) as Float
+    }
+
+    // `Float` is `f64` with the `64bit` feature.
+    #[allow(clippy::unnecessary_cast)]
+    #[inline]
+    fn set_key_level(&self, level: Float) {
+        self.shared
+            .key_level
+            .store((level as f32).to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Internal function that builds a `DuckKey` object.
+pub(crate) fn duck_key<I>(input: I, ducker: &Ducker) -> DuckKey<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    DuckKey {
+        input,
+        ducker: ducker.clone(),
+        level: 0.0,
+        release_coeff: duration_to_coefficient(KEY_RELEASE, sample_rate),
+        sample_rate,
+        frame_peak: 0.0,
+        frame_channels: 0,
+        samples_in_frame: 0,
+    }
+}
+
+/// Source that measures its level for a [`Ducker`], see [`Source::duck_key`].
+///
+/// The samples pass through unchanged.
+#[derive(Debug)]
+pub struct DuckKey<I> {
+    input: I,
+    ducker: Ducker,
+    level: Float,
+    release_coeff: Float,
+    // Sample rate `release_coeff` was computed for.
+    sample_rate: SampleRate,
+    frame_peak: Sample,
+    // Channels of the frame being played, and how many of its samples were played.
+    frame_channels: usize,
+    samples_in_frame: usize,
+}
+
+impl<I> DuckKey<I> {
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+}
+
+impl<I> Iterator for DuckKey<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.samples_in_frame == 0 {
+            // The channel count can only change at the start of a frame.
+            self.frame_channels = self.input.channels().get() as usize;
+        }
+
+        let Some(sample) = self.input.next() else {
+            self.level = 0.0;
+            self.ducker.set_key_level(0.0);
+            return None;
+        };
+
+        self.frame_peak = self.frame_peak.max(sample.abs());
+        self.samples_in_frame += 1;
+        if self.samples_in_frame == self.frame_channels {
+            self.samples_in_frame = 0;
+            let sample_rate = self.input.sample_rate();
+            if sample_rate != self.sample_rate {
+                self.sample_rate = sample_rate;
+                self.release_coeff = duration_to_coefficient(KEY_RELEASE, sample_rate);
+            }
+            self.level = self.frame_peak.max(self.level * self.release_coeff);
+            self.frame_peak = 0.0;
+            self.ducker.set_key_level(self.level);
+        }
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> Drop for DuckKey<I> {
+    fn drop(&mut self) {
+        // Skipped or cleared sources are dropped without reaching their end.
+        if self.level > 0.0 {
+            self.ducker.set_key_level(0.0);
+        }
+    }
+}
+
+impl<I> Source for DuckKey<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.samples_in_frame = 0;
+        self.frame_peak = 0.0;
+        Ok(())
+    }
+}
+
+/// Internal function that builds a `Ducked` object.
+pub(crate) fn ducked<I>(input: I, ducker: &Ducker) -> Ducked<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let mut ducked = Ducked {
+        input,
+        ducker: ducker.clone(),
+        gain: 1.0,
+        attack_coeff: 0.0,
+        release_coeff: 0.0,
+        sample_rate,
+        frame_channels: 0,
+        samples_in_frame: 0,
+    };
+    ducked.update_timing(sample_rate);
+    ducked
+}
+
+/// Source whose volume is lowered by a [`Ducker`], see [`Source::ducked_by`].
+#[derive(Clone, Debug)]
+pub struct Ducked<I> {
+    input: I,
+    ducker: Ducker,
+    gain: Float,
+    attack_coeff: Float,
+    release_coeff: Float,
+    // Sample rate the coefficients were computed for.
+    sample_rate: SampleRate,
+    // Channels of the frame being played, and how many of its samples were played.
+    frame_channels: usize,
+    samples_in_frame: usize,
+}
+
+impl<I> Ducked<I> {
+    /// Returns the gain applied to the current frame, `1.0` when not ducked.
+    #[inline]
+    pub fn gain(&self) -> Float {
+        self.gain
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    fn update_timing(&mut self, sample_rate: SampleRate) {
+        self.sample_rate = sample_rate;
+        self.attack_coeff = duration_to_coefficient(self.ducker.shared.attack, sample_rate);
+        self.release_coeff = duration_to_coefficient(self.ducker.shared.release, sample_rate);
+    }
+}
+
+impl<I> Iterator for Ducked<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.samples_in_frame == 0 {
+            // The channel count can only change at the start of a frame.
+            self.frame_channels = self.input.channels().get() as usize;
+        }
+
+        let output = self.input.next()? * self.gain;
+
+        self.samples_in_frame += 1;
+        if self.samples_in_frame == self.frame_channels {
+            self.samples_in_frame = 0;
+            let sample_rate = self.input.sample_rate();
+            if sample_rate != self.sample_rate {
+                self.update_timing(sample_rate);
+            }
+            let (target, coeff) = if self.ducker.is_ducking() {
+                (self.ducker.shared.reduced_gain, self.attack_coeff)
+            } else {
+                (1.0, self.release_coeff)
+            };
+            self.gain = target + coeff * (self.gain - target);
+        }
+        Some(output)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Ducked<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Ducked<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.samples_in_frame = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn target_dips_while_key_is_loud() {
+        let ducker = Ducker::new(
+            -20.0,
+            12.0,
+            Duration::from_millis(10),
+            Duration::from_millis(100),
+        );
+        // 100ms of silence, a 200ms burst and silence again, at 1kHz.
+        let mut key_samples = vec![0.0; 1000];
+        key_samples[100..300].fill(0.8);
+        let mut key = SamplesBuffer::new(nz!(1), nz!(1000), key_samples).duck_key(&ducker);
+        let mut target = SamplesBuffer::new(nz!(2), nz!(1000), vec![1.0; 3000]).ducked_by(&ducker);
+
+        // Play both at the same pace, like two players on one device.
+        let mut gains = Vec::new();
+        while key.next().is_some() {
+            target.next();
+            target.next();
+            gains.push(target.gain());
+        }
+
+        assert_eq!(gains[99], 1.0);
+        let reduced = math::db_to_linear(-12.0);
+        assert!((gains[299] - reduced).abs() < 0.01, "{}", gains[299]);
+        assert!(gains[999] > 0.95, "{}", gains[999]);
+        assert!(!ducker.is_ducking());
+
+        // The target recovers fully once the key has ended.
+        let rest: Vec<Sample> = target.collect();
+        assert!((rest[rest.len() - 1] - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn dropping_key_stops_ducking() {
+        let ducker = Ducker::new(-20.0, 12.0, Duration::ZERO, Duration::ZERO);
+        let mut key = SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 10]).duck_key(&ducker);
+        key.next();
+        assert!(ducker.is_ducking());
+        drop(key);
+        assert!(!ducker.is_ducking());
+    }
+}
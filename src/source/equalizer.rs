@@ -0,0 +1,383 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{SeekError, SpanTracker};
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::PI;
+use crate::{Float, Sample, Source};
+
+/// Internal function that builds an `Equalizer` object.
+pub fn equalizer<I>(input: I, bands: Vec<EqBand>) -> Equalizer<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let channels = input.channels();
+
+    let gains = bands
+        .iter()
+        .map(|band| AtomicU32::new(band.gain_db.to_bits()))
+        .collect();
+    let filters = bands
+        .iter()
+        .map(|band| PeakingFilter::new(band, sample_rate))
+        .collect();
+    let state = vec![BiquadState::default(); bands.len() * channels.get() as usize];
+
+    Equalizer {
+        input,
+        bands,
+        gains: Arc::new(gains),
+        filters,
+        state,
+        channels: channels.get() as usize,
+        current_channel: 0,
+        span: SpanTracker::new(sample_rate, channels),
+    }
+}
+
+/// A single band of an [`Equalizer`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EqBand {
+    /// Center frequency of the band in Hz.
+    pub freq: f32,
+    /// Quality factor. Higher values make the band narrower.
+    pub q: f32,
+    /// Gain at the center frequency in decibels. Negative values cut.
+    pub gain_db: f32,
+}
+
+/// Filter that applies a cascade of peaking filters, one per band.
+///
+/// Every band is a biquad peaking filter following the
+/// [Audio EQ Cookbook](https://webaudio.github.io/Audio-EQ-Cookbook/audio-eq-cookbook.html).
+/// The gain of each band can be changed while playing through an [`EqualizerHandle`].
+#[derive(Clone, Debug)]
+pub struct Equalizer<I> {
+    input: I,
+    bands: Vec<EqBand>,
+    gains: Arc<Vec<AtomicU32>>,
+    filters: Vec<PeakingFilter>,
+    // Filter state for every band and channel, indexed by `band * channels + channel`.
+    state: Vec<BiquadState>,
+    channels: usize,
+    current_channel: usize,
+    span: SpanTracker,
+}
+
+impl<I> Equalizer<I>
+where
+    I: Source,
+{
+    /// Returns a handle to change the gain of the bands while the source is playing.
+    #[inline]
+    pub fn handle(&self) -> EqualizerHandle {
+        EqualizerHandle {
+            gains: Arc::clone(&self.gains),
+        }
+    }
+
+    /// Returns the bands with their current gain.
+    pub fn bands(&self) -> Vec<EqBand> {
+        self.bands
+            .iter()
+            .zip(self.gains.iter())
+            .map(|(band, gain)| EqBand {
+                gain_db: f32::from_bits(gain.load(Ordering::Relaxed)),
+                ..*band
+            })
+            .collect()
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    /// Recomputes the coefficients of bands whose gain changed through a handle.
+    #[inline]
+    fn update_gains(&mut self) {
+        let sample_rate = self.input.sample_rate();
+        for ((band, gain), filter) in self
+            .bands
+            .iter_mut()
+            .zip(self.gains.iter())
+            .zip(self.filters.iter_mut())
+        {
+            let gain_db = f32::from_bits(gain.load(Ordering::Relaxed));
+            if gain_db != band.gain_db {
+                band.gain_db = gain_db;
+                *filter = PeakingFilter::new(band, sample_rate);
+            }
+        }
+    }
+
+    fn reset(&mut self, sample_rate: SampleRate, channels: ChannelCount) {
+        self.channels = channels.get() as usize;
+        self.current_channel = 0;
+        self.state = vec![BiquadState::default(); self.bands.len() * self.channels];
+        self.filters = self
+            .bands
+            .iter()
+            .map(|band| PeakingFilter::new(band, sample_rate))
+            .collect();
+    }
+}
+
+impl<I> Iterator for Equalizer<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+
+        let input_span_len = self.input.current_span_len();
+        let current_sample_rate = self.input.sample_rate();
+        let current_channels = self.input.channels();
+
+        let detection = self
+            .span
+            .advance(input_span_len, current_sample_rate, current_channels);
+        if detection.at_span_boundary && detection.parameters_changed {
+            self.reset(current_sample_rate, current_channels);
+        }
+
+        // Only pick up new gains between frames so all channels of a frame match.
+        if self.current_channel == 0 {
+            self.update_gains();
+        }
+
+        let channel = self.current_channel;
+        self.current_channel = (self.current_channel + 1) % self.channels;
+
+        let output = self
+            .filters
+            .iter()
+            .zip(self.state.chunks_exact_mut(self.channels))
+            .fold(sample, |sample, (filter, state)| {
+                filter.apply(sample, &mut state[channel])
+            });
+        Some(output)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Equalizer<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Equalizer<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.span.seek(pos, &self.input);
+        self.reset(self.input.sample_rate(), self.input.channels());
+
+        Ok(())
+    }
+}
+
+/// Changes the gain of the bands of an [`Equalizer`] while it is playing.
+///
+/// Can be cloned and sent to other threads, for example a UI thread.
+#[derive(Clone, Debug)]
+pub struct EqualizerHandle {
+    gains: Arc<Vec<AtomicU32>>,
+}
+
+impl EqualizerHandle {
+    /// Returns the number of bands.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.gains.len()
+    }
+
+    /// Returns `true` if the equalizer has no bands.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.gains.is_empty()
+    }
+
+    /// Returns the gain of a band in decibels, or `None` if there is no such band.
+    #[inline]
+    pub fn gain_db(&self, band: usize) -> Option<f32> {
+        self.gains
+            .get(band)
+            .map(|gain| f32::from_bits(gain.load(Ordering::Relaxed)))
+    }
+
+    /// Sets the gain of a band in decibels. Does nothing if there is no such band.
+    ///
+    /// The new gain is applied from the next frame on.
+    #[inline]
+    pub fn set_gain_db(&self, band: usize, gain_db: f32) {
+        if let Some(gain) = self.gains.get(band) {
+            gain.store(gain_db.to_bits(), Ordering::Relaxed);
+        }
+    }
+}
+
+/// Coefficients of a peaking biquad, normalized by `a0`.
+#[derive(Clone, Debug)]
+struct PeakingFilter {
+    b0: Float,
+    b1: Float,
+    b2: Float,
+    a1: Float,
+    a2: Float,
+}
+
+impl PeakingFilter {
+    fn new(band: &EqBand, sample_rate: SampleRate) -> Self {
+        let sample_rate = sample_rate.get() as Float;
+        // Keep the center frequency below Nyquist so the filter stays stable.
+        let freq = (band.freq as Float).clamp(1.0, sample_rate * 0.49);
+
+        let a = Float::powf(10.0, band.gain_db as Float / 40.0);
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * band.q as Float);
+        let cos_w0 = w0.cos();
+
+        let a0 = 1.0 + alpha / a;
+        PeakingFilter {
+            b0: (1.0 + alpha * a) / a0,
+            b1: (-2.0 * cos_w0) / a0,
+            b2: (1.0 - alpha * a) / a0,
+            a1: (-2.0 * cos_w0) / a0,
+            a2: (1.0 - alpha / a) / a0,
+        }
+    }
+
+    #[inline]
+    fn apply(&self, sample: Sample, state: &mut BiquadState) -> Sample {
+        let result = self.b0 * sample + self.b1 * state.x_n1 + self.b2 * state.x_n2
+            - self.a1 * state.y_n1
+            - self.a2 * state.y_n2;
+
+        state.x_n2 = state.x_n1;
+        state.x_n1 = sample;
+        state.y_n2 = state.y_n1;
+        state.y_n1 = result;
+
+        result
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct BiquadState {
+    x_n1: Float,
+    x_n2: Float,
+    y_n1: Float,
+    y_n2: Float,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SineWave;
+
+    fn rms(samples: impl Iterator<Item = Sample>) -> Float {
+        let (sum, count) = samples.fold((0.0, 0), |(sum, count), s| (sum + s * s, count + 1));
+        (sum / count as Float).sqrt()
+    }
+
+    fn band(freq: f32, gain_db: f32) -> EqBand {
+        EqBand {
+            freq,
+            q: 1.0,
+            gain_db,
+        }
+    }
+
+    #[test]
+    fn boosts_center_frequency() {
+        let dry = rms(SineWave::new(1000.0).skip(4800).take(48000));
+        let boosted = rms(SineWave::new(1000.0)
+            .equalizer(vec![band(1000.0, 6.0)])
+            .skip(4800)
+            .take(48000));
+
+        let gain_db = 20.0 * (boosted / dry).log10();
+        assert!(
+            (gain_db - 6.0).abs() < 0.1,
+            "gain at center was {gain_db}dB"
+        );
+    }
+
+    #[test]
+    fn leaves_distant_frequencies_alone() {
+        let dry = rms(SineWave::new(50.0).skip(4800).take(48000));
+        let filtered = rms(SineWave::new(50.0)
+            .equalizer(vec![band(8000.0, 12.0), band(10000.0, -12.0)])
+            .skip(4800)
+            .take(48000));
+
+        let gain_db = 20.0 * (filtered / dry).log10();
+        assert!(
+            gain_db.abs() < 0.1,
+            "gain far from the bands was {gain_db}dB"
+        );
+    }
+
+    #[test]
+    fn handle_changes_gain() {
+        let mut source = SineWave::new(1000.0).equalizer(vec![band(1000.0, 6.0)]);
+        let handle = source.handle();
+        assert_eq!(handle.len(), 1);
+
+        handle.set_gain_db(0, 0.0);
+        assert_eq!(handle.gain_db(0), Some(0.0));
+
+        let dry = rms(SineWave::new(1000.0).skip(4800).take(48000));
+        let flat = rms(source.by_ref().skip(4800).take(48000));
+        assert!((flat / dry - 1.0).abs() < 1e-3);
+        assert_eq!(source.bands()[0].gain_db, 0.0);
+    }
+}
@@ -1,16 +1,24 @@
 use std::time::Duration;
 
-use super::{linear_ramp::linear_gain_ramp, LinearGainRamp, SeekError};
+use super::{linear_ramp::gain_ramp_with_curve, FadeCurve, LinearGainRamp, SeekError};
 use crate::common::{ChannelCount, SampleRate};
 use crate::Source;
 
 /// Internal function that builds a `FadeIn` object.
 pub fn fadein<I>(input: I, duration: Duration) -> FadeIn<I>
+where
+    I: Source,
+{
+    fadein_with_curve(input, duration, FadeCurve::Linear)
+}
+
+/// Internal function that builds a `FadeIn` object following `curve`.
+pub fn fadein_with_curve<I>(input: I, duration: Duration, curve: FadeCurve) -> FadeIn<I>
 where
     I: Source,
 {
     FadeIn {
-        input: linear_gain_ramp(input, duration, 0.0, 1.0, false),
+        input: gain_ramp_with_curve(input, duration, 0.0, 1.0, false, curve),
     }
 }
 
@@ -86,6 +94,11 @@ where
         self.inner().total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner().is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.inner_mut().try_seek(pos)
@@ -1,16 +1,24 @@
 use std::time::Duration;
 
-use super::{linear_ramp::linear_gain_ramp, LinearGainRamp, SeekError};
+use super::{linear_ramp::gain_ramp_with_curve, FadeCurve, LinearGainRamp, SeekError};
 use crate::common::{ChannelCount, SampleRate};
 use crate::Source;
 
 /// Internal function that builds a `FadeOut` object.
 pub fn fadeout<I>(input: I, duration: Duration) -> FadeOut<I>
+where
+    I: Source,
+{
+    fadeout_with_curve(input, duration, FadeCurve::Linear)
+}
+
+/// Internal function that builds a `FadeOut` object following `curve`.
+pub fn fadeout_with_curve<I>(input: I, duration: Duration, curve: FadeCurve) -> FadeOut<I>
 where
     I: Source,
 {
     FadeOut {
-        input: linear_gain_ramp(input, duration, 1.0, 0.0, true),
+        input: gain_ramp_with_curve(input, duration, 1.0, 0.0, true, curve),
     }
 }
 
@@ -86,6 +94,11 @@ where
         self.inner().total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner().is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.inner_mut().try_seek(pos)
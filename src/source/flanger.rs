@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use super::fractional_delay::FractionalDelay;
+use super::{SeekError, SpanTracker};
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::{duration_to_float, TAU};
+use crate::{Float, Sample, Source};
+
+/// Longest delay swing around the center, longer delays sound like a chorus or echo.
+const MAX_DEPTH: Duration = Duration::from_millis(5);
+
+/// Largest feedback, higher values ring for a long time and at `1.0` never decay.
+const MAX_FEEDBACK: Float = 0.95;
+
+/// Internal function that builds a `Flanger` object.
+pub(crate) fn flanger<I>(
+    input: I,
+    rate_hz: Float,
+    depth: Duration,
+    feedback: Float,
+    mix: Float,
+) -> Flanger<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let channels = input.channels();
+    let depth = depth.min(MAX_DEPTH);
+
+    Flanger {
+        input,
+        rate_hz,
+        depth,
+        feedback: feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK),
+        mix: mix.clamp(0.0, 1.0),
+        delay_line: DelayLine::new(sample_rate, channels, depth),
+        span: SpanTracker::new(sample_rate, channels),
+    }
+}
+
+/// Filter that mixes the source with a copy delayed by a few milliseconds, with the delay
+/// slowly sweeping up and down.
+///
+/// Mixing a signal with a delayed copy cancels evenly spaced frequencies, a comb filter. As the
+/// delay moves the notches sweep through the spectrum, giving the characteristic jet-plane
+/// sound. Feeding the delayed signal back into the delay line makes the notches and peaks
+/// sharper.
+///
+/// The delay moves between one frame and one frame plus twice `depth`, starting in the middle.
+/// The depth is limited to 5 ms and the feedback to ±0.95 to keep the filter stable.
+#[derive(Clone, Debug)]
+pub struct Flanger<I> {
+    input: I,
+    rate_hz: Float,
+    depth: Duration,
+    feedback: Float,
+    mix: Float,
+    delay_line: DelayLine,
+    span: SpanTracker,
+}
+
+impl<I> Flanger<I>
+where
+    I: Source,
+{
+    /// Returns how often per second the delay sweeps up and down.
+    #[inline]
+    pub fn rate_hz(&self) -> Float {
+        self.rate_hz
+    }
+
+    /// Changes how often per second the delay sweeps up and down.
+    #[inline]
+    pub fn set_rate_hz(&mut self, rate_hz: Float) {
+        self.rate_hz = rate_hz;
+    }
+
+    /// Returns how far the delay swings around its center.
+    #[inline]
+    pub fn depth(&self) -> Duration {
+        self.depth
+    }
+
+    /// Returns the part of the delayed signal that is fed back into the delay line.
+    #[inline]
+    pub fn feedback(&self) -> Float {
+        self.feedback
+    }
+
+    /// Changes the part of the delayed signal that is fed back into the delay line. Negative
+    /// values invert it. Clamped to ±0.95.
+    #[inline]
+    pub fn set_feedback(&mut self, feedback: Float) {
+        self.feedback = feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+    }
+
+    /// Returns the part of the output that is the delayed signal.
+    #[inline]
+    pub fn mix(&self) -> Float {
+        self.mix
+    }
+
+    /// Changes the part of the output that is the delayed signal, from `0.0` (only the source)
+    /// to `1.0` (only the delayed signal). The strongest notches are at `0.5`.
+    #[inline]
+    pub fn set_mix(&mut self, mix: Float) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for Flanger<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+
+        let input_span_len = self.input.current_span_len();
+        let current_sample_rate = self.input.sample_rate();
+        let current_channels = self.input.channels();
+
+        let detection = self
+            .span
+            .advance(input_span_len, current_sample_rate, current_channels);
+        if detection.at_span_boundary && detection.parameters_changed {
+            self.delay_line = DelayLine::new(current_sample_rate, current_channels, self.depth);
+        }
+
+        let delayed = self.delay_line.process(sample, self.rate_hz, self.feedback);
+        Some(sample + (delayed - sample) * self.mix)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Flanger<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Flanger<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.span.seek(pos, &self.input);
+        self.delay_line =
+            DelayLine::new(self.input.sample_rate(), self.input.channels(), self.depth);
+
+        Ok(())
+    }
+}
+
+/// Delay line with feedback whose delay moves between one frame and one plus twice the depth.
+#[derive(Clone, Debug)]
+struct DelayLine {
+    delay: FractionalDelay,
+    sample_rate: Float,
+    // Delay swing around the center, in frames.
+    depth: Float,
+    // Position within the modulation period, in `[0.0, 1.0)`.
+    phase: Float,
+}
+
+impl DelayLine {
+    fn new(sample_rate: SampleRate, channels: ChannelCount, depth: Duration) -> Self {
+        let sample_rate = sample_rate.get() as Float;
+        let depth = duration_to_float(depth) * sample_rate;
+
+        Self {
+            delay: FractionalDelay::new(channels, 1.0 + 2.0 * depth),
+            sample_rate,
+            depth,
+            phase: 0.0,
+        }
+    }
+
+    /// Returns the delayed signal and feeds it back together with `sample`.
+    #[inline]
+    fn process(&mut self, sample: Sample, rate_hz: Float, feedback: Float) -> Sample {
+        // At least one frame, the current frame is only written after reading.
+        let delayed = self
+            .delay
+            .read(1.0 + self.depth * (1.0 + (TAU * self.phase).sin()));
+        self.delay.write(sample + delayed * feedback);
+
+        if self.delay.advance() {
+            self.phase = (self.phase + rate_hz / self.sample_rate).rem_euclid(1.0);
+        }
+
+        delayed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SineWave;
+
+    /// Peak level after the delay line has settled.
+    fn peak(source: impl Source) -> Float {
+        source
+            .skip(4800)
+            .take(4800)
+            .fold(0.0, |peak, sample| sample.abs().max(peak))
+    }
+
+    #[test]
+    fn comb_filter_notches() {
+        // Without modulation the delay stays at 1 + 47 frames, so at 48kHz there are notches
+        // at odd multiples of 500Hz and peaks at multiples of 1kHz.
+        let flanged = |freq, feedback| {
+            SineWave::new(freq).flanger(0.0, Duration::from_secs_f64(47.0 / 48000.0), feedback, 0.5)
+        };
+
+        assert!(peak(flanged(500.0, 0.0)) < 0.01);
+        assert!(peak(flanged(1500.0, 0.0)) < 0.01);
+        assert!((peak(flanged(1000.0, 0.0)) - 1.0).abs() < 0.01);
+
+        // Feedback raises the peaks much more than the notches.
+        assert!(peak(flanged(500.0, 0.5)) < 0.2);
+        assert!(peak(flanged(1000.0, 0.5)) > 1.4);
+    }
+
+    #[test]
+    fn feedback_is_clamped() {
+        let flanger = SineWave::new(1000.0).flanger(0.5, Duration::from_millis(2), 10.0, 1.0);
+        assert_eq!(flanger.feedback(), MAX_FEEDBACK);
+        assert!(peak(flanger).is_finite());
+    }
+}
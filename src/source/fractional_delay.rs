@@ -0,0 +1,72 @@
+use crate::common::ChannelCount;
+use crate::{Float, Sample};
+
+/// Interleaved ring buffer per channel that can be read at fractional delays.
+///
+/// Shared by the effects built on a modulated delay, such as [`Vibrato`](super::Vibrato),
+/// [`Flanger`](super::Flanger) and [`Chorus`](super::Chorus). Samples are written one channel
+/// at a time in interleaved order and reads linearly interpolate between the two nearest
+/// frames, which avoids zipper noise while the delay moves.
+#[derive(Clone, Debug)]
+pub(crate) struct FractionalDelay {
+    buffer: Vec<Sample>,
+    channels: usize,
+    frames: usize,
+    // Frame that is currently being written.
+    write_frame: usize,
+    // Channel of the next sample within the current frame.
+    current_channel: usize,
+}
+
+impl FractionalDelay {
+    /// Creates a silent delay line that can be read up to `max_delay` frames back.
+    pub(crate) fn new(channels: ChannelCount, max_delay: Float) -> Self {
+        let channels = channels.get() as usize;
+        // One extra frame so the longest delay can still interpolate.
+        let frames = max_delay.max(0.0).ceil() as usize + 2;
+
+        Self {
+            buffer: vec![0.0; frames * channels],
+            channels,
+            frames,
+            write_frame: 0,
+            current_channel: 0,
+        }
+    }
+
+    /// Writes the sample of the current channel.
+    #[inline]
+    pub(crate) fn write(&mut self, sample: Sample) {
+        self.buffer[self.write_frame * self.channels + self.current_channel] = sample;
+    }
+
+    /// Reads the current channel `delay` frames behind the frame being written.
+    ///
+    /// Delays below one frame interpolate with the sample of the current frame, so write it
+    /// first.
+    #[inline]
+    pub(crate) fn read(&self, delay: Float) -> Sample {
+        let whole = delay.floor();
+        let frac = delay - whole;
+        let whole = whole as usize;
+
+        let newer = (self.write_frame + self.frames - whole) % self.frames;
+        let older = (newer + self.frames - 1) % self.frames;
+        let newer = self.buffer[newer * self.channels + self.current_channel];
+        let older = self.buffer[older * self.channels + self.current_channel];
+
+        newer + (older - newer) * frac
+    }
+
+    /// Moves on to the next channel. Returns `true` when this completed a frame.
+    #[inline]
+    pub(crate) fn advance(&mut self) -> bool {
+        self.current_channel += 1;
+        if self.current_channel < self.channels {
+            return false;
+        }
+        self.current_channel = 0;
+        self.write_frame = (self.write_frame + 1) % self.frames;
+        true
+    }
+}
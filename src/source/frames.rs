@@ -0,0 +1,98 @@
+use dasp_sample::Sample as _;
+
+use crate::{Sample, Source};
+
+/// Internal function that builds a `Frames` object.
+pub fn frames<I>(input: I) -> Frames<I>
+where
+    I: Source,
+{
+    Frames { input }
+}
+
+/// Iterator over the frames of a source.
+///
+/// Each item holds one sample for every channel, in channel order. The length of the frames
+/// follows the channel count of the source, so it may change at a span boundary.
+#[derive(Clone, Debug)]
+pub struct Frames<I> {
+    input: I,
+}
+
+impl<I> Frames<I> {
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for Frames<I>
+where
+    I: Source,
+{
+    type Item = Vec<Sample>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // Channel count can only change at a frame boundary, which is where we are now.
+        let channels = self.input.channels().get() as usize;
+
+        let first = self.input.next()?;
+        let mut frame = Vec::with_capacity(channels);
+        frame.push(first);
+        // Sources must return complete frames, pad just in case one does not.
+        frame.extend((1..channels).map(|_| self.input.next().unwrap_or(Sample::EQUILIBRIUM)));
+        Some(frame)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let channels = self.input.channels().get() as usize;
+        let (min, max) = self.input.size_hint();
+        (
+            min.div_ceil(channels),
+            max.map(|max| max.div_ceil(channels)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use crate::queue;
+    use crate::Source;
+
+    #[test]
+    fn stereo_frames() {
+        let source = SamplesBuffer::new(nz!(2), nz!(44100), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let frames: Vec<_> = source.into_frames().collect();
+        assert_eq!(frames.len(), 3);
+        assert!(frames.iter().all(|frame| frame.len() == 2));
+        assert_eq!(frames[1], vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn follows_channel_changes() {
+        let (tx, rx) = queue::queue(false);
+        tx.append(SamplesBuffer::new(nz!(1), nz!(44100), vec![1.0, 2.0]));
+        tx.append(SamplesBuffer::new(nz!(2), nz!(44100), vec![3.0, 4.0]));
+
+        let frames: Vec<_> = rx.into_frames().collect();
+        assert_eq!(frames, vec![vec![1.0], vec![2.0], vec![3.0, 4.0]]);
+    }
+}
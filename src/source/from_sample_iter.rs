@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use dasp_sample::FromSample;
+
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Builds a source from an iterator of samples of any type, such as `i16` or `f64`.
+///
+/// The samples are interleaved and converted to [`Sample`] while playing, integer samples are
+/// scaled so their full range maps to `-1.0..1.0`. This avoids collecting a stream into a
+/// buffer just to convert it. The resulting source can not seek.
+///
+/// # Example
+///
+/// ```
+/// use rodio::source::{from_sample_iter, Source};
+/// use std::num::NonZero;
+///
+/// let pcm: Vec<i16> = vec![0, i16::MAX, i16::MIN, 0];
+/// let source = from_sample_iter(pcm, NonZero::new(2).unwrap(), NonZero::new(44100).unwrap());
+/// assert_eq!(source.channels().get(), 2);
+/// assert_eq!(source.collect::<Vec<_>>()[2], -1.0);
+/// ```
+pub fn from_sample_iter<I>(
+    iterator: I,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+) -> FromSampleIter<I::IntoIter>
+where
+    I: IntoIterator,
+    Sample: FromSample<I::Item>,
+{
+    FromSampleIter {
+        iterator: iterator.into_iter(),
+        channels,
+        sample_rate,
+    }
+}
+
+/// A source that converts the samples of an iterator to [`Sample`].
+///
+/// See [`from_sample_iter`] for details.
+#[derive(Clone, Debug)]
+pub struct FromSampleIter<I> {
+    iterator: I,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+}
+
+impl<I> FromSampleIter<I> {
+    /// Returns a reference to the inner iterator.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.iterator
+    }
+
+    /// Returns a mutable reference to the inner iterator.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.iterator
+    }
+
+    /// Returns the inner iterator.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.iterator
+    }
+}
+
+impl<I> Iterator for FromSampleIter<I>
+where
+    I: Iterator,
+    Sample: FromSample<I::Item>,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        self.iterator.next().map(Sample::from_sample_)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for FromSampleIter<I>
+where
+    I: ExactSizeIterator,
+    Sample: FromSample<I::Item>,
+{
+}
+
+impl<I> Source for FromSampleIter<I>
+where
+    I: Iterator,
+    Sample: FromSample<I::Item>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::nz;
+
+    #[test]
+    fn normalizes_i16() {
+        let pcm: Vec<i16> = vec![0, 16384, -16384, i16::MIN, i16::MAX];
+        let source = from_sample_iter(pcm, nz!(1), nz!(8000));
+        assert_eq!(source.size_hint(), (5, Some(5)));
+
+        let samples: Vec<Sample> = source.collect();
+        assert_eq!(samples[..4], [0.0, 0.5, -0.5, -1.0]);
+        assert!((samples[4] - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn converts_f64() {
+        let source = from_sample_iter([0.25f64, -0.75], nz!(2), nz!(8000));
+        assert_eq!(source.channels(), nz!(2));
+        assert_eq!(source.collect::<Vec<_>>(), [0.25, -0.75]);
+    }
+}
@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::{duration_to_float, NANOS_PER_SEC};
+use crate::{Float, Sample, Source};
+
+/// Internal function that builds a `GainEnvelope` object.
+pub(crate) fn gain_envelope<I>(input: I, mut points: Vec<(Duration, Float)>) -> GainEnvelope<I>
+where
+    I: Source,
+{
+    points.sort_by_key(|(time, _)| *time);
+    let mut envelope = GainEnvelope {
+        input,
+        points,
+        segment: 0,
+        elapsed: Duration::ZERO,
+        gain: 1.0,
+        frame_channels: 0,
+        samples_in_frame: 0,
+    };
+    envelope.update_gain();
+    envelope
+}
+
+/// Filter that changes the gain of a source over time following a list of breakpoints.
+///
+/// The gain moves linearly from one breakpoint to the next, based on the play position of the
+/// source. Before the first breakpoint the gain is that of the first one, after the last
+/// breakpoint it stays at the last value. Without breakpoints the source is not changed. All
+/// channels of a frame get the same gain.
+#[derive(Clone, Debug)]
+pub struct GainEnvelope<I> {
+    input: I,
+    // Sorted by time.
+    points: Vec<(Duration, Float)>,
+    // Index of the last breakpoint at or before `elapsed`.
+    segment: usize,
+    elapsed: Duration,
+    gain: Float,
+    // Channels of the frame being played, and how many of its samples were played.
+    frame_channels: usize,
+    samples_in_frame: usize,
+}
+
+impl<I> GainEnvelope<I> {
+    /// Returns the gain applied to the current frame.
+    #[inline]
+    pub fn gain(&self) -> Float {
+        self.gain
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    fn update_gain(&mut self) {
+        let Some(&(first_time, first_gain)) = self.points.first() else {
+            return;
+        };
+        while self
+            .points
+            .get(self.segment + 1)
+            .is_some_and(|(time, _)| *time <= self.elapsed)
+        {
+            self.segment += 1;
+        }
+
+        let (start_time, start_gain) = self.points[self.segment];
+        self.gain = match self.points.get(self.segment + 1) {
+            _ if self.elapsed < first_time => first_gain,
+            Some(&(end_time, end_gain)) => {
+                let progress = duration_to_float(self.elapsed - start_time)
+                    / duration_to_float(end_time - start_time);
+                start_gain + (end_gain - start_gain) * progress
+            }
+            None => start_gain,
+        };
+    }
+}
+
+impl<I> Iterator for GainEnvelope<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.samples_in_frame == 0 {
+            // The channel count can only change at the start of a frame.
+            self.frame_channels = self.input.channels().get() as usize;
+        }
+
+        let sample = self.input.next()?;
+        let value = sample * self.gain;
+
+        self.samples_in_frame += 1;
+        if self.samples_in_frame == self.frame_channels {
+            self.samples_in_frame = 0;
+            let rate = self.input.sample_rate().get() as u64;
+            self.elapsed += Duration::from_nanos(NANOS_PER_SEC / rate);
+            self.update_gain();
+        }
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for GainEnvelope<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for GainEnvelope<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.elapsed = pos;
+        self.segment = 0;
+        self.samples_in_frame = 0;
+        self.update_gain();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn interpolates_between_points() {
+        let source = SamplesBuffer::new(nz!(2), nz!(10), vec![1.0; 30]);
+        let points = vec![(Duration::ZERO, 1.0), (Duration::from_secs(1), 0.0)];
+        let played: Vec<Sample> = source.automate_gain(points).collect();
+
+        // Both channels of a frame get the same gain.
+        assert_eq!(played[0], 1.0);
+        assert_eq!(played[10], 0.5);
+        assert_eq!(played[11], 0.5);
+        // The last value is held.
+        assert_eq!(played[20..], [0.0; 10]);
+    }
+
+    #[test]
+    fn holds_first_value_and_seeks() {
+        let source = SamplesBuffer::new(nz!(1), nz!(10), vec![1.0; 20]);
+        let points = vec![
+            (Duration::from_secs(1), 0.0),
+            (Duration::from_millis(500), 0.5),
+        ];
+        let mut envelope = source.automate_gain(points);
+        assert_eq!(envelope.next(), Some(0.5));
+
+        envelope.try_seek(Duration::from_millis(800)).unwrap();
+        assert!((envelope.gain() - 0.2).abs() < 1e-6);
+        envelope.try_seek(Duration::from_millis(200)).unwrap();
+        assert_eq!(envelope.gain(), 0.5);
+    }
+}
@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Float, Source};
+
+/// Internal function that builds a `HardLimit` object.
+pub(crate) fn hard_limit<I>(input: I, ceiling: Float) -> HardLimit<I>
+where
+    I: Source,
+{
+    assert_valid_ceiling(ceiling);
+    HardLimit { input, ceiling }
+}
+
+fn assert_valid_ceiling(ceiling: Float) {
+    assert!(ceiling >= 0.0, "ceiling must not be negative or NaN");
+}
+
+/// Filter that clamps every sample to `±ceiling`.
+///
+/// Meant as a last safety net in front of the speakers, not to shape the sound: samples within
+/// the ceiling pass unchanged and louder ones are cut off, which distorts. Use [`Source::limit`]
+/// to reduce peaks transparently.
+#[derive(Clone, Debug)]
+pub struct HardLimit<I> {
+    input: I,
+    ceiling: Float,
+}
+
+impl<I> HardLimit<I> {
+    /// Returns the highest absolute value a sample can have.
+    #[inline]
+    pub fn ceiling(&self) -> Float {
+        self.ceiling
+    }
+
+    /// Modifies the highest absolute value a sample can have.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ceiling` is negative or NaN.
+    #[inline]
+    pub fn set_ceiling(&mut self, ceiling: Float) {
+        assert_valid_ceiling(ceiling);
+        self.ceiling = ceiling;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for HardLimit<I>
+where
+    I: Source,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.input
+            .next()
+            .map(|value| value.clamp(-self.ceiling, self.ceiling))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for HardLimit<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for HardLimit<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn clamps_to_ceiling() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5, 1.5, -2.0, -0.25, 1.0]);
+        let limited: Vec<_> = source.clone().hard_limit(1.0).collect();
+        assert_eq!(limited, [0.5, 1.0, -1.0, -0.25, 1.0]);
+
+        let limited: Vec<_> = source.hard_limit(0.5).collect();
+        assert_eq!(limited, [0.5, 0.5, -0.5, -0.25, 0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ceiling must not be negative")]
+    fn rejects_negative_ceiling() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5]);
+        let _ = source.hard_limit(-1.0);
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds an `Inspect` object.
+pub(crate) fn inspect<I, F>(input: I, inspect: F) -> Inspect<I, F>
+where
+    I: Source,
+    F: FnMut(&Sample),
+{
+    Inspect { input, inspect }
+}
+
+/// Source that calls a function on every sample passing through it.
+///
+/// The samples and all metadata of the inner source are passed on unchanged.
+#[derive(Clone, Debug)]
+pub struct Inspect<I, F> {
+    input: I,
+    inspect: F,
+}
+
+impl<I, F> Inspect<I, F> {
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I, F> Iterator for Inspect<I, F>
+where
+    I: Source,
+    F: FnMut(&Sample),
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+        (self.inspect)(&sample);
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I, F> ExactSizeIterator for Inspect<I, F>
+where
+    I: Source + ExactSizeIterator,
+    F: FnMut(&Sample),
+{
+}
+
+impl<I, F> Source for Inspect<I, F>
+where
+    I: Source,
+    F: FnMut(&Sample),
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn observes_every_sample() {
+        let mut seen = 0;
+        let mut peak: Sample = 0.0;
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.1, -0.5, 0.3, 0.2])
+            .inspect_samples(|sample| {
+                seen += 1;
+                peak = peak.max(sample.abs());
+            });
+        assert_eq!(source.channels(), nz!(2));
+        assert_eq!(source.collect::<Vec<_>>(), [0.1, -0.5, 0.3, 0.2]);
+        assert_eq!(seen, 4);
+        assert_eq!(peak, 0.5);
+    }
+}
@@ -0,0 +1,278 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds an `Instrumented` object.
+pub(crate) fn instrument<I>(input: I, name: &str) -> Instrumented<I>
+where
+    I: Source,
+{
+    Instrumented {
+        input,
+        stats: InstrumentStats {
+            timings: Arc::new(Timings {
+                name: name.into(),
+                calls: AtomicU64::new(0),
+                total_nanos: AtomicU64::new(0),
+                min_nanos: AtomicU64::new(u64::MAX),
+                max_nanos: AtomicU64::new(0),
+                slow_calls: AtomicU64::new(0),
+            }),
+        },
+    }
+}
+
+/// Source that measures how long the source it wraps takes to produce each sample.
+///
+/// Wrap different parts of a chain to find the adapter that is too slow to play in real time.
+/// The measurements are collected in an [`InstrumentStats`] which can be read from another
+/// thread while playing. With the `tracing` feature a summary is logged once the source and
+/// all handles are dropped.
+///
+/// Only debug builds measure anything. In release builds this passes the samples through
+/// without timing them and the statistics stay empty.
+#[derive(Debug)]
+pub struct Instrumented<I> {
+    input: I,
+    stats: InstrumentStats,
+}
+
+impl<I> Instrumented<I> {
+    /// Returns a handle to the measurements of this source.
+    #[inline]
+    pub fn stats(&self) -> InstrumentStats {
+        self.stats.clone()
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for Instrumented<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if !cfg!(debug_assertions) {
+            return self.input.next();
+        }
+
+        let start = Instant::now();
+        let sample = self.input.next();
+        let elapsed = start.elapsed();
+
+        // The time one sample lasts when played.
+        let samples_per_second =
+            self.input.sample_rate().get() as u64 * self.input.channels().get() as u64;
+        let budget = Duration::from_secs(1) / samples_per_second as u32;
+        self.stats.timings.record(elapsed, elapsed > budget);
+
+        sample
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Instrumented<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Instrumented<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+/// Handle to the measurements of an [`Instrumented`] source.
+///
+/// Every call to `next` on the source is timed. Calls that take longer than the sample they
+/// return lasts when played are counted as slow. Decoders are often slow once per packet and
+/// fast for the rest, so compare the [`average`](InstrumentStats::average) with the duration of
+/// a sample before blaming them. Can be cloned and sent to other threads.
+#[derive(Clone, Debug)]
+pub struct InstrumentStats {
+    timings: Arc<Timings>,
+}
+
+impl InstrumentStats {
+    /// Returns the name the source was instrumented with.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.timings.name
+    }
+
+    /// Returns the number of samples that were timed.
+    #[inline]
+    pub fn calls(&self) -> u64 {
+        self.timings.calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of samples that took longer to produce than to play.
+    #[inline]
+    pub fn slow_calls(&self) -> u64 {
+        self.timings.slow_calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the shortest time a sample took to produce, `None` if none were timed.
+    pub fn min(&self) -> Option<Duration> {
+        let nanos = self.timings.min_nanos.load(Ordering::Relaxed);
+        (nanos != u64::MAX).then(|| Duration::from_nanos(nanos))
+    }
+
+    /// Returns the longest time a sample took to produce, `None` if none were timed.
+    pub fn max(&self) -> Option<Duration> {
+        (self.calls() > 0)
+            .then(|| Duration::from_nanos(self.timings.max_nanos.load(Ordering::Relaxed)))
+    }
+
+    /// Returns the average time a sample took to produce, `None` if none were timed.
+    pub fn average(&self) -> Option<Duration> {
+        let calls = self.calls();
+        (calls > 0)
+            .then(|| Duration::from_nanos(self.timings.total_nanos.load(Ordering::Relaxed) / calls))
+    }
+
+    /// Forgets all measurements so far.
+    pub fn reset(&self) {
+        let timings = &self.timings;
+        timings.calls.store(0, Ordering::Relaxed);
+        timings.total_nanos.store(0, Ordering::Relaxed);
+        timings.min_nanos.store(u64::MAX, Ordering::Relaxed);
+        timings.max_nanos.store(0, Ordering::Relaxed);
+        timings.slow_calls.store(0, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug)]
+struct Timings {
+    name: Box<str>,
+    calls: AtomicU64,
+    total_nanos: AtomicU64,
+    min_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+    slow_calls: AtomicU64,
+}
+
+impl Timings {
+    #[inline]
+    fn record(&self, elapsed: Duration, slow: bool) {
+        let nanos = elapsed.as_nanos() as u64;
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.min_nanos.fetch_min(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        if slow {
+            self.slow_calls.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Timings {
+    fn drop(&mut self) {
+        let calls = *self.calls.get_mut();
+        if calls == 0 {
+            return;
+        }
+        tracing::debug!(
+            "{}: {} samples, {} slow, min {:?}, max {:?}, average {:?}",
+            self.name,
+            calls,
+            self.slow_calls.get_mut(),
+            Duration::from_nanos(*self.min_nanos.get_mut()),
+            Duration::from_nanos(*self.max_nanos.get_mut()),
+            Duration::from_nanos(*self.total_nanos.get_mut() / calls),
+        );
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn counts_timed_samples() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.5; 10]).instrument("buffer");
+        let stats = source.stats();
+        assert_eq!(stats.name(), "buffer");
+        assert_eq!(stats.average(), None);
+
+        assert_eq!(source.count(), 10);
+        // The final `None` is timed too.
+        assert_eq!(stats.calls(), 11);
+        assert!(stats.min() <= stats.average());
+        assert!(stats.average() <= stats.max());
+
+        stats.reset();
+        assert_eq!(stats.calls(), 0);
+        assert_eq!(stats.min(), None);
+    }
+
+    #[test]
+    fn counts_slow_samples() {
+        // A sample at 48kHz lasts about 21µs, much shorter than the sleep.
+        let source = SamplesBuffer::new(nz!(1), nz!(48000), vec![0.0; 3])
+            .periodic_access(Duration::ZERO, |_| {
+                std::thread::sleep(Duration::from_millis(1))
+            })
+            .instrument("slow");
+        let stats = source.stats();
+        source.for_each(drop);
+        assert!(stats.slow_calls() >= 3);
+        assert!(stats.max().unwrap() >= Duration::from_millis(1));
+    }
+}
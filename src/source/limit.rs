@@ -593,6 +593,11 @@ where
         self.inner.as_ref().unwrap().total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner.as_ref().unwrap().is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, position: Duration) -> Result<(), SeekError> {
         self.inner.as_mut().unwrap().try_seek(position)?;
@@ -1127,6 +1132,12 @@ where
         self.inner().total_duration()
     }
 
+    /// Returns whether the inner source can seek.
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner().is_seekable()
+    }
+
     /// Attempts to seek to the specified position.
     ///
     /// Resets limiter state to prevent artifacts after seeking:
@@ -13,6 +13,28 @@ pub fn linear_gain_ramp<I>(
     end_gain: Float,
     clamp_end: bool,
 ) -> LinearGainRamp<I>
+where
+    I: Source,
+{
+    gain_ramp_with_curve(
+        input,
+        duration,
+        start_gain,
+        end_gain,
+        clamp_end,
+        FadeCurve::Linear,
+    )
+}
+
+/// Internal function that builds a `LinearRamp` object that follows `curve`.
+pub(crate) fn gain_ramp_with_curve<I>(
+    input: I,
+    duration: Duration,
+    start_gain: Float,
+    end_gain: Float,
+    clamp_end: bool,
+    curve: FadeCurve,
+) -> LinearGainRamp<I>
 where
     I: Source,
 {
@@ -28,12 +50,60 @@ where
         start_gain,
         end_gain,
         clamp_end,
+        curve,
         sample_idx: 0,
         span: SpanTracker::new(sample_rate, channels),
     }
 }
 
-/// Filter that adds a linear gain ramp to the source over a given time range.
+/// Shape of a gain ramp such as a fade.
+///
+/// Every curve starts at the start gain and ends exactly at the end gain, they only differ in
+/// how the gain changes in between. Ramps that lower the gain follow the curve backwards in
+/// time, so a fade out sounds like a fade in played in reverse.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FadeCurve {
+    /// The gain changes at a constant rate. Loudness seems to change quickly at the quiet
+    /// end of the ramp and barely at the loud end.
+    Linear,
+    /// The gain follows `(e^(steepness * t) - 1) / (e^steepness - 1)`, which changes slowly
+    /// while quiet and quickly while loud. This sounds more even than a linear ramp. Larger
+    /// steepness values make the curve more pronounced, around `5.0` is a good start.
+    Exponential {
+        /// How strongly the curve bends, `0.0` is linear.
+        steepness: Float,
+    },
+    /// The gain changes slowly at both ends and quickly in the middle (smoothstep).
+    SCurve,
+}
+
+impl FadeCurve {
+    /// Maps the progress through a rising ramp, between `0.0` and `1.0`, to the proportion of
+    /// the gain change that has been applied.
+    pub fn apply(&self, progress: Float) -> Float {
+        match *self {
+            FadeCurve::Linear => progress,
+            FadeCurve::Exponential { steepness } if steepness.abs() < 1e-6 => progress,
+            FadeCurve::Exponential { steepness } => {
+                (steepness * progress).exp_m1() / steepness.exp_m1()
+            }
+            FadeCurve::SCurve => progress * progress * (3.0 - 2.0 * progress),
+        }
+    }
+
+    #[inline]
+    fn shape(&self, progress: Float, rising: bool) -> Float {
+        match self {
+            FadeCurve::Linear => progress,
+            _ if rising => self.apply(progress),
+            _ => 1.0 - self.apply(1.0 - progress),
+        }
+    }
+}
+
+/// Filter that adds a gain ramp to the source over a given time range.
+///
+/// The ramp is linear unless it was created with another [`FadeCurve`].
 #[derive(Clone, Debug)]
 pub struct LinearGainRamp<I> {
     input: I,
@@ -42,6 +112,7 @@ pub struct LinearGainRamp<I> {
     start_gain: Float,
     end_gain: Float,
     clamp_end: bool,
+    curve: FadeCurve,
     sample_idx: u64,
     span: SpanTracker,
 }
@@ -95,6 +166,7 @@ where
         } else {
             self.sample_idx = self.sample_idx.wrapping_add(1);
             let p = duration_to_float(self.elapsed) / duration_to_float(self.total);
+            let p = self.curve.shape(p, self.end_gain >= self.start_gain);
             self.start_gain * (1.0 - p) + self.end_gain * p
         };
 
@@ -143,6 +215,11 @@ where
         self.input.total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.elapsed = pos;
@@ -248,4 +325,37 @@ mod tests {
             panic!("try_seek() failed!");
         }
     }
+
+    /// Gains of a ramp from `start` to `end` over 4 seconds at 25%, 50% and 75%.
+    fn gains_at_quarters(start: Float, end: Float, curve: FadeCurve) -> Vec<Sample> {
+        let ramp = gain_ramp_with_curve(
+            const_source(4, 1.0),
+            Duration::from_secs(4),
+            start,
+            end,
+            true,
+            curve,
+        );
+        ramp.skip(1).collect()
+    }
+
+    #[test]
+    fn test_fade_curves() {
+        let exponential = FadeCurve::Exponential { steepness: 5.0 };
+        let cases = [
+            (FadeCurve::Linear, [0.25, 0.5, 0.75]),
+            (exponential, [0.016894, 0.075858, 0.281665]),
+            (FadeCurve::SCurve, [0.15625, 0.5, 0.84375]),
+        ];
+
+        for (curve, expected) in cases {
+            let rising = gains_at_quarters(0.0, 1.0, curve);
+            let falling = gains_at_quarters(1.0, 0.0, curve);
+            for i in 0..3 {
+                assert_abs_diff_eq!(rising[i], expected[i], epsilon = 1e-5);
+                // Falling ramps are the rising ramp played backwards.
+                assert_abs_diff_eq!(falling[i], expected[2 - i], epsilon = 1e-5);
+            }
+        }
+    }
 }
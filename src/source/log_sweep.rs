@@ -0,0 +1,207 @@
+//! Logarithmic sine sweep source.
+
+use std::f64::consts::PI;
+use std::time::Duration;
+
+use crate::{
+    common::{ChannelCount, SampleRate},
+    math::nz,
+    source::SeekError,
+    Float, Sample, Source,
+};
+
+/// Longest fade in and out, a sweep shorter than ten times this fades for a tenth of its length.
+const MAX_FADE: Duration = Duration::from_millis(10);
+
+/// Generates a sine wave with a frequency that rises or falls exponentially over time.
+///
+/// This is the exponential sine sweep commonly used to measure frequency responses: every
+/// octave lasts equally long, so low and high frequencies get the same amount of energy per
+/// octave. The sweep starts at phase zero and fades in and out over a few milliseconds to avoid
+/// clicks. Once `end_frequency` is reached the source is exhausted.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use rodio::source::LogSweep;
+///
+/// let sweep = LogSweep::new(rodio::nz!(48000), 20.0, 20_000.0, Duration::from_secs(5));
+/// assert_eq!(sweep.frequency(), 20.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogSweep {
+    sample_rate: SampleRate,
+    start_frequency: f64,
+    // Natural log of the ratio between the end and start frequency.
+    log_ratio: f64,
+    total_samples: u64,
+    fade_samples: u64,
+    elapsed_samples: u64,
+}
+
+impl LogSweep {
+    /// Builds a sweep from `start_frequency` to `end_frequency` in Hz lasting `duration`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either frequency is not positive.
+    pub fn new(
+        sample_rate: SampleRate,
+        start_frequency: Float,
+        end_frequency: Float,
+        duration: Duration,
+    ) -> Self {
+        assert!(
+            start_frequency > 0.0 && end_frequency > 0.0,
+            "sweep frequencies must be positive"
+        );
+        let total_samples = (duration.as_secs_f64() * sample_rate.get() as f64) as u64;
+        let max_fade = (MAX_FADE.as_secs_f64() * sample_rate.get() as f64) as u64;
+        Self {
+            sample_rate,
+            start_frequency: start_frequency as f64,
+            log_ratio: (end_frequency as f64 / start_frequency as f64).ln(),
+            total_samples,
+            fade_samples: max_fade.min(total_samples / 10),
+            elapsed_samples: 0,
+        }
+    }
+
+    /// Returns the instantaneous frequency in Hz of the next sample.
+    pub fn frequency(&self) -> Float {
+        let progress = self.progress(self.elapsed_samples);
+        (self.start_frequency * (self.log_ratio * progress).exp()) as Float
+    }
+
+    /// How far into the sweep sample `i` is, from 0 to 1.
+    fn progress(&self, i: u64) -> f64 {
+        if self.total_samples == 0 {
+            return 0.0;
+        }
+        i as f64 / self.total_samples as f64
+    }
+
+    /// The phase of sample `i`, the integral of the instantaneous frequency.
+    fn phase(&self, i: u64) -> f64 {
+        let t = i as f64 / self.sample_rate.get() as f64;
+        if self.log_ratio.abs() < f64::EPSILON {
+            return 2.0 * PI * self.start_frequency * t;
+        }
+        let duration = self.total_samples as f64 / self.sample_rate.get() as f64;
+        2.0 * PI * self.start_frequency * duration / self.log_ratio
+            * ((self.log_ratio * self.progress(i)).exp() - 1.0)
+    }
+
+    /// Raised cosine fade at both ends of the sweep.
+    fn gain(&self, i: u64) -> f64 {
+        let from_edge = i.min(self.total_samples - 1 - i);
+        if from_edge >= self.fade_samples {
+            return 1.0;
+        }
+        0.5 - 0.5 * (PI * from_edge as f64 / self.fade_samples as f64).cos()
+    }
+}
+
+impl Iterator for LogSweep {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.elapsed_samples;
+        if i >= self.total_samples {
+            return None;
+        }
+
+        self.elapsed_samples += 1;
+        Some((self.phase(i).sin() * self.gain(i)) as Sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.total_samples - self.elapsed_samples) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for LogSweep {}
+
+impl Source for LogSweep {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> ChannelCount {
+        nz!(1)
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        let secs = self.total_samples as f64 / self.sample_rate.get() as f64;
+        Some(Duration::from_secs_f64(secs))
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        let target = (pos.as_secs_f64() * self.sample_rate.get() as f64) as u64;
+        self.elapsed_samples = target.min(self.total_samples);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Positions of the rising zero crossings, interpolated between samples.
+    fn rising_crossings(samples: &[Sample]) -> Vec<f64> {
+        samples
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0] < 0.0 && pair[1] > 0.0)
+            .map(|(i, pair)| i as f64 + (pair[0] / (pair[0] - pair[1])) as f64)
+            .collect()
+    }
+
+    #[test]
+    fn sweeps_from_start_to_end_frequency() {
+        let mut sweep = LogSweep::new(nz!(48_000), 100.0, 1000.0, Duration::from_secs(1));
+        assert_eq!(sweep.frequency(), 100.0);
+        let samples: Vec<Sample> = sweep.clone().collect();
+        assert_eq!(samples.len(), 48_000);
+        assert_eq!(samples[0], 0.0);
+
+        // The frequency of a period measured from the zero crossings matches the frequency
+        // halfway through it.
+        let crossings = rising_crossings(&samples);
+        for period in [&crossings[..2], &crossings[crossings.len() - 2..]] {
+            let measured = 48_000.0 / (period[1] - period[0]);
+            let middle = (period[0] + period[1]) / 2.0 / 48_000.0;
+            sweep.try_seek(Duration::from_secs_f64(middle)).unwrap();
+            let expected = sweep.frequency() as f64;
+            assert!(
+                (measured - expected).abs() < expected * 0.01,
+                "measured {measured} Hz, expected {expected} Hz"
+            );
+        }
+
+        sweep.try_seek(Duration::from_secs(1)).unwrap();
+        assert!((sweep.frequency() - 1000.0).abs() < 0.01);
+        assert_eq!(sweep.next(), None);
+    }
+
+    #[test]
+    fn fades_in_and_out() {
+        let samples: Vec<Sample> =
+            LogSweep::new(nz!(48_000), 1000.0, 2000.0, Duration::from_secs(1)).collect();
+        let peak = |range: &[Sample]| range.iter().fold(0.0, |max: Sample, s| max.max(s.abs()));
+        assert!(peak(&samples[..48]) < 0.05);
+        assert!(peak(&samples[samples.len() - 48..]) < 0.05);
+        assert!(peak(&samples[24_000..24_100]) > 0.99);
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds a `MapSamples` object.
+pub(crate) fn map_samples<I, F>(input: I, map: F) -> MapSamples<I, F>
+where
+    I: Source,
+    F: FnMut(Sample) -> Sample,
+{
+    MapSamples { input, map }
+}
+
+/// Source that replaces every sample with the result of a function.
+///
+/// All metadata of the inner source is passed on unchanged.
+#[derive(Clone, Debug)]
+pub struct MapSamples<I, F> {
+    input: I,
+    map: F,
+}
+
+impl<I, F> MapSamples<I, F> {
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I, F> Iterator for MapSamples<I, F>
+where
+    I: Source,
+    F: FnMut(Sample) -> Sample,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        self.input.next().map(&mut self.map)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I, F> ExactSizeIterator for MapSamples<I, F>
+where
+    I: Source + ExactSizeIterator,
+    F: FnMut(Sample) -> Sample,
+{
+}
+
+impl<I, F> Source for MapSamples<I, F>
+where
+    I: Source,
+    F: FnMut(Sample) -> Sample,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn maps_every_sample() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.125, -0.25, 0.5, 0.0])
+            .map_samples(|sample| sample * 2.0);
+        assert_eq!(source.channels(), nz!(2));
+        assert_eq!(source.sample_rate(), nz!(1000));
+        assert_eq!(source.collect::<Vec<_>>(), [0.25, -0.5, 1.0, 0.0]);
+    }
+}
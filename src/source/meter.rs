@@ -0,0 +1,249 @@
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, Float, SampleRate};
+use crate::math::duration_to_coefficient;
+use crate::{Sample, Source};
+
+/// Internal function that builds a `Meter` object and its `MeterHandle`.
+pub(crate) fn meter<I>(input: I) -> (Meter<I>, MeterHandle)
+where
+    I: Source,
+{
+    let channels = input.channels().get() as usize;
+    let handle = MeterHandle {
+        shared: Arc::new(Shared {
+            peaks: (0..channels).map(|_| AtomicU32::new(0)).collect(),
+            hold_nanos: AtomicU64::new(0),
+        }),
+    };
+    let source = Meter {
+        input,
+        shared: handle.shared.clone(),
+        levels: vec![0.0; channels],
+        channel: 0,
+        frame_channels: channels,
+        hold_nanos: 0,
+        decay: 0.0,
+    };
+    (source, handle)
+}
+
+#[derive(Debug)]
+struct Shared {
+    // Absolute peak per channel as `f32` bits. Those compare like the values for positive
+    // numbers, so `fetch_max` on the bits keeps the highest peak.
+    peaks: Box<[AtomicU32]>,
+    // Decay time of the peak hold in nanoseconds, zero if it is off.
+    hold_nanos: AtomicU64,
+}
+
+/// Source that measures the peak level of every channel while passing the samples on
+/// unchanged.
+///
+/// The levels are read through the [`MeterHandle`] returned by [`Source::meter`]. Only the
+/// channels the source had when the meter was created are measured.
+#[derive(Clone, Debug)]
+pub struct Meter<I> {
+    input: I,
+    shared: Arc<Shared>,
+    // Held level per channel, only used with peak hold.
+    levels: Vec<Float>,
+    channel: usize,
+    frame_channels: usize,
+    hold_nanos: u64,
+    // Factor the held levels are multiplied with every frame.
+    decay: Float,
+}
+
+impl<I> Meter<I> {
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for Meter<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    #[allow(clippy::unnecessary_cast)]
+    fn next(&mut self) -> Option<Sample> {
+        if self.channel == 0 {
+            // The channel count can only change at the start of a frame.
+            self.frame_channels = self.input.channels().get() as usize;
+            let hold_nanos = self.shared.hold_nanos.load(Ordering::Relaxed);
+            if hold_nanos != self.hold_nanos {
+                self.hold_nanos = hold_nanos;
+                self.decay = duration_to_coefficient(
+                    Duration::from_nanos(hold_nanos),
+                    self.input.sample_rate(),
+                );
+            }
+        }
+
+        let sample = self.input.next()?;
+        let level = sample.abs();
+        if let (Some(peak), false) = (self.shared.peaks.get(self.channel), level.is_nan()) {
+            if self.hold_nanos == 0 {
+                peak.fetch_max((level as f32).to_bits(), Ordering::Relaxed);
+            } else {
+                let held = &mut self.levels[self.channel];
+                *held = level.max(*held * self.decay);
+                peak.store((*held as f32).to_bits(), Ordering::Relaxed);
+            }
+        }
+
+        self.channel += 1;
+        if self.channel >= self.frame_channels {
+            self.channel = 0;
+        }
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Meter<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Meter<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        // Seeking starts at the first channel of a frame.
+        self.channel = 0;
+        Ok(())
+    }
+}
+
+/// Reads the levels measured by a [`Meter`].
+///
+/// By default every read returns the highest level since the previous read, which suits a
+/// meter that is redrawn at a fixed rate. With [`peak_hold`](MeterHandle::peak_hold) the
+/// levels jump up with the signal and fall back slowly like on a hardware meter. Can be cloned
+/// and sent to other threads, all clones share the levels.
+#[derive(Clone, Debug)]
+pub struct MeterHandle {
+    shared: Arc<Shared>,
+}
+
+impl MeterHandle {
+    /// Returns the peak level of every channel, `1.0` being full scale.
+    ///
+    /// Without peak hold this is the highest absolute sample value since the last call, and
+    /// the levels are reset.
+    pub fn channel_peaks(&self) -> Vec<Float> {
+        let hold = self.shared.hold_nanos.load(Ordering::Relaxed) != 0;
+        self.shared
+            .peaks
+            .iter()
+            .map(|peak| {
+                let bits = if hold {
+                    peak.load(Ordering::Relaxed)
+                } else {
+                    peak.swap(0, Ordering::Relaxed)
+                };
+                f32::from_bits(bits) as Float
+            })
+            .collect()
+    }
+
+    /// Holds the peak levels and lets them fall back over time.
+    ///
+    /// A level follows a louder signal right away. Once the signal gets quieter the level
+    /// decays exponentially and drops to about a third every `decay`. Reading the levels does
+    /// not reset them in this mode. Pass [`Duration::ZERO`] to go back to reporting the peak
+    /// since the last read.
+    pub fn peak_hold(&self, decay: Duration) {
+        let nanos = u64::try_from(decay.as_nanos()).unwrap_or(u64::MAX);
+        self.shared.hold_nanos.store(nanos, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn peaks_since_last_read() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.5, -0.25, -0.75, 0.1]);
+        let (mut source, meter) = source.meter();
+        source.next();
+        source.next();
+        assert_eq!(meter.channel_peaks(), [0.5, 0.25]);
+
+        source.next();
+        assert_eq!(meter.channel_peaks(), [0.75, 0.0]);
+    }
+
+    #[test]
+    fn hold_decays_after_transient() {
+        let mut samples = vec![0.0; 40];
+        samples[0] = 1.0;
+        let (mut source, meter) = SamplesBuffer::new(nz!(1), nz!(1000), samples).meter();
+        meter.peak_hold(Duration::from_millis(10));
+
+        source.next();
+        assert_eq!(meter.channel_peaks(), [1.0]);
+        // Reading does not reset a held peak.
+        assert_eq!(meter.channel_peaks(), [1.0]);
+
+        source.by_ref().take(10).for_each(drop);
+        let level = meter.channel_peaks()[0];
+        assert!((level - (-1.0 as Float).exp()).abs() < 1e-3, "{level}");
+
+        source.by_ref().take(20).for_each(drop);
+        assert!(meter.channel_peaks()[0] < 0.1);
+    }
+}
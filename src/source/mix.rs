@@ -4,7 +4,7 @@ use std::time::Duration;
 use crate::common::{ChannelCount, SampleRate};
 use crate::source::uniform::UniformSourceIterator;
 use crate::source::SeekError;
-use crate::Source;
+use crate::{Sample, Source};
 
 /// Internal function that builds a `Mix` object.
 pub fn mix<I1, I2>(input1: I1, input2: I2) -> Mix<I1, I2>
@@ -18,6 +18,7 @@ where
     Mix {
         input1: UniformSourceIterator::new(input1, channels, rate),
         input2: UniformSourceIterator::new(input2, channels, rate),
+        buffer: Vec::new(),
     }
 }
 
@@ -30,6 +31,8 @@ where
 {
     input1: UniformSourceIterator<I1>,
     input2: UniformSourceIterator<I2>,
+    // Holds the samples of `input2` in `fill_buffer`.
+    buffer: Vec<Sample>,
 }
 
 impl<I1, I2> Iterator for Mix<I1, I2>
@@ -111,6 +114,31 @@ where
         }
     }
 
+    #[inline]
+    fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+        if self.buffer.len() < out.len() {
+            self.buffer.resize(out.len(), 0.0);
+        }
+        let other = &mut self.buffer[..out.len()];
+
+        let written1 = self.input1.fill_buffer(out);
+        let written2 = self.input2.fill_buffer(other);
+        for (sample, other) in out.iter_mut().zip(&*other).take(written1.min(written2)) {
+            *sample += other;
+        }
+        // Past the end of the first input only the second one plays.
+        if written2 > written1 {
+            out[written1..written2].copy_from_slice(&other[written1..written2]);
+        }
+        written1.max(written2)
+    }
+
+    /// Seeking is not implemented for a mix, even if both sources can seek.
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        false
+    }
+
     /// Will only attempt a seek if both underlying sources support seek.
     #[inline]
     fn try_seek(&mut self, _: Duration) -> Result<(), SeekError> {
@@ -119,3 +147,23 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use crate::Source;
+
+    #[test]
+    fn fill_buffer_matches_next() {
+        let long = || SamplesBuffer::new(nz!(1), nz!(48000), vec![1.0, 2.0, 3.0, 4.0]);
+        let short = || SamplesBuffer::new(nz!(1), nz!(48000), vec![10.0, 20.0]);
+
+        for (a, b) in [(long(), short()), (short(), long())] {
+            let expected: Vec<_> = a.clone().mix(b.clone()).collect();
+            let mut out = [0.0; 6];
+            assert_eq!(a.mix(b).fill_buffer(&mut out), 4);
+            assert_eq!(out[..4], expected);
+        }
+    }
+}
@@ -13,77 +13,148 @@ use dasp_sample::FromSample;
 
 pub use self::agc::{AutomaticGainControl, AutomaticGainControlSettings};
 pub use self::amplify::Amplify;
+pub use self::amplify_checked::{AmplifyChecked, ClipFlag};
+pub use self::bit_crush::BitCrush;
 pub use self::blt::BltFilter;
 pub use self::buffered::Buffered;
 pub use self::channel_volume::ChannelVolume;
+pub use self::channel_volumes::{ChannelVolumes, ChannelVolumesHandle};
 pub use self::chirp::{chirp, Chirp};
+pub use self::chorus::Chorus;
+pub use self::clip::Clip;
+pub use self::convert_sample_type::ConvertSampleType;
 pub use self::crossfade::Crossfade;
-pub use self::delay::Delay;
+pub use self::declick::{DeclickOnSeek, DEFAULT_DECLICK_RAMP};
+pub use self::delay::{Delay, DelayFrames};
 pub use self::distortion::Distortion;
 pub use self::done::Done;
+pub use self::duck::{DuckKey, Ducked, Ducker};
 pub use self::empty::Empty;
 pub use self::empty_callback::EmptyCallback;
+pub use self::equalizer::{EqBand, Equalizer, EqualizerHandle};
 pub use self::fadein::FadeIn;
 pub use self::fadeout::FadeOut;
+pub use self::flanger::Flanger;
+pub use self::frames::Frames;
 pub use self::from_factory::{from_factory, FromFactoryIter};
 pub use self::from_iter::{from_iter, FromIter};
+pub use self::from_sample_iter::{from_sample_iter, FromSampleIter};
+pub use self::gain_envelope::GainEnvelope;
+pub use self::hard_limit::HardLimit;
+pub use self::inspect::Inspect;
+pub use self::instrument::{InstrumentStats, Instrumented};
 pub use self::limit::{Limit, LimitSettings};
-pub use self::linear_ramp::LinearGainRamp;
+pub use self::linear_ramp::{FadeCurve, LinearGainRamp};
+pub use self::log_sweep::LogSweep;
+pub use self::map_samples::MapSamples;
+pub use self::meter::{Meter, MeterHandle};
 pub use self::mix::Mix;
+pub use self::noise_gate::NoiseGate;
+pub use self::normalize::{Normalize, NormalizeError};
 pub use self::pausable::Pausable;
+pub use self::peekable::PeekableSource;
 pub use self::periodic::PeriodicAccess;
+pub use self::pitch_shift::PitchShift;
 pub use self::position::TrackPosition;
-pub use self::repeat::Repeat;
+pub use self::prefetch::Prefetch;
+pub use self::repeat::{Repeat, RepeatFrom, RepeatProgress};
+pub use self::reverb::Reverb;
+pub use self::ring_mod::RingMod;
 pub use self::sawtooth::SawtoothWave;
 pub use self::signal_generator::{Function, GeneratorFunction, SignalGenerator};
 pub use self::sine::SineWave;
 pub use self::skip::SkipDuration;
 pub use self::skippable::Skippable;
+pub use self::soft_clip::SoftClip;
+pub use self::span_chunked::SpanChunked;
 pub use self::spatial::Spatial;
 pub use self::speed::Speed;
 pub use self::square::SquareWave;
+pub use self::stereo_width::StereoWidth;
 pub use self::stoppable::Stoppable;
 pub use self::take::TakeDuration;
+pub use self::take_samples::TakeSamples;
+pub use self::tee::Tee;
 pub use self::triangle::TriangleWave;
+pub use self::trim_silence::TrimSilence;
 pub use self::uniform::UniformSourceIterator;
+pub use self::upmix_surround::UpmixSurround;
+pub use self::vibrato::Vibrato;
 pub use self::zero::{Zero, ZeroError};
 
 mod agc;
 mod amplify;
+mod amplify_checked;
+mod bit_crush;
 mod blt;
 mod buffered;
 mod channel_volume;
+mod channel_volumes;
 mod chirp;
+mod chorus;
+mod clip;
+mod convert_sample_type;
 mod crossfade;
+mod declick;
 mod delay;
 mod distortion;
 mod done;
+mod duck;
 mod empty;
 mod empty_callback;
+mod equalizer;
 mod fadein;
 mod fadeout;
+mod flanger;
+mod fractional_delay;
+mod frames;
 mod from_factory;
 mod from_iter;
+mod from_sample_iter;
+mod gain_envelope;
+mod hard_limit;
+mod inspect;
+mod instrument;
 mod limit;
 mod linear_ramp;
+mod log_sweep;
+mod map_samples;
+mod meter;
 mod mix;
+mod noise_gate;
+mod normalize;
 mod pausable;
+mod peekable;
 mod periodic;
+mod pitch_shift;
 mod position;
+mod prefetch;
 mod repeat;
+mod reverb;
+mod ring_mod;
 mod sawtooth;
 mod signal_generator;
 mod sine;
 mod skip;
 mod skippable;
+mod soft_clip;
 mod span;
+mod span_chunked;
 mod spatial;
 mod speed;
 mod square;
+mod stereo_width;
 mod stoppable;
 mod take;
+mod take_samples;
+mod tee;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 mod triangle;
+mod trim_silence;
 mod uniform;
+mod upmix_surround;
+mod vibrato;
 mod zero;
 
 pub(crate) use self::span::SpanTracker;
@@ -217,6 +288,37 @@ pub trait Source: Iterator<Item = Sample> {
     /// `None` indicates at the same time "infinite" or "unknown".
     fn total_duration(&self) -> Option<Duration>;
 
+    /// Writes the next samples to `out` and returns how many were written.
+    ///
+    /// This behaves exactly like calling [`next`](Iterator::next) once for every sample of
+    /// `out`, stopping at the first `None`. A return value lower than `out.len()` therefore
+    /// means that `next` returned `None` for the sample after the last one written.
+    ///
+    /// Sources that can produce a block of samples faster than one sample at a time, such as
+    /// [`Amplify`] or the [`Mixer`](crate::mixer::Mixer), override this. Pulling a chain of
+    /// such sources with `fill_buffer` avoids most of the per-sample overhead of the chain.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::{nz, Source};
+    ///
+    /// let mut source = SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5; 3]).amplify(2.0);
+    /// let mut out = [0.0; 4];
+    /// assert_eq!(source.fill_buffer(&mut out), 3);
+    /// assert_eq!(out[..3], [1.0, 1.0, 1.0]);
+    /// ```
+    #[inline]
+    fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+        for (written, slot) in out.iter_mut().enumerate() {
+            match self.next() {
+                Some(sample) => *slot = sample,
+                None => return written,
+            }
+        }
+        out.len()
+    }
+
     /// Stores the source in a buffer in addition to returning it. This iterator can be cloned.
     #[inline]
     fn buffered(self) -> Buffered<Self>
@@ -226,6 +328,36 @@ pub trait Source: Iterator<Item = Sample> {
         buffered::buffered(self)
     }
 
+    /// Decodes the source ahead of time on a background thread.
+    ///
+    /// A background thread keeps `ahead` worth of samples decoded and buffered while the
+    /// returned source is played. The first part of the sound is decoded before this method
+    /// returns, so the start of playback is not delayed by slow decoding. Unlike
+    /// [`buffered`](Source::buffered) this does not keep the whole sound in memory, only the
+    /// prefetch window.
+    ///
+    /// Sources shorter than `ahead` are simply decoded completely.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rodio::{Decoder, Source};
+    /// use std::fs::File;
+    /// use std::time::Duration;
+    ///
+    /// let file = File::open("assets/music.mp3").unwrap();
+    /// let source = Decoder::try_from(file)
+    ///     .unwrap()
+    ///     .buffered_prefetch(Duration::from_millis(500));
+    /// ```
+    #[inline]
+    fn buffered_prefetch(self, ahead: Duration) -> Prefetch
+    where
+        Self: Sized + Send + 'static,
+    {
+        prefetch::prefetch(self, ahead)
+    }
+
     /// Applies dithering to the source at the specified bit depth.
     ///
     /// Dithering eliminates quantization artifacts during digital audio playback
@@ -250,6 +382,33 @@ pub trait Source: Iterator<Item = Sample> {
         Dither::new(self, target_bits, algorithm)
     }
 
+    /// Converts every sample to the sample type `S` and back, to hear or measure the effect of
+    /// quantizing to that type.
+    ///
+    /// The conversion is the same one used to write samples to an audio device of that sample
+    /// type: converting to an integer type truncates towards zero. Add
+    /// [`dither`](Source::dither) before this to dither the quantization.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// // Hear what a quiet tone sounds like on an 8-bit device.
+    /// let lofi = SineWave::new(440.0)
+    ///     .amplify(0.05)
+    ///     .convert_sample_type::<i8>();
+    /// ```
+    #[inline]
+    fn convert_sample_type<S>(self) -> ConvertSampleType<Self, S>
+    where
+        Self: Sized,
+        S: FromSample<Sample>,
+        Sample: FromSample<S>,
+    {
+        convert_sample_type::convert_sample_type(self)
+    }
+
     /// Mixes this source with another one.
     #[inline]
     fn mix<S>(self, other: S) -> Mix<Self, S>
@@ -263,7 +422,8 @@ pub trait Source: Iterator<Item = Sample> {
     /// Repeats this source forever.
     ///
     /// Note that this works by storing the data in a buffer, so the amount of memory used is
-    /// proportional to the size of the sound.
+    /// proportional to the size of the sound. Use [`Repeat::progress`] to find out which
+    /// repetition is playing.
     #[inline]
     fn repeat_infinite(self) -> Repeat<Self>
     where
@@ -272,6 +432,73 @@ pub trait Source: Iterator<Item = Sample> {
         repeat::repeat(self)
     }
 
+    /// Plays this source once, then repeats it forever starting at `loop_start`.
+    ///
+    /// Useful for music with an intro that should only play once before the looping part.
+    /// Unlike [`repeat_infinite`](Source::repeat_infinite) nothing is buffered; the source is
+    /// seeked back to `loop_start` every time it ends, so it must support
+    /// [`try_seek`](Source::try_seek). Playback stops if seeking fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::{nz, Source};
+    /// use std::time::Duration;
+    ///
+    /// let music = SamplesBuffer::new(nz!(2), nz!(44_100), vec![0.0; 44_100 * 2 * 10]);
+    /// // Play the first 2 seconds once, then loop the remaining 8 seconds.
+    /// let looped = music.repeat_from(Duration::from_secs(2));
+    /// ```
+    #[inline]
+    fn repeat_from(self, loop_start: Duration) -> RepeatFrom<Self>
+    where
+        Self: Sized,
+    {
+        repeat::repeat_from(self, loop_start)
+    }
+
+    /// Turns the source into one that can be cloned into several outputs playing the same
+    /// samples, while the source itself is only read once.
+    ///
+    /// Use this to play one mix on several devices at the same time. The outputs may be read
+    /// by different threads, but devices do not share a clock, so this is best effort. See
+    /// [`Tee`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let left_room = SineWave::new(440.0).tee();
+    /// let right_room = left_room.clone();
+    /// ```
+    #[inline]
+    fn tee(self) -> Tee<Self>
+    where
+        Self: Sized,
+    {
+        tee::tee(self)
+    }
+
+    /// Splits the source into spans of `span_len` samples without changing the samples.
+    ///
+    /// [`current_span_len`](Source::current_span_len) reports `span_len`, or less where the
+    /// source changes its channel count or sample rate or ends. This is useful to test how
+    /// sources handle span boundaries, or to control where a [`queue`](crate::queue) or
+    /// mixer checks for format changes. `span_len` should be a multiple of the channel count.
+    ///
+    /// See [`SpanChunked`] for details.
+    ///
+    /// # Panics
+    /// Panics if `span_len` is zero.
+    #[inline]
+    fn with_span_len(self, span_len: usize) -> SpanChunked<Self>
+    where
+        Self: Sized,
+    {
+        span_chunked::span_chunked(self, span_len)
+    }
+
     /// Takes a certain duration of this source and then stops.
     #[inline]
     fn take_duration(self, duration: Duration) -> TakeDuration<Self>
@@ -281,6 +508,44 @@ pub trait Source: Iterator<Item = Sample> {
         take::take_duration(self, duration)
     }
 
+    /// Takes a number of frames of this source and then stops.
+    ///
+    /// A frame holds one sample for every channel, so this yields `frames * channels()`
+    /// samples. Unlike [`take_duration`](Source::take_duration) the length is exact, which is
+    /// useful for tests and for aligning to blocks.
+    #[inline]
+    fn take_samples(self, frames: usize) -> TakeSamples<Self>
+    where
+        Self: Sized,
+    {
+        take_samples::take_samples(self, frames)
+    }
+
+    /// Plays only the part of this source between `start` and `end`.
+    ///
+    /// The source is seeked to `start` if it supports [`try_seek`](Source::try_seek), otherwise
+    /// the samples before `start` are skipped. An `end` past the end of the source is clamped
+    /// to it and if `start` is not before `end` the clip is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::{nz, Source};
+    /// use std::time::Duration;
+    ///
+    /// let music = SamplesBuffer::new(nz!(2), nz!(44_100), vec![0.0; 44_100 * 2 * 20]);
+    /// let chorus = music.clip(Duration::from_secs(10), Duration::from_secs(15));
+    /// assert_eq!(chorus.total_duration(), Some(Duration::from_secs(5)));
+    /// ```
+    #[inline]
+    fn clip(self, start: Duration, end: Duration) -> Clip<Self>
+    where
+        Self: Sized,
+    {
+        clip::clip(self, start, end)
+    }
+
     /// Delays the sound by a certain duration.
     ///
     /// The rate and channels of the silence will use the same format as the first span of the
@@ -293,6 +558,30 @@ pub trait Source: Iterator<Item = Sample> {
         delay::delay(self, duration)
     }
 
+    /// Delays the sound by a number of frames, each of them `channels()` silent samples.
+    ///
+    /// Unlike [`delay`](Source::delay) no duration is rounded to samples, so tracks can be
+    /// aligned exactly to each other. The rate and channels of the silence will use the same
+    /// format as the first span of the source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::{nz, Source};
+    ///
+    /// let source = SamplesBuffer::new(nz!(2), nz!(44_100), vec![0.5; 4]);
+    /// let delayed: Vec<_> = source.delay_frames(1).collect();
+    /// assert_eq!(delayed, [0.0, 0.0, 0.5, 0.5, 0.5, 0.5]);
+    /// ```
+    #[inline]
+    fn delay_frames(self, frames: usize) -> DelayFrames<Self>
+    where
+        Self: Sized,
+    {
+        delay::delay_frames(self, frames)
+    }
+
     /// Immediately skips a certain duration of this source.
     ///
     /// If the specified duration is longer than the source itself, `skip_duration` will skip to the end of the source.
@@ -304,6 +593,34 @@ pub trait Source: Iterator<Item = Sample> {
         skip::skip_duration(self, duration)
     }
 
+    /// Removes silence from the start and the end of the sound.
+    ///
+    /// Frames in which every sample has an absolute value below `threshold` are considered
+    /// silent. Leading silence is skipped. Once a run of silence lasts longer than the trailing
+    /// silence duration (500ms unless changed with [`TrimSilence::with_trailing_silence`]) the
+    /// source ends. Shorter pauses and quiet fades are played as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::nz;
+    /// use rodio::source::Source;
+    /// use std::time::Duration;
+    ///
+    /// let recording = SamplesBuffer::new(nz!(1), nz!(44_100), vec![0.0; 44_100]);
+    /// let trimmed = recording
+    ///     .trim_silence(0.01)
+    ///     .with_trailing_silence(Duration::from_secs(1));
+    /// ```
+    #[inline]
+    fn trim_silence(self, threshold: Float) -> TrimSilence<Self>
+    where
+        Self: Sized,
+    {
+        trim_silence::trim_silence(self, threshold)
+    }
+
     /// Amplifies the sound by the given value.
     #[inline]
     fn amplify(self, value: Float) -> Amplify<Self>
@@ -322,6 +639,36 @@ pub trait Source: Iterator<Item = Sample> {
         amplify::amplify(self, math::db_to_linear(value))
     }
 
+    /// Applies a ReplayGain, so that tracks with different loudness play equally loud.
+    ///
+    /// `gain_db` is the gain in dB, usually from [`Decoder::replay_gain`](crate::Decoder::replay_gain).
+    /// Pass the peak sample value of the track, for example from
+    /// [`Decoder::replay_gain_peak`](crate::Decoder::replay_gain_peak), to lower the gain so the
+    /// loudest sample does not clip, or `None` to apply the full gain.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use rodio::{Decoder, Source};
+    ///
+    /// let decoder = Decoder::try_from(File::open("track.flac").unwrap()).unwrap();
+    /// let gain = decoder.replay_gain().unwrap_or(0.0);
+    /// let peak = decoder.replay_gain_peak();
+    /// let source = decoder.apply_replay_gain(gain, peak);
+    /// ```
+    #[inline]
+    fn apply_replay_gain(self, gain_db: Float, peak: Option<Float>) -> Amplify<Self>
+    where
+        Self: Sized,
+    {
+        let mut factor = math::db_to_linear(gain_db);
+        if let Some(peak) = peak.filter(|peak| *peak > 0.0) {
+            factor = factor.min(1.0 / peak);
+        }
+        amplify::amplify(self, factor)
+    }
+
     /// Normalized amplification in `[0.0, 1.0]` range. This method better matches the perceived
     /// loudness of sounds in human hearing and is recommended to use when you want to change
     /// volume in `[0.0, 1.0]` range.
@@ -348,6 +695,89 @@ pub trait Source: Iterator<Item = Sample> {
         amplify::amplify(self, amplitude)
     }
 
+    /// Reads the whole sound into memory and scales it so that its loudest sample has an
+    /// absolute value of `target_peak`.
+    ///
+    /// Unlike [`automatic_gain_control`](Source::automatic_gain_control) this applies one gain
+    /// to the whole sound, which keeps its dynamics. That needs the complete sound before the
+    /// first sample can be played, so it suits offline processing such as leveling uploaded
+    /// files. A silent sound is left unchanged. Do not confuse this with
+    /// [`amplify_normalized`](Source::amplify_normalized), which maps a volume slider to a gain.
+    ///
+    /// Returns [`NormalizeError`] for sources that report to never end, like the
+    /// [`SignalGenerator`]. Limit those with [`take_duration`](Source::take_duration) first.
+    /// Other sources that never end make this read forever.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::nz;
+    /// use rodio::source::Source;
+    ///
+    /// let quiet = SamplesBuffer::new(nz!(1), nz!(44_100), vec![0.1, -0.25, 0.2]);
+    /// let leveled = quiet.normalize(0.5).unwrap();
+    /// assert_eq!(leveled.collect::<Vec<_>>(), [0.2, -0.5, 0.4]);
+    /// ```
+    #[inline]
+    fn normalize(self, target_peak: Float) -> Result<Normalize, NormalizeError>
+    where
+        Self: Sized,
+    {
+        normalize::normalize(self, target_peak)
+    }
+
+    /// Amplifies the sound by the given value and reports when the result clips.
+    ///
+    /// Returns the amplified source and a [`ClipFlag`] that is raised whenever a sample ends up
+    /// outside of ±1.0, for example to light a clip indicator. The audio is the same as with
+    /// [`amplify`](Source::amplify).
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let (source, clip) = SineWave::new(440.0).amplify_checked(2.0);
+    /// let _ = source.take(100).count();
+    /// assert!(clip.clipped());
+    /// ```
+    #[inline]
+    fn amplify_checked(self, value: Float) -> (AmplifyChecked<Self>, ClipFlag)
+    where
+        Self: Sized,
+    {
+        amplify_checked::amplify_checked(self, value)
+    }
+
+    /// Multiplies every channel with its own gain, for example to trim a single speaker.
+    ///
+    /// `gains` holds one gain per channel, in the channel order of the source. Channels without
+    /// a gain play unchanged and extra gains are ignored. Use [`ChannelVolumes::handle`] to
+    /// change the gains while the source is playing, for example from a mixing desk UI.
+    ///
+    /// Unlike [`ChannelVolume`] this keeps the channels of the source apart instead of mixing
+    /// them to mono first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::source::Source;
+    /// use rodio::nz;
+    ///
+    /// let stereo = SamplesBuffer::new(nz!(2), nz!(44_100), vec![0.5, 0.5]);
+    /// let right_only = stereo.channel_volumes(vec![0.0, 1.0]);
+    /// let handle = right_only.handle();
+    /// handle.set_gain(0, 0.5);
+    /// assert_eq!(right_only.collect::<Vec<_>>(), [0.25, 0.5]);
+    /// ```
+    #[inline]
+    fn channel_volumes(self, gains: Vec<Float>) -> ChannelVolumes<Self>
+    where
+        Self: Sized,
+    {
+        channel_volumes::channel_volumes(self, gains)
+    }
+
     /// Applies automatic gain control to the sound.
     ///
     /// Automatic Gain Control (AGC) adjusts the amplitude of the audio signal
@@ -471,6 +901,61 @@ pub trait Source: Iterator<Item = Sample> {
         fadeout::fadeout(self, duration)
     }
 
+    /// Fades in the sound following the given curve.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{FadeCurve, SineWave, Source};
+    /// use std::time::Duration;
+    ///
+    /// let source = SineWave::new(440.0)
+    ///     .fade_in_with_curve(Duration::from_secs(2), FadeCurve::Exponential { steepness: 5.0 });
+    /// ```
+    #[inline]
+    fn fade_in_with_curve(self, duration: Duration, curve: FadeCurve) -> FadeIn<Self>
+    where
+        Self: Sized,
+    {
+        fadein::fadein_with_curve(self, duration, curve)
+    }
+
+    /// Fades out the sound following the given curve. The curve is followed backwards, so the
+    /// gain drops quickly at first for an [exponential](FadeCurve::Exponential) curve.
+    #[inline]
+    fn fade_out_with_curve(self, duration: Duration, curve: FadeCurve) -> FadeOut<Self>
+    where
+        Self: Sized,
+    {
+        fadeout::fadeout_with_curve(self, duration, curve)
+    }
+
+    /// Fades out before and fades in after every seek, which removes the click of jumping to
+    /// another position in the waveform.
+    ///
+    /// Each fade takes `ramp`, a few milliseconds such as [`DEFAULT_DECLICK_RAMP`] are
+    /// enough. The fade-out plays the audio that follows the position before the seek, so a
+    /// seek is heard `ramp` later. See [`DeclickOnSeek`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::nz;
+    /// use rodio::source::{Source, DEFAULT_DECLICK_RAMP};
+    /// use std::time::Duration;
+    ///
+    /// let mut source = SamplesBuffer::new(nz!(1), nz!(44_100), vec![0.5; 44_100])
+    ///     .declick_on_seek(DEFAULT_DECLICK_RAMP);
+    /// source.try_seek(Duration::from_millis(500)).unwrap();
+    /// ```
+    #[inline]
+    fn declick_on_seek(self, ramp: Duration) -> DeclickOnSeek<Self>
+    where
+        Self: Sized,
+    {
+        declick::declick_on_seek(self, ramp)
+    }
+
     /// Applies limiting to prevent audio peaks from exceeding a threshold.
     ///
     /// A limiter reduces the amplitude of audio signals that exceed a specified level,
@@ -525,6 +1010,41 @@ pub trait Source: Iterator<Item = Sample> {
         limit::limit(self, settings)
     }
 
+    /// Attenuates the sound to near silence while it is quieter than `threshold_db`.
+    ///
+    /// Useful to mute hum or background noise between spoken phrases. The gate opens over
+    /// `attack` as soon as the sound exceeds the threshold. Once it falls below, the gate stays
+    /// open for `hold` and then closes over `release`, which avoids chatter on sounds that
+    /// hover around the threshold. All channels are gated together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    /// use std::time::Duration;
+    ///
+    /// let voice = SineWave::new(440.0).amplify(0.5);
+    /// let gated = voice.noise_gate(
+    ///     -50.0,
+    ///     Duration::from_millis(1),
+    ///     Duration::from_millis(100),
+    ///     Duration::from_millis(50),
+    /// );
+    /// ```
+    #[inline]
+    fn noise_gate(
+        self,
+        threshold_db: Float,
+        attack: Duration,
+        hold: Duration,
+        release: Duration,
+    ) -> NoiseGate<Self>
+    where
+        Self: Sized,
+    {
+        noise_gate::noise_gate(self, threshold_db, attack, hold, release)
+    }
+
     /// Applies a linear gain ramp to the sound.
     ///
     /// If `clamp_end` is `true`, all samples subsequent to the end of the ramp
@@ -544,6 +1064,58 @@ pub trait Source: Iterator<Item = Sample> {
         linear_ramp::linear_gain_ramp(self, duration, start_value, end_value, clamp_end)
     }
 
+    /// Changes the gain over time following a list of `(time, gain)` breakpoints.
+    ///
+    /// The gain moves linearly between breakpoints based on the play position, and stays at
+    /// the last value after the last breakpoint. This makes fades and ducking possible without
+    /// touching the samples by hand. See [`GainEnvelope`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    /// use std::time::Duration;
+    ///
+    /// // Duck the music to a quarter between 2 and 5 seconds.
+    /// let music = SineWave::new(440.0).automate_gain(vec![
+    ///     (Duration::from_secs(2), 1.0),
+    ///     (Duration::from_millis(2200), 0.25),
+    ///     (Duration::from_secs(5), 0.25),
+    ///     (Duration::from_millis(5200), 1.0),
+    /// ]);
+    /// ```
+    #[inline]
+    fn automate_gain(self, points: Vec<(Duration, Float)>) -> GainEnvelope<Self>
+    where
+        Self: Sized,
+    {
+        gain_envelope::gain_envelope(self, points)
+    }
+
+    /// Measures the level of this source for a [`Ducker`], which lowers the volume of the
+    /// sources added with [`ducked_by`](Source::ducked_by) while this one is loud.
+    ///
+    /// The samples are not changed. See [`Ducker`] for an example.
+    #[inline]
+    fn duck_key(self, ducker: &Ducker) -> DuckKey<Self>
+    where
+        Self: Sized,
+    {
+        duck::duck_key(self, ducker)
+    }
+
+    /// Lowers the volume of this source while the key source of the [`Ducker`] is loud, for
+    /// example to lower music while a voice plays.
+    ///
+    /// See [`Ducker`] for an example.
+    #[inline]
+    fn ducked_by(self, ducker: &Ducker) -> Ducked<Self>
+    where
+        Self: Sized,
+    {
+        duck::ducked(self, ducker)
+    }
+
     /// Calls the `access` closure on `Self` the first time the source is iterated and every
     /// time `period` elapses.
     ///
@@ -585,14 +1157,115 @@ pub trait Source: Iterator<Item = Sample> {
         speed::speed(self, ratio)
     }
 
-    /// Consumes the source and returns a SamplesBuffer
+    /// Changes the pitch of the sound by the given number of semitones without changing its
+    /// duration.
     ///
-    /// Use `take_duration` on infinite sources (like the microphone source) before
-    /// calling `record` to prevent this from hanging forever.
+    /// Positive values raise the pitch, negative values lower it. A value of `12.0` raises the
+    /// sound by one octave. Unlike [`Source::speed`] the number of samples and the
+    /// [`total_duration`](Source::total_duration) stay the same.
     ///
-    /// # Note
-    /// As `SamplesBuffer` only supports a single *samplerate* and *channel count*
-    /// all samples are resampled to the initial samplerate and channel count is.
+    /// See [`PitchShift`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// // Plays at 880Hz.
+    /// let source = SineWave::new(440.0).pitch_shift(12.0);
+    /// ```
+    #[inline]
+    fn pitch_shift(self, semitones: f32) -> PitchShift<Self>
+    where
+        Self: Sized,
+    {
+        pitch_shift::pitch_shift(self, semitones)
+    }
+
+    /// Periodically raises and lowers the pitch of the sound, like a singer's vibrato.
+    ///
+    /// The pitch goes up and down `rate_hz` times per second. `depth` is how far the internal
+    /// delay swings, the pitch changes by up to `2π * rate_hz * depth`. The duration of the
+    /// sound does not change.
+    ///
+    /// See [`Vibrato`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// // Swings the pitch by about ±3%, five times per second.
+    /// let source = SineWave::new(440.0).vibrato(5.0, Duration::from_millis(1));
+    /// ```
+    #[inline]
+    fn vibrato(self, rate_hz: Float, depth: Duration) -> Vibrato<Self>
+    where
+        Self: Sized,
+    {
+        vibrato::vibrato(self, rate_hz, depth)
+    }
+
+    /// Mixes the sound with a copy of itself delayed by a few milliseconds, with the delay
+    /// slowly sweeping up and down.
+    ///
+    /// The delay sweeps `rate_hz` times per second and swings by `depth` around its center,
+    /// at most 5 ms. `feedback` sends part of the delayed signal back into the delay for a
+    /// sharper, more resonant sound and is clamped to ±0.95. `mix` is the part of the output
+    /// that is the delayed signal.
+    ///
+    /// See [`Flanger`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let source = SineWave::new(440.0).flanger(0.25, Duration::from_millis(2), 0.5, 0.5);
+    /// ```
+    #[inline]
+    fn flanger(self, rate_hz: Float, depth: Duration, feedback: Float, mix: Float) -> Flanger<Self>
+    where
+        Self: Sized,
+    {
+        flanger::flanger(self, rate_hz, depth, feedback, mix)
+    }
+
+    /// Mixes the sound with several slightly detuned and delayed copies of itself, making one
+    /// instrument sound like an ensemble.
+    ///
+    /// Each of the `voices` is delayed by around 20 ms, with the delay swinging by `depth` at
+    /// `rate_hz`. The depth is limited to 10 ms. `mix` is the part of the output that is the
+    /// delayed voices.
+    ///
+    /// See [`Chorus`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let source = SineWave::new(440.0).chorus(3, 0.8, Duration::from_millis(3), 0.5);
+    /// ```
+    #[inline]
+    fn chorus(self, voices: usize, rate_hz: Float, depth: Duration, mix: Float) -> Chorus<Self>
+    where
+        Self: Sized,
+    {
+        chorus::chorus(self, voices, rate_hz, depth, mix)
+    }
+
+    /// Consumes the source and returns a SamplesBuffer
+    ///
+    /// Use `take_duration` on infinite sources (like the microphone source) before
+    /// calling `record` to prevent this from hanging forever.
+    ///
+    /// # Note
+    /// As `SamplesBuffer` only supports a single *samplerate* and *channel count*
+    /// all samples are resampled to the initial samplerate and channel count is.
     ///
     /// # Example
     /// ```no_run
@@ -612,6 +1285,68 @@ pub trait Source: Iterator<Item = Sample> {
         SamplesBuffer::record_source(self)
     }
 
+    /// Converts the source into an iterator over frames.
+    ///
+    /// Every item holds one sample per channel, so its length is [`channels()`](Source::channels)
+    /// at the time the frame was read. Useful for feeding audio to analysis code that works on
+    /// frames rather than interleaved samples.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::nz;
+    /// use rodio::Source;
+    ///
+    /// let stereo = SamplesBuffer::new(nz!(2), nz!(44_100), vec![0.1, 0.2, 0.3, 0.4]);
+    /// for frame in stereo.into_frames() {
+    ///     let (left, right) = (frame[0], frame[1]);
+    /// }
+    /// ```
+    #[inline]
+    fn into_frames(self) -> Frames<Self>
+    where
+        Self: Sized,
+    {
+        frames::frames(self)
+    }
+
+    /// Upmixes stereo to 5.1 surround for playing on surround speakers.
+    ///
+    /// The center and LFE channels are derived from the sum of both channels, the surround
+    /// channels from their difference. Sources that are not stereo pass through unchanged. See
+    /// [`UpmixSurround`] for details.
+    #[inline]
+    fn upmix_surround(self) -> UpmixSurround<Self>
+    where
+        Self: Sized,
+    {
+        upmix_surround::upmix_surround(self)
+    }
+
+    /// Widens or narrows the stereo image.
+    ///
+    /// A `width` of `0.0` plays mono, `1.0` leaves the source unchanged and values above `1.0`
+    /// make it sound wider. What is in the center keeps its level. Sources that are not stereo
+    /// pass through unchanged. See [`StereoWidth`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::{nz, Source};
+    ///
+    /// let source = SamplesBuffer::new(nz!(2), nz!(44100), vec![1.0, 0.0]);
+    /// let mono: Vec<_> = source.stereo_width(0.0).collect();
+    /// assert_eq!(mono, [0.5, 0.5]);
+    /// ```
+    #[inline]
+    fn stereo_width(self, width: Float) -> StereoWidth<Self>
+    where
+        Self: Sized,
+    {
+        stereo_width::stereo_width(self, width)
+    }
+
     /// Adds a basic reverb effect.
     ///
     /// This function requires the source to implement `Clone`. This can be done by using
@@ -633,6 +1368,33 @@ pub trait Source: Iterator<Item = Sample> {
         self.mix(echo)
     }
 
+    /// Adds a Freeverb-style reverb built from comb and all-pass filters.
+    ///
+    /// `room_size` and `damping` range from `0.0` to `1.0` and control the length of the tail
+    /// and how quickly its high frequencies fade. `wet` is the proportion of reverb in the
+    /// output, `0.0` only plays the dry source. The tail keeps playing after the source has
+    /// ended until it has decayed to silence.
+    ///
+    /// Unlike [`reverb`](Source::reverb) this does not require the source to implement `Clone`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    /// use std::time::Duration;
+    ///
+    /// let source = SineWave::new(440.0)
+    ///     .take_duration(Duration::from_millis(200))
+    ///     .schroeder_reverb(0.7, 0.5, 0.3);
+    /// ```
+    #[inline]
+    fn schroeder_reverb(self, room_size: f32, damping: f32, wet: f32) -> Reverb<Self>
+    where
+        Self: Sized,
+    {
+        reverb::reverb(self, room_size, damping, wet)
+    }
+
     /// Makes the sound pausable.
     // TODO: add example
     #[inline]
@@ -680,6 +1442,134 @@ pub trait Source: Iterator<Item = Sample> {
         position::track_position(self)
     }
 
+    /// Measures how long this source takes to produce each sample, to find the part of a chain
+    /// that is too slow. Only debug builds measure anything, in release builds this adds no
+    /// overhead.
+    ///
+    /// The measurements are read through the handle returned by
+    /// [`Instrumented::stats`]. With the `tracing` feature a summary is logged when the source
+    /// is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let source = SineWave::new(440.0).low_pass(1000).instrument("low pass");
+    /// let stats = source.stats();
+    /// source.take(48_000).for_each(drop);
+    /// println!("{}: {:?} per sample", stats.name(), stats.average());
+    /// ```
+    #[inline]
+    fn instrument(self, name: &str) -> Instrumented<Self>
+    where
+        Self: Sized,
+    {
+        instrument::instrument(self, name)
+    }
+
+    /// Calls a function on every sample as it passes, for debugging or custom metering.
+    ///
+    /// Like [`Iterator::inspect`], but the result is still a [`Source`]. Named differently
+    /// because both methods would otherwise be ambiguous on every source. The function runs in
+    /// the audio thread when playing, so it must be cheap and must not block.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let mut peak: f32 = 0.0;
+    /// let source = SineWave::new(440.0)
+    ///     .take_duration(std::time::Duration::from_millis(10))
+    ///     .inspect_samples(|sample| peak = peak.max(sample.abs() as f32));
+    /// source.for_each(drop);
+    /// assert!(peak > 0.9);
+    /// ```
+    #[inline]
+    fn inspect_samples<F>(self, f: F) -> Inspect<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Sample) + Send,
+    {
+        inspect::inspect(self, f)
+    }
+
+    /// Replaces every sample with the result of a function, for quick custom effects such as a
+    /// bit crusher or a distortion curve.
+    ///
+    /// Channels, sample rate, duration and seeking are passed on unchanged. The function is
+    /// called on the interleaved samples of all channels. It runs in the audio thread when
+    /// playing, so it must be cheap and must not block. Named differently from
+    /// [`Iterator::map`], which would return an iterator instead of a [`Source`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// // Reduce the resolution to 16 steps.
+    /// let crushed = SineWave::new(440.0).map_samples(|sample| (sample * 8.0).round() / 8.0);
+    /// ```
+    #[inline]
+    fn map_samples<F>(self, f: F) -> MapSamples<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Sample) -> Sample + Send,
+    {
+        map_samples::map_samples(self, f)
+    }
+
+    /// Makes it possible to look at the next sample without consuming it, see
+    /// [`PeekableSource::peek`].
+    ///
+    /// Like [`Iterator::peekable`], but the result is still a [`Source`] whose metadata describes
+    /// the next sample, also while it is peeked. Named differently because both methods would
+    /// otherwise be ambiguous on every source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let mut source = SineWave::new(440.0).peekable_source();
+    /// let next = *source.peek().unwrap();
+    /// assert_eq!(source.next(), Some(next));
+    /// ```
+    #[inline]
+    fn peekable_source(self) -> PeekableSource<Self>
+    where
+        Self: Sized,
+    {
+        peekable::peekable(self)
+    }
+
+    /// Measures the peak level of every channel, for example to draw a level meter.
+    ///
+    /// Returns the source, which plays unchanged, and a [`MeterHandle`] that reads the levels
+    /// from any thread. See [`MeterHandle::peak_hold`] for meters that fall back slowly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    /// use std::time::Duration;
+    ///
+    /// let (source, meter) = SineWave::new(440.0)
+    ///     .amplify(0.5)
+    ///     .take_duration(Duration::from_millis(10))
+    ///     .meter();
+    /// source.for_each(drop);
+    /// assert!(meter.channel_peaks()[0] > 0.45);
+    /// ```
+    #[inline]
+    fn meter(self) -> (Meter<Self>, MeterHandle)
+    where
+        Self: Sized,
+    {
+        meter::meter(self)
+    }
+
     /// Applies a low-pass filter to the source.
     /// **Warning**: Probably buggy.
     #[inline]
@@ -701,6 +1591,34 @@ pub trait Source: Iterator<Item = Sample> {
         blt::high_pass(self, freq)
     }
 
+    /// Applies a low-shelf filter to the source, which raises or lowers everything below `freq`
+    /// by `gain_db`, for example as a bass control.
+    ///
+    /// `slope` sets how steep the transition is, `1.0` is the steepest without overshooting
+    /// the gain around `freq`. Follows the
+    /// [Audio EQ Cookbook](https://webaudio.github.io/Audio-EQ-Cookbook/audio-eq-cookbook.html).
+    #[inline]
+    fn low_shelf(self, freq: u32, gain_db: Float, slope: Float) -> BltFilter<Self>
+    where
+        Self: Sized,
+        Self: Source<Item = Sample>,
+    {
+        blt::low_shelf(self, freq, gain_db, slope)
+    }
+
+    /// Applies a high-shelf filter to the source, which raises or lowers everything above
+    /// `freq` by `gain_db`, for example as a treble control.
+    ///
+    /// See [`low_shelf`](Source::low_shelf) for the `slope`.
+    #[inline]
+    fn high_shelf(self, freq: u32, gain_db: Float, slope: Float) -> BltFilter<Self>
+    where
+        Self: Sized,
+        Self: Source<Item = Sample>,
+    {
+        blt::high_shelf(self, freq, gain_db, slope)
+    }
+
     /// Applies a low-pass filter to the source while allowing the q (bandwidth) to be changed.
     #[inline]
     fn low_pass_with_q(self, freq: u32, q: Float) -> BltFilter<Self>
@@ -721,6 +1639,30 @@ pub trait Source: Iterator<Item = Sample> {
         blt::high_pass_with_q(self, freq, q)
     }
 
+    /// Applies a parametric equalizer made of one peaking filter per band.
+    ///
+    /// Use [`Equalizer::handle`] to change the gain of the bands while the source is playing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{EqBand, SineWave, Source};
+    ///
+    /// let source = SineWave::new(440.0).equalizer(vec![
+    ///     EqBand { freq: 100.0, q: 0.7, gain_db: 3.0 },
+    ///     EqBand { freq: 3000.0, q: 1.0, gain_db: -6.0 },
+    /// ]);
+    /// let handle = source.handle();
+    /// handle.set_gain_db(1, -3.0);
+    /// ```
+    #[inline]
+    fn equalizer(self, bands: Vec<EqBand>) -> Equalizer<Self>
+    where
+        Self: Sized,
+    {
+        equalizer::equalizer(self, bands)
+    }
+
     /// Applies a distortion effect to the sound.
     #[inline]
     fn distortion(self, gain: Float, threshold: Float) -> Distortion<Self>
@@ -730,9 +1672,133 @@ pub trait Source: Iterator<Item = Sample> {
         distortion::distortion(self, gain, threshold)
     }
 
-    // There is no `can_seek()` method as it is impossible to use correctly. Between
-    // checking if a source supports seeking and actually seeking the sink can
-    // switch to a new source.
+    /// Applies a soft saturation curve to the sound.
+    ///
+    /// Every sample is multiplied by `drive` and passed through `tanh`. At a `drive` of `1.0`
+    /// quiet signals pass nearly unchanged while peaks near ±1.0 are rounded off. Higher values
+    /// saturate more. The output always stays within ±1.0.
+    ///
+    /// Unlike [`Source::limit`] this does not follow the level of the signal: the curve is
+    /// applied instantly to each sample and adds harmonics. Unlike [`Source::distortion`] it
+    /// does not clip hard.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let warm = SineWave::new(440.0).soft_clip(1.5);
+    /// ```
+    #[inline]
+    fn soft_clip(self, drive: Float) -> SoftClip<Self>
+    where
+        Self: Sized,
+    {
+        soft_clip::soft_clip(self, drive)
+    }
+
+    /// Clamps every sample to `±ceiling`, a guaranteed limit for the level of the output.
+    ///
+    /// This is a cheap safety net for audio that could get too loud, for example sounds made by
+    /// users. Pass `1.0` to stay within full scale. Louder samples are cut off, which distorts,
+    /// so use [`Source::limit`] in front of it to keep the sound clean.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ceiling` is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let safe = SineWave::new(440.0).amplify(4.0).hard_limit(1.0);
+    /// assert!(safe.take(1000).all(|sample| sample.abs() <= 1.0));
+    /// ```
+    #[inline]
+    fn hard_limit(self, ceiling: Float) -> HardLimit<Self>
+    where
+        Self: Sized,
+    {
+        hard_limit::hard_limit(self, ceiling)
+    }
+
+    /// Reduces the resolution and the sample rate of the sound, for a lo-fi effect.
+    ///
+    /// Every sample is rounded to the resolution of a `bits` bit signed integer, and every
+    /// frame kept is repeated for `downsample_factor` frames. The rate is reduced without any
+    /// filtering, the resulting aliasing is part of the effect. Use [`BitCrush::set_bits`] and
+    /// [`BitCrush::set_downsample_factor`] to change the effect while playing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` or `downsample_factor` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let retro = SineWave::new(440.0).bit_crush(4, 8);
+    /// ```
+    #[inline]
+    fn bit_crush(self, bits: u8, downsample_factor: u32) -> BitCrush<Self>
+    where
+        Self: Sized,
+    {
+        bit_crush::bit_crush(self, bits, downsample_factor)
+    }
+
+    /// Multiplies the sound with a sine wave at `carrier_hz`, a ring modulator.
+    ///
+    /// Every frequency in the sound is replaced by its sum and difference with the carrier
+    /// frequency, giving a metallic, robotic character. Carriers of a few tens of Hz sound like
+    /// a tremolo, higher ones like a sci-fi voice.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::source::{SineWave, Source};
+    ///
+    /// let robot = SineWave::new(440.0).ring_modulate(30.0);
+    /// ```
+    #[inline]
+    fn ring_modulate(self, carrier_hz: Float) -> RingMod<Self>
+    where
+        Self: Sized,
+    {
+        ring_mod::ring_modulate(self, carrier_hz)
+    }
+
+    /// Returns `true` if this source supports [`try_seek`](Source::try_seek).
+    ///
+    /// This allows checking up front, for example to disable a seek bar, instead of attempting
+    /// a seek. Filters forward this to the source they wrap, buffers, generators and seekable
+    /// decoders return `true`.
+    ///
+    /// This is only a hint, a seek can still fail even if this returns `true`. The underlying
+    /// reader can run into an IO error, and a source made of other sources, like the queue of
+    /// a [`Player`](crate::Player), can switch to a source that does not support seeking
+    /// between this check and the seek. Always handle the error of
+    /// [`try_seek`](Source::try_seek).
+    ///
+    /// The default implementation returns `false`, matching the default `try_seek`.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::source::{from_sample_iter, Source};
+    /// use rodio::nz;
+    ///
+    /// let buffer = SamplesBuffer::new(nz!(1), nz!(44100), vec![0.0; 100]);
+    /// assert!(buffer.amplify(0.5).is_seekable());
+    ///
+    /// let stream = from_sample_iter(vec![0i16; 100], nz!(1), nz!(44100));
+    /// assert!(!stream.is_seekable());
+    /// ```
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        false
+    }
 
     /// Attempts to seek to a given position in the current source.
     ///
@@ -748,20 +1814,50 @@ pub trait Source: Iterator<Item = Sample> {
     /// It will return an error if an implementation ran
     /// into one during the seek.
     ///
-    /// Seeking beyond the end of a source might return an error if the total duration of
-    /// the source is not known.
+    /// Seeking beyond the end of a source might return [`SeekError::OutOfRange`] if the
+    /// total duration of the source is not known.
     #[allow(unused_variables)]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         Err(SeekError::NotSupported {
             underlying_source: std::any::type_name::<Self>(),
         })
     }
+
+    /// Attempts to seek to the start of a frame, counted from the start of the source at its
+    /// current sample rate. A frame is one sample for every channel.
+    ///
+    /// A [`Duration`] can not represent every frame position exactly. Seeking to a frame
+    /// avoids rounding errors where it matters, for example for sample exact loop points.
+    /// [`SamplesBuffer`](crate::buffer::SamplesBuffer) and the [`Decoder`](crate::Decoder)
+    /// seek to the exact frame, other sources seek to the start of the frame through
+    /// [`try_seek`](Source::try_seek).
+    ///
+    /// # Errors
+    /// See [`try_seek`](Source::try_seek).
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::{nz, Source};
+    ///
+    /// let mut source = SamplesBuffer::new(nz!(2), nz!(44100), vec![0.0, 0.1, 0.2, 0.3, 0.4, 0.5]);
+    /// source.try_seek_frame(2).unwrap();
+    /// assert_eq!(source.next(), Some(0.4));
+    /// ```
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), SeekError> {
+        self.try_seek(math::frame_to_duration(frame, self.sample_rate()))
+    }
 }
 
 // We might add decoders requiring new error types, without non_exhaustive
 // this would break users' builds.
 /// Occurs when `try_seek` fails because the underlying decoder has an error or
 /// does not support seeking.
+///
+/// Match on the variant to react to the cause. For example a seek bar can clamp the
+/// position on [`SeekError::OutOfRange`] but should be disabled on
+/// [`SeekError::NotSupported`]. New variants may be added in minor releases, so always
+/// include a wildcard arm.
 #[non_exhaustive]
 #[derive(Debug, thiserror::Error, Clone)]
 pub enum SeekError {
@@ -771,12 +1867,28 @@ pub enum SeekError {
         /// The source that did not support seek
         underlying_source: &'static str,
     },
+    /// The requested position lies beyond the end of the source
+    ///
+    /// Sources whose duration is known saturate at the end instead, so this is only
+    /// returned when the duration was not known up front. The source remains at its
+    /// position before the seek.
+    #[error("Seek position {requested:?} is out of range")]
+    OutOfRange {
+        /// The position that was requested
+        requested: Duration,
+        /// The total duration of the source, if known
+        total: Option<Duration>,
+    },
+    /// Reading from or seeking the underlying stream failed
+    #[error("I/O error while seeking")]
+    Io(#[source] Arc<std::io::Error>),
     #[cfg(feature = "symphonia")]
     /// The symphonia decoder ran into an issue
     #[error("Symphonia decoder returned an error")]
     SymphoniaDecoder(#[source] crate::decoder::symphonia::SeekError),
     #[cfg(feature = "hound")]
     /// The hound (wav) decoder ran into an issue
+    #[deprecated(note = "hound errors are now reported as `SeekError::Io`")]
     #[error("Hound decoder returned an error")]
     HoundDecoder(#[source] Arc<std::io::Error>),
     // Prefer adding an enum variant to using this. It's meant for end users their
@@ -794,15 +1906,24 @@ impl From<crate::decoder::symphonia::SeekError> for SeekError {
     }
 }
 
+impl From<std::io::Error> for SeekError {
+    fn from(source: std::io::Error) -> Self {
+        SeekError::Io(Arc::new(source))
+    }
+}
+
 impl SeekError {
     /// Will the source remain playing at its position before the seek or is it
     /// broken?
     pub fn source_intact(&self) -> bool {
         match self {
             SeekError::NotSupported { .. } => true,
+            SeekError::OutOfRange { .. } => true,
+            SeekError::Io(_) => false,
             #[cfg(feature = "symphonia")]
             SeekError::SymphoniaDecoder(_) => false,
             #[cfg(feature = "hound")]
+            #[allow(deprecated)]
             SeekError::HoundDecoder(_) => false,
             SeekError::Other(_) => false,
         }
@@ -832,6 +1953,16 @@ macro_rules! source_pointer_impl {
                 (**self).total_duration()
             }
 
+            #[inline]
+            fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+                (**self).fill_buffer(out)
+            }
+
+            #[inline]
+            fn is_seekable(&self) -> bool {
+                (**self).is_seekable()
+            }
+
             #[inline]
             fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
                 (**self).try_seek(pos)
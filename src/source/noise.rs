@@ -86,6 +86,10 @@ macro_rules! impl_noise_source {
                 None
             }
 
+            fn is_seekable(&self) -> bool {
+                true
+            }
+
             fn try_seek(&mut self, _pos: Duration) -> Result<(), crate::source::SeekError> {
                 // Stateless noise generators can seek to any position since all positions
                 // are equally random and don't depend on previous state
@@ -788,6 +792,10 @@ impl<R: Rng> Source for Brownian<R> {
         None
     }
 
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
     fn try_seek(&mut self, _pos: Duration) -> Result<(), crate::source::SeekError> {
         // Stateless noise generators can seek to any position since all positions
         // are equally random and don't depend on previous state
@@ -871,6 +879,10 @@ impl<R: Rng> Source for Red<R> {
         None
     }
 
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
     fn try_seek(&mut self, _pos: Duration) -> Result<(), crate::source::SeekError> {
         // Stateless noise generators can seek to any position since all positions
         // are equally random and don't depend on previous state
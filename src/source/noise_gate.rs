@@ -0,0 +1,269 @@
+use std::time::Duration;
+
+use super::{SeekError, SpanTracker};
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::{self, duration_to_coefficient};
+use crate::{Float, Sample, Source};
+
+/// Gain of a closed gate, -80 dB.
+const CLOSED_GAIN: Float = 1e-4;
+
+/// Internal function that builds a `NoiseGate` object.
+pub fn noise_gate<I>(
+    input: I,
+    threshold_db: Float,
+    attack: Duration,
+    hold: Duration,
+    release: Duration,
+) -> NoiseGate<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let channels = input.channels();
+    let mut gate = NoiseGate {
+        input,
+        threshold: math::db_to_linear(threshold_db),
+        attack,
+        hold,
+        release,
+        attack_coeff: 0.0,
+        release_coeff: 0.0,
+        hold_frames: 0,
+        hold_remaining: 0,
+        gain: 1.0,
+        frame_peak: 0.0,
+        current_channel: 0,
+        channels: channels.get(),
+        span: SpanTracker::new(sample_rate, channels),
+    };
+    gate.update_timing(sample_rate);
+    gate
+}
+
+/// Filter that attenuates the signal to near silence while it is below a threshold.
+///
+/// The gate looks at the loudest sample of each frame, so all channels open and close
+/// together. Once the signal drops below the threshold the gate stays open for the hold time
+/// and then closes over the release time. Choose a hold time longer than half a period of the
+/// lowest frequency you want to pass, otherwise the gate closes at the zero crossings.
+#[derive(Clone, Debug)]
+pub struct NoiseGate<I> {
+    input: I,
+    threshold: Float,
+    attack: Duration,
+    hold: Duration,
+    release: Duration,
+    attack_coeff: Float,
+    release_coeff: Float,
+    hold_frames: u64,
+    hold_remaining: u64,
+    gain: Float,
+    frame_peak: Sample,
+    current_channel: u16,
+    channels: u16,
+    span: SpanTracker,
+}
+
+impl<I> NoiseGate<I>
+where
+    I: Source,
+{
+    /// Returns the threshold in decibels below which the gate closes.
+    #[inline]
+    pub fn threshold_db(&self) -> Float {
+        math::linear_to_db(self.threshold)
+    }
+
+    /// Sets the threshold in decibels below which the gate closes.
+    #[inline]
+    pub fn set_threshold_db(&mut self, threshold_db: Float) {
+        self.threshold = math::db_to_linear(threshold_db);
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    fn update_timing(&mut self, sample_rate: SampleRate) {
+        self.attack_coeff = duration_to_coefficient(self.attack, sample_rate);
+        self.release_coeff = duration_to_coefficient(self.release, sample_rate);
+        self.hold_frames = (self.hold.as_secs_f64() * sample_rate.get() as f64).round() as u64;
+    }
+
+    /// Moves the gain towards open or closed based on the peak of the frame that just ended.
+    fn end_frame(&mut self) {
+        let open = if self.frame_peak >= self.threshold {
+            self.hold_remaining = self.hold_frames;
+            true
+        } else if self.hold_remaining > 0 {
+            self.hold_remaining -= 1;
+            true
+        } else {
+            false
+        };
+        self.frame_peak = 0.0;
+
+        let (target, coeff) = if open {
+            (1.0, self.attack_coeff)
+        } else {
+            (CLOSED_GAIN, self.release_coeff)
+        };
+        self.gain = target + coeff * (self.gain - target);
+    }
+}
+
+impl<I> Iterator for NoiseGate<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+
+        let input_span_len = self.input.current_span_len();
+        let current_sample_rate = self.input.sample_rate();
+        let current_channels = self.input.channels();
+
+        let detection = self
+            .span
+            .advance(input_span_len, current_sample_rate, current_channels);
+        if detection.at_span_boundary && detection.parameters_changed {
+            self.update_timing(current_sample_rate);
+            self.channels = current_channels.get();
+            self.current_channel = 0;
+            self.frame_peak = 0.0;
+        }
+
+        self.frame_peak = self.frame_peak.max(sample.abs());
+        let output = sample * self.gain;
+
+        self.current_channel += 1;
+        if self.current_channel >= self.channels {
+            self.current_channel = 0;
+            self.end_frame();
+        }
+
+        Some(output)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for NoiseGate<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for NoiseGate<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.span.seek(pos, &self.input);
+        self.update_timing(self.input.sample_rate());
+        self.channels = self.input.channels().get();
+        self.current_channel = 0;
+        self.frame_peak = 0.0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use crate::source::SineWave;
+
+    fn rms(samples: &[Sample]) -> Float {
+        let sum: Float = samples.iter().map(|s| s * s).sum();
+        (sum / samples.len() as Float).sqrt()
+    }
+
+    #[test]
+    fn attenuates_quiet_gap() {
+        // One second of tone, one second of quiet hum (-60 dB) and one second of tone, in stereo.
+        let tone = |amplitude: Float| {
+            SineWave::new(440.0)
+                .take(48000)
+                .flat_map(move |s| [s * amplitude; 2])
+        };
+        let samples: Vec<Sample> = tone(0.5).chain(tone(0.001)).chain(tone(0.5)).collect();
+        let source = SamplesBuffer::new(nz!(2), nz!(48000), samples.clone());
+
+        let gated: Vec<Sample> = source
+            .noise_gate(
+                -40.0,
+                Duration::from_millis(1),
+                Duration::from_millis(20),
+                Duration::from_millis(20),
+            )
+            .collect();
+        let second = 2 * 48000;
+
+        let tone_in = rms(&samples[..second]);
+        let tone_out = rms(&gated[..second]);
+        assert!(
+            (tone_out / tone_in - 1.0).abs() < 0.01,
+            "tone was attenuated"
+        );
+
+        // Leave time for hold and release before measuring the gap.
+        let gap = &gated[second + second / 5..2 * second];
+        assert!(rms(gap) < 1e-6, "gap was not attenuated: {}", rms(gap));
+
+        // The gate opens again for the second tone.
+        let tone_out = rms(&gated[2 * second + second / 10..]);
+        assert!(
+            (tone_out / tone_in - 1.0).abs() < 0.01,
+            "gate did not reopen"
+        );
+
+        // Channels are gated together.
+        assert!(gated.chunks(2).all(|frame| frame[0] == frame[1]));
+    }
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use super::{SeekError, UniformSourceIterator};
+use crate::buffer::SamplesBuffer;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Float, Sample, Source};
+
+/// Error returned by [`Source::normalize`] for a source that never ends.
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+#[error("an endless source can not be normalized")]
+pub struct NormalizeError;
+
+/// Internal function that builds a `Normalize` object.
+pub(crate) fn normalize<I>(input: I, target_peak: Float) -> Result<Normalize, NormalizeError>
+where
+    I: Source,
+{
+    // Endless sources such as the signal generators report a lower bound of `usize::MAX`.
+    if input.size_hint().0 == usize::MAX {
+        return Err(NormalizeError);
+    }
+
+    let channels = input.channels();
+    let sample_rate = input.sample_rate();
+    let mut samples: Vec<Sample> =
+        UniformSourceIterator::new(input, channels, sample_rate).collect();
+    let peak = samples.iter().fold(0.0, |peak: Float, s| peak.max(s.abs()));
+    let gain = if peak > 0.0 { target_peak / peak } else { 1.0 };
+    for sample in &mut samples {
+        *sample *= gain;
+    }
+
+    Ok(Normalize {
+        buffer: SamplesBuffer::new(channels, sample_rate, samples),
+        gain,
+    })
+}
+
+/// Source that plays a whole sound scaled so that its loudest sample has a given level.
+///
+/// The sound is read into memory completely when the source is built, see
+/// [`Source::normalize`].
+#[derive(Clone, Debug)]
+pub struct Normalize {
+    buffer: SamplesBuffer,
+    gain: Float,
+}
+
+impl Normalize {
+    /// Returns the factor every sample was multiplied with.
+    ///
+    /// This is `1.0` for a silent sound.
+    #[inline]
+    pub fn gain(&self) -> Float {
+        self.gain
+    }
+}
+
+impl Iterator for Normalize {
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        self.buffer.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.buffer.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Normalize {}
+
+impl Source for Normalize {
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.buffer.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.buffer.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.buffer.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.buffer.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.buffer.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.buffer.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::nz;
+    use crate::source::SineWave;
+
+    #[test]
+    fn scales_peak_to_target() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.25, -0.5, 0.1, 0.0]);
+        let normalized = source.normalize(1.0).unwrap();
+        assert_eq!(normalized.gain(), 2.0);
+        assert_eq!(normalized.channels(), nz!(2));
+
+        let samples: Vec<Sample> = normalized.collect();
+        assert_eq!(samples, [0.5, -1.0, 0.2, 0.0]);
+        let peak = samples.iter().fold(0.0, |peak: Float, s| peak.max(s.abs()));
+        assert_eq!(peak, 1.0);
+    }
+
+    #[test]
+    fn silence_is_unchanged() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![0.0; 4]);
+        let normalized = source.normalize(1.0).unwrap();
+        assert_eq!(normalized.gain(), 1.0);
+    }
+
+    #[test]
+    fn rejects_endless_source() {
+        assert_eq!(
+            SineWave::new(440.0).normalize(1.0).err(),
+            Some(NormalizeError)
+        );
+        let finite = SineWave::new(440.0).take_duration(Duration::from_millis(10));
+        assert!(finite.normalize(1.0).is_ok());
+    }
+}
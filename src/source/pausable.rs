@@ -2,7 +2,8 @@ use std::time::Duration;
 
 use super::SeekError;
 use crate::common::{ChannelCount, SampleRate};
-use crate::Source;
+use crate::math::NANOS_PER_SEC;
+use crate::{Float, Source};
 
 /// Builds a `Pausable` object.
 pub fn pausable<I>(source: I, paused: bool) -> Pausable<I>
@@ -18,6 +19,7 @@ where
         input: source,
         paused_channels,
         remaining_paused_samples: 0,
+        fade_in: None,
     }
 }
 
@@ -32,6 +34,14 @@ pub struct Pausable<I> {
     input: I,
     paused_channels: Option<ChannelCount>,
     remaining_paused_samples: u16,
+    fade_in: Option<FadeIn>,
+}
+
+/// Progress of the fade in after resuming with [`Pausable::resume_with_fade`].
+#[derive(Clone, Copy, Debug)]
+struct FadeIn {
+    total_frames: u64,
+    samples_done: u64,
 }
 
 impl<I> Pausable<I>
@@ -44,12 +54,36 @@ where
     #[inline]
     pub fn set_paused(&mut self, paused: bool) {
         match (self.paused_channels, paused) {
-            (None, true) => self.paused_channels = Some(self.input.channels()),
+            (None, true) => {
+                self.paused_channels = Some(self.input.channels());
+                self.fade_in = None;
+            }
             (Some(_), false) => self.paused_channels = None,
             _ => (),
         }
     }
 
+    /// Resumes playback and ramps the volume up from silence to full over `duration`.
+    ///
+    /// This avoids the click that can occur when playback resumes in the middle of a waveform.
+    /// Has no effect if the source is not paused.
+    #[inline]
+    pub fn resume_with_fade(&mut self, duration: Duration) {
+        if self.paused_channels.is_none() {
+            return;
+        }
+        self.set_paused(false);
+
+        let total_frames = (duration.as_nanos() * self.input.sample_rate().get() as u128
+            / NANOS_PER_SEC as u128) as u64;
+        if total_frames > 0 {
+            self.fade_in = Some(FadeIn {
+                total_frames,
+                samples_done: 0,
+            });
+        }
+    }
+
     /// Indicates if the data source is in a paused state.
     #[inline]
     pub fn is_paused(&self) -> bool {
@@ -93,7 +127,18 @@ where
             return Some(0.0);
         }
 
-        self.input.next()
+        let sample = self.input.next()?;
+        let Some(fade_in) = self.fade_in.as_mut() else {
+            return Some(sample);
+        };
+
+        let frame = fade_in.samples_done / self.input.channels().get() as u64;
+        if frame >= fade_in.total_frames {
+            self.fade_in = None;
+            return Some(sample);
+        }
+        fade_in.samples_done += 1;
+        Some(sample * (frame as Float / fade_in.total_frames as Float))
     }
 
     #[inline]
@@ -130,6 +175,11 @@ where
         self.input.total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.input.try_seek(pos)
@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds a `PeekableSource` object.
+pub(crate) fn peekable<I>(input: I) -> PeekableSource<I>
+where
+    I: Source,
+{
+    let channels = input.channels();
+    let sample_rate = input.sample_rate();
+    PeekableSource {
+        input,
+        peeked: None,
+        span_len: None,
+        channels,
+        sample_rate,
+    }
+}
+
+/// Source that can look at its next sample without consuming it, see
+/// [`peek`](PeekableSource::peek).
+///
+/// The metadata describes the next sample returned, also while it is peeked and the inner
+/// source has already moved past it.
+#[derive(Clone, Debug)]
+pub struct PeekableSource<I> {
+    input: I,
+    // The peeked sample, `Some(None)` if the inner source ended.
+    peeked: Option<Option<Sample>>,
+    // What the inner source reported before the peeked sample was read.
+    span_len: Option<usize>,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+}
+
+impl<I> PeekableSource<I>
+where
+    I: Source,
+{
+    /// Returns the next sample without consuming it, or `None` if the source ended.
+    #[inline]
+    pub fn peek(&mut self) -> Option<&Sample> {
+        if self.peeked.is_none() {
+            self.span_len = self.input.current_span_len();
+            self.channels = self.input.channels();
+            self.sample_rate = self.input.sample_rate();
+            self.peeked = Some(self.input.next());
+        }
+        self.peeked.as_ref().and_then(Option::as_ref)
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    ///
+    /// A peeked sample is still returned first.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source. A peeked sample is lost.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for PeekableSource<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.input.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let peeked = match self.peeked {
+            Some(Some(_)) => 1,
+            Some(None) => return (0, Some(0)),
+            None => 0,
+        };
+        let (lower, upper) = self.input.size_hint();
+        (
+            lower.saturating_add(peeked),
+            upper.and_then(|upper| upper.checked_add(peeked)),
+        )
+    }
+}
+
+impl<I> ExactSizeIterator for PeekableSource<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for PeekableSource<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        match self.peeked {
+            Some(_) => self.span_len,
+            None => self.input.current_span_len(),
+        }
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        match self.peeked {
+            Some(_) => self.channels,
+            None => self.input.channels(),
+        }
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        match self.peeked {
+            Some(_) => self.sample_rate,
+            None => self.input.sample_rate(),
+        }
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.peeked = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use crate::source::from_iter;
+
+    #[test]
+    fn peek_then_consume() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![0.25, -0.5, 0.75]);
+        let mut source = source.peekable_source();
+        assert_eq!(source.peek(), Some(&0.25));
+        assert_eq!(source.peek(), Some(&0.25));
+        assert_eq!(source.size_hint(), (3, Some(3)));
+        assert_eq!(source.next(), Some(0.25));
+
+        assert_eq!(source.peek(), Some(&-0.5));
+        assert_eq!(source.collect::<Vec<_>>(), [-0.5, 0.75]);
+    }
+
+    #[test]
+    fn peeking_keeps_metadata_of_next_sample() {
+        let spans = [
+            SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5]),
+            SamplesBuffer::new(nz!(2), nz!(2000), vec![0.25, 0.25]),
+        ];
+        let mut source = from_iter(spans).peekable_source();
+        assert_eq!(source.peek(), Some(&0.5));
+        assert_eq!(source.current_span_len(), Some(1));
+        assert_eq!(source.channels(), nz!(1));
+        assert_eq!(source.sample_rate(), nz!(1000));
+
+        assert_eq!(source.next(), Some(0.5));
+        assert_eq!(source.collect::<Vec<_>>(), [0.25, 0.25]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use super::{SeekError, SpanTracker};
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Float, Sample, Source};
+
+/// Length of the grain window used to re-read the delay line.
+const WINDOW_DURATION: Duration = Duration::from_millis(50);
+
+/// Internal function that builds a `PitchShift` object.
+pub fn pitch_shift<I>(input: I, semitones: f32) -> PitchShift<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let channels = input.channels();
+
+    PitchShift {
+        input,
+        semitones,
+        ratio: semitones_to_ratio(semitones),
+        delay_line: DelayLine::new(sample_rate, channels),
+        span: SpanTracker::new(sample_rate, channels),
+    }
+}
+
+/// Filter that changes the pitch of the source without changing its duration.
+///
+/// The input is written into a short delay line per channel which is read back by two taps
+/// moving at the pitch ratio. The taps are half a window apart and crossfaded so that one is
+/// always silent when it wraps around. Every input sample produces exactly one output sample,
+/// so the duration and span layout of the source are unchanged.
+#[derive(Clone, Debug)]
+pub struct PitchShift<I> {
+    input: I,
+    semitones: f32,
+    ratio: Float,
+    delay_line: DelayLine,
+    span: SpanTracker,
+}
+
+impl<I> PitchShift<I>
+where
+    I: Source,
+{
+    /// Returns the pitch shift in semitones.
+    #[inline]
+    pub fn semitones(&self) -> f32 {
+        self.semitones
+    }
+
+    /// Modifies the pitch shift in semitones. Positive values raise the pitch, negative values
+    /// lower it.
+    #[inline]
+    pub fn set_semitones(&mut self, semitones: f32) {
+        self.semitones = semitones;
+        self.ratio = semitones_to_ratio(semitones);
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for PitchShift<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+
+        let input_span_len = self.input.current_span_len();
+        let current_sample_rate = self.input.sample_rate();
+        let current_channels = self.input.channels();
+
+        let detection = self
+            .span
+            .advance(input_span_len, current_sample_rate, current_channels);
+        if detection.at_span_boundary && detection.parameters_changed {
+            self.delay_line = DelayLine::new(current_sample_rate, current_channels);
+        }
+
+        Some(self.delay_line.process(sample, self.ratio))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for PitchShift<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for PitchShift<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.span.seek(pos, &self.input);
+        self.delay_line = DelayLine::new(self.input.sample_rate(), self.input.channels());
+
+        Ok(())
+    }
+}
+
+#[inline]
+fn semitones_to_ratio(semitones: f32) -> Float {
+    Float::powf(2.0, semitones as Float / 12.0)
+}
+
+/// Interleaved ring buffer holding the most recent window of frames.
+#[derive(Clone, Debug)]
+struct DelayLine {
+    buffer: Vec<Sample>,
+    channels: usize,
+    window: usize,
+    // Frame that is currently being written.
+    write_frame: usize,
+    // Channel of the next sample within the current frame.
+    current_channel: usize,
+    // Position of the first tap within the window, in `[0.0, 1.0)`.
+    phase: Float,
+}
+
+impl DelayLine {
+    fn new(sample_rate: SampleRate, channels: ChannelCount) -> Self {
+        let channels = channels.get() as usize;
+        let window = ((sample_rate.get() as u128 * WINDOW_DURATION.as_millis()) / 1000) as usize;
+        let window = window.max(4);
+
+        Self {
+            // One extra frame so a tap at the full window length can still interpolate.
+            buffer: vec![0.0; (window + 2) * channels],
+            channels,
+            window,
+            write_frame: 0,
+            current_channel: 0,
+            phase: 0.0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: Sample, ratio: Float) -> Sample {
+        let frames = self.buffer.len() / self.channels;
+        let channel = self.current_channel;
+        self.buffer[self.write_frame * self.channels + channel] = sample;
+
+        let second_phase = (self.phase + 0.5).fract();
+        let first = self.tap(self.phase, channel, frames);
+        let second = self.tap(second_phase, channel, frames);
+        let output = first * triangle(self.phase) + second * triangle(second_phase);
+
+        self.current_channel += 1;
+        if self.current_channel == self.channels {
+            self.current_channel = 0;
+            self.write_frame = (self.write_frame + 1) % frames;
+            // The delay shrinks when raising the pitch and grows when lowering it.
+            self.phase = (self.phase + (1.0 - ratio) / self.window as Float).rem_euclid(1.0);
+        }
+
+        output
+    }
+
+    /// Reads a channel `phase * window` frames behind the write position.
+    #[inline]
+    fn tap(&self, phase: Float, channel: usize, frames: usize) -> Sample {
+        let delay = phase * self.window as Float;
+        let whole = delay.floor();
+        let frac = delay - whole;
+        let whole = whole as usize;
+
+        let newer = (self.write_frame + frames - whole) % frames;
+        let older = (newer + frames - 1) % frames;
+        let newer = self.buffer[newer * self.channels + channel];
+        let older = self.buffer[older * self.channels + channel];
+
+        newer + (older - newer) * frac
+    }
+}
+
+/// Crossfade gain of a tap. Zero when the tap wraps around, one half a window later.
+#[inline]
+fn triangle(phase: Float) -> Float {
+    1.0 - (2.0 * phase - 1.0).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SineWave;
+
+    fn positive_zero_crossings(samples: &[Sample]) -> usize {
+        samples
+            .windows(2)
+            .filter(|pair| pair[0] <= 0.0 && pair[1] > 0.0)
+            .count()
+    }
+
+    #[test]
+    fn octave_up_doubles_frequency() {
+        let source = SineWave::new(440.0).take_duration(Duration::from_secs(2));
+        let sample_rate = source.sample_rate().get() as usize;
+        let expected_len = source.clone().count();
+
+        let shifted: Vec<Sample> = source.pitch_shift(12.0).collect();
+        assert_eq!(shifted.len(), expected_len);
+
+        // Skip the first second so the delay line is filled.
+        let crossings = positive_zero_crossings(&shifted[sample_rate..]);
+        assert!(
+            (860..=900).contains(&crossings),
+            "expected a fundamental near 880Hz, got {crossings}Hz"
+        );
+    }
+
+    #[test]
+    fn duration_unchanged() {
+        let source = SineWave::new(440.0).take_duration(Duration::from_millis(1500));
+        let duration = source.total_duration();
+        assert_eq!(source.pitch_shift(-5.0).total_duration(), duration);
+    }
+}
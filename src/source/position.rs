@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use super::{SeekError, SpanTracker};
 use crate::common::{ChannelCount, Float, SampleRate};
-use crate::math::{duration_from_secs, duration_to_float};
+use crate::math::{duration_from_secs, duration_to_float, frame_to_duration};
 use crate::Source;
 
 /// Internal function that builds a `TrackPosition` object. See trait docs for
@@ -138,6 +138,11 @@ where
         self.input.total_duration()
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.input.try_seek(pos)?;
@@ -145,6 +150,15 @@ where
         self.span.seek(pos, &self.input);
         Ok(())
     }
+
+    #[inline]
+    fn try_seek_frame(&mut self, frame: u64) -> Result<(), SeekError> {
+        self.input.try_seek_frame(frame)?;
+        let pos = frame_to_duration(frame, self.input.sample_rate());
+        self.offset_duration = duration_to_float(pos);
+        self.span.seek(pos, &self.input);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
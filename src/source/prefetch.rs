@@ -0,0 +1,470 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::NANOS_PER_SEC;
+use crate::{Sample, Source};
+
+/// Duration of audio sent from the background thread in one go.
+const CHUNK_DURATION: Duration = Duration::from_millis(10);
+
+/// Internal function that builds a `Prefetch` object.
+pub fn prefetch<I>(input: I, ahead: Duration) -> Prefetch
+where
+    I: Source + Send + 'static,
+{
+    let channels = input.channels();
+    let sample_rate = input.sample_rate();
+    let total_duration = input.total_duration();
+    let seekable = input.is_seekable();
+
+    let chunks = ahead.as_nanos().div_ceil(CHUNK_DURATION.as_nanos()).max(1) as usize;
+    let (message_tx, message_rx) = mpsc::sync_channel(chunks);
+    let (seek_tx, seek_rx) = mpsc::channel();
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let window = Window {
+        ahead,
+        chunks,
+        ready: ready_tx,
+    };
+
+    thread::Builder::new()
+        .name("rodio prefetch".to_owned())
+        .spawn(move || produce(input, message_tx, seek_rx, window))
+        .expect("failed to spawn prefetch thread");
+
+    let mut prefetch = Prefetch {
+        current: Chunk {
+            channels,
+            sample_rate,
+            samples: Vec::new(),
+            spans: Vec::new(),
+        },
+        position: 0,
+        ended: false,
+        total_duration,
+        seekable,
+        backlog: VecDeque::new(),
+        messages: message_rx,
+        seeks: seek_tx,
+    };
+    // Wait for the whole window here so reading it does not block. Fails only if the
+    // background thread stopped, then reading ends the source.
+    let _ = ready_rx.recv();
+    prefetch.receive_chunk();
+    prefetch
+}
+
+/// Source that decodes its input ahead of time on a background thread.
+///
+/// The background thread keeps up to the requested prefetch window of audio buffered. Reading
+/// from this source takes samples from that buffer, so slow decoding of the input does not stall
+/// the thread that plays the audio as long as it catches up on average. Memory use is bounded by
+/// the prefetch window.
+///
+/// The whole prefetch window, or the whole input if it is shorter, is decoded while this source
+/// is created.
+#[derive(Debug)]
+pub struct Prefetch {
+    current: Chunk,
+    position: usize,
+    ended: bool,
+    total_duration: Option<Duration>,
+    seekable: bool,
+    // Messages received while waiting for a seek that failed.
+    backlog: VecDeque<Message>,
+    messages: Receiver<Message>,
+    seeks: Sender<Duration>,
+}
+
+#[derive(Debug)]
+struct Chunk {
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+    samples: Vec<Sample>,
+    /// Positions in `samples` at which the input reported how many samples are left in its
+    /// current span, with that number. The first one is at the start of the chunk.
+    spans: Vec<(usize, Option<usize>)>,
+}
+
+#[derive(Debug)]
+enum Message {
+    Chunk(Chunk),
+    Seeked(Result<(), SeekError>),
+    End,
+}
+
+impl Prefetch {
+    /// Replaces the current chunk with the next one. Blocks if none is buffered yet.
+    fn receive_chunk(&mut self) {
+        loop {
+            let message = match self.backlog.pop_front() {
+                Some(message) => Ok(message),
+                None => self.messages.recv(),
+            };
+            match message {
+                Ok(Message::Chunk(chunk)) => {
+                    self.current = chunk;
+                    self.position = 0;
+                    return;
+                }
+                // Stale answer to a seek that was abandoned.
+                Ok(Message::Seeked(_)) => continue,
+                Ok(Message::End) | Err(_) => {
+                    self.current.samples.clear();
+                    self.position = 0;
+                    self.ended = true;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Prefetch {
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        loop {
+            if let Some(sample) = self.current.samples.get(self.position) {
+                self.position += 1;
+                return Some(*sample);
+            }
+            if self.ended {
+                return None;
+            }
+            self.receive_chunk();
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.current.samples.len() - self.position;
+        if self.ended {
+            (remaining, Some(remaining))
+        } else {
+            (remaining, None)
+        }
+    }
+}
+
+impl Source for Prefetch {
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        if self.ended && self.position >= self.current.samples.len() {
+            return Some(0);
+        }
+        let (start, len) = self
+            .current
+            .spans
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= self.position)?;
+        len.map(|len| len.saturating_sub(self.position - start))
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.current.channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.current.sample_rate
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.total_duration
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.seekable
+    }
+
+    /// Blocks until the background thread has performed the seek. Everything that was
+    /// prefetched before a successful seek is discarded.
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        if self.seeks.send(pos).is_err() {
+            return Err(thread_stopped());
+        }
+
+        let mut skipped = VecDeque::new();
+        loop {
+            match self.messages.recv() {
+                Ok(Message::Seeked(Ok(()))) => {
+                    self.backlog.clear();
+                    self.ended = false;
+                    self.current.samples.clear();
+                    self.position = 0;
+                    self.receive_chunk();
+                    return Ok(());
+                }
+                Ok(Message::Seeked(Err(err))) => {
+                    // The input did not move, keep playing what was already prefetched.
+                    self.backlog.append(&mut skipped);
+                    return Err(err);
+                }
+                Ok(message) => skipped.push_back(message),
+                Err(_) => return Err(thread_stopped()),
+            }
+        }
+    }
+}
+
+fn thread_stopped() -> SeekError {
+    SeekError::Other(Arc::new(std::io::Error::other(
+        "prefetch thread is no longer running",
+    )))
+}
+
+/// Tells the constructor of a `Prefetch` once its window is buffered.
+struct Window {
+    ahead: Duration,
+    /// Number of chunks that fit in the channel.
+    chunks: usize,
+    ready: Sender<()>,
+}
+
+/// Runs on the background thread until the `Prefetch` is dropped.
+fn produce<I>(
+    mut input: I,
+    messages: SyncSender<Message>,
+    seeks: Receiver<Duration>,
+    window: Window,
+) where
+    I: Source,
+{
+    let mut ready = Some(window.ready);
+    let mut buffered = Duration::ZERO;
+    let mut sent = 0;
+    let mut span_left = None;
+    let mut ended = false;
+    loop {
+        let seek = if ended {
+            // Nothing to prefetch, wait for a seek that might make the input play again.
+            match seeks.recv() {
+                Ok(pos) => Some(pos),
+                Err(_) => return,
+            }
+        } else {
+            seeks.try_recv().ok()
+        };
+
+        if let Some(pos) = seek {
+            let result = input.try_seek(pos);
+            ended = ended && result.is_err();
+            span_left = None;
+            if messages.send(Message::Seeked(result)).is_err() {
+                return;
+            }
+            continue;
+        }
+
+        let message = match read_chunk(&mut input, &mut span_left) {
+            Some(chunk) => {
+                let frames = chunk.samples.len() / chunk.channels.get() as usize;
+                buffered += Duration::from_secs(frames as u64) / chunk.sample_rate.get();
+                sent += 1;
+                Message::Chunk(chunk)
+            }
+            None => {
+                ended = true;
+                Message::End
+            }
+        };
+        if messages.send(message).is_err() {
+            return;
+        }
+        // Stop at a full channel too, chunks cut short by a span change hold less audio.
+        if ended || buffered >= window.ahead || sent >= window.chunks {
+            if let Some(ready) = ready.take() {
+                let _ = ready.send(());
+            }
+        }
+    }
+}
+
+/// Span length reported by the input, `None` if it is unknown or the input has ended.
+fn known_span_len<I>(input: &I) -> Option<usize>
+where
+    I: Source,
+{
+    input.current_span_len().filter(|&len| len > 0)
+}
+
+/// Reads up to `CHUNK_DURATION` of whole frames that share the same channel count and sample
+/// rate. Returns `None` once the input is exhausted.
+///
+/// `span_left` counts down the samples left in the current span of the input across chunks.
+fn read_chunk<I>(input: &mut I, span_left: &mut Option<usize>) -> Option<Chunk>
+where
+    I: Source,
+{
+    if span_left.is_none() {
+        *span_left = known_span_len(input);
+    }
+    let mut spans = vec![(0, *span_left)];
+
+    let channels = input.channels();
+    let sample_rate = input.sample_rate();
+    let frames = (CHUNK_DURATION.as_nanos() * sample_rate.get() as u128 / NANOS_PER_SEC as u128)
+        .max(1) as usize;
+
+    let mut samples = Vec::with_capacity(frames * channels.get() as usize);
+    for _ in 0..frames {
+        if input.channels() != channels || input.sample_rate() != sample_rate {
+            break;
+        }
+        let frame_start = samples.len();
+        for _ in 0..channels.get() {
+            match input.next() {
+                Some(sample) => samples.push(sample),
+                None => {
+                    samples.truncate(frame_start);
+                    return (!samples.is_empty()).then_some(Chunk {
+                        channels,
+                        sample_rate,
+                        samples,
+                        spans,
+                    });
+                }
+            }
+            match span_left {
+                Some(left) if *left > 1 => *left -= 1,
+                // Also asks again while unknown, some inputs only know after the first sample.
+                _ => {
+                    let len = known_span_len(input);
+                    if len.is_some() || span_left.is_some() {
+                        spans.push((samples.len(), len));
+                    }
+                    *span_left = len;
+                }
+            }
+        }
+    }
+
+    (!samples.is_empty()).then_some(Chunk {
+        channels,
+        sample_rate,
+        samples,
+        spans,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    #[test]
+    fn plays_everything() {
+        let samples: Vec<Sample> = (0..10_000).map(|i| i as Sample).collect();
+        let source = SamplesBuffer::new(nz!(2), nz!(44100), samples.clone());
+
+        let prefetched = source.buffered_prefetch(Duration::from_millis(50));
+        assert_eq!(prefetched.channels(), nz!(2));
+        assert_eq!(prefetched.collect::<Vec<_>>(), samples);
+    }
+
+    #[test]
+    fn shorter_than_window() {
+        let source = SamplesBuffer::new(nz!(1), nz!(44100), vec![1.0, 2.0, 3.0]);
+
+        let prefetched = source.buffered_prefetch(Duration::from_secs(10));
+        assert_eq!(prefetched.collect::<Vec<_>>(), vec![1.0, 2.0, 3.0]);
+    }
+
+    /// Takes 2ms to produce each sample, and counts them.
+    struct SlowSource {
+        produced: Arc<AtomicUsize>,
+        len: usize,
+    }
+
+    impl Iterator for SlowSource {
+        type Item = Sample;
+
+        fn next(&mut self) -> Option<Sample> {
+            let produced = self.produced.load(Ordering::Relaxed);
+            if produced == self.len {
+                return None;
+            }
+            thread::sleep(Duration::from_millis(2));
+            self.produced.store(produced + 1, Ordering::Relaxed);
+            Some(produced as Sample)
+        }
+    }
+
+    impl Source for SlowSource {
+        fn current_span_len(&self) -> Option<usize> {
+            None
+        }
+
+        fn channels(&self) -> ChannelCount {
+            nz!(1)
+        }
+
+        fn sample_rate(&self) -> SampleRate {
+            nz!(1000)
+        }
+
+        fn total_duration(&self) -> Option<Duration> {
+            None
+        }
+    }
+
+    #[test]
+    fn window_is_buffered_when_created() {
+        let produced = Arc::new(AtomicUsize::new(0));
+        let source = SlowSource {
+            produced: Arc::clone(&produced),
+            len: 150,
+        };
+
+        let prefetched = source.buffered_prefetch(Duration::from_millis(100));
+        assert!(produced.load(Ordering::Relaxed) >= 100);
+
+        // Producing the window takes 200ms, reading it must not wait for that.
+        let start = Instant::now();
+        let window: Vec<_> = prefetched.take(100).collect();
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(window, (0..100).map(|i| i as Sample).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn span_len_counts_down_to_end() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0, 2.0, 3.0, 4.0]);
+
+        let mut prefetched = source.buffered_prefetch(Duration::from_millis(50));
+        assert_eq!(prefetched.current_span_len(), Some(4));
+        prefetched.next();
+        assert_eq!(prefetched.current_span_len(), Some(3));
+        assert_eq!(prefetched.by_ref().count(), 3);
+        assert_eq!(prefetched.current_span_len(), Some(0));
+    }
+
+    #[test]
+    fn seek_discards_prefetched() {
+        let samples: Vec<Sample> = (0..1000).map(|i| i as Sample).collect();
+        let source = SamplesBuffer::new(nz!(1), nz!(100), samples);
+
+        let mut prefetched = source.buffered_prefetch(Duration::from_secs(1));
+        assert_eq!(prefetched.next(), Some(0.0));
+        prefetched.try_seek(Duration::from_secs(5)).unwrap();
+        assert_eq!(prefetched.next(), Some(500.0));
+
+        // Seeking back after the end restarts playback.
+        assert_eq!(prefetched.by_ref().count(), 499);
+        prefetched.try_seek(Duration::ZERO).unwrap();
+        assert_eq!(prefetched.next(), Some(0.0));
+    }
+}
@@ -1,9 +1,12 @@
+use std::sync::atomic::{AtomicU16, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::source::buffered::Buffered;
 
 use super::SeekError;
 use crate::common::{ChannelCount, SampleRate};
+use crate::math::NANOS_PER_SEC;
 use crate::Source;
 
 /// Internal function that builds a `Repeat` object.
@@ -12,9 +15,12 @@ where
     I: Source,
 {
     let input = input.buffered();
+    let progress = RepeatProgress::new(&input);
     Repeat {
         inner: input.clone(),
         next: input,
+        progress,
+        samples: 0,
     }
 }
 
@@ -25,6 +31,107 @@ where
 {
     inner: Buffered<I>,
     next: Buffered<I>,
+    progress: RepeatProgress,
+    // Samples played since the current repetition started.
+    samples: u64,
+}
+
+impl<I> Repeat<I>
+where
+    I: Source,
+{
+    /// Returns a handle to follow which repetition is playing and where, for example to keep
+    /// visuals in sync with a looping backing track.
+    ///
+    /// The handle can be read from another thread while the source plays.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rodio::buffer::SamplesBuffer;
+    /// use rodio::{nz, Source};
+    ///
+    /// let source = SamplesBuffer::new(nz!(1), nz!(4), vec![0.0; 4]).repeat_infinite();
+    /// let progress = source.progress();
+    /// source.take(6).for_each(drop);
+    /// assert_eq!(progress.iterations_completed(), 1);
+    /// assert_eq!(progress.position_in_source().as_millis(), 500);
+    /// ```
+    #[inline]
+    pub fn progress(&self) -> RepeatProgress {
+        self.progress.clone()
+    }
+
+    fn set_samples(&mut self, samples: u64) {
+        self.samples = samples;
+        self.progress
+            .shared
+            .samples
+            .store(samples, Ordering::Relaxed);
+    }
+}
+
+/// Handle to the progress of a [`Repeat`] source.
+///
+/// Updated with every sample the source plays, so it is exact to the sample. Can be cloned
+/// and sent to other threads.
+#[derive(Clone, Debug)]
+pub struct RepeatProgress {
+    shared: Arc<Progress>,
+}
+
+#[derive(Debug)]
+struct Progress {
+    iterations: AtomicU64,
+    samples: AtomicU64,
+    // Format of the repeated source, used to turn samples into a position.
+    channels: AtomicU16,
+    sample_rate: AtomicU32,
+}
+
+impl RepeatProgress {
+    fn new(input: &impl Source) -> Self {
+        Self {
+            shared: Arc::new(Progress {
+                iterations: AtomicU64::new(0),
+                samples: AtomicU64::new(0),
+                channels: AtomicU16::new(input.channels().get()),
+                sample_rate: AtomicU32::new(input.sample_rate().get()),
+            }),
+        }
+    }
+
+    /// Returns how many times the source played to its end and started again.
+    #[inline]
+    pub fn iterations_completed(&self) -> u64 {
+        self.shared.iterations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the position within the repetition that is playing.
+    ///
+    /// This is computed from the samples played so far using the channel count and sample rate
+    /// at the start of the source.
+    pub fn position_in_source(&self) -> Duration {
+        let shared = &self.shared;
+        let samples = shared.samples.load(Ordering::Relaxed);
+        let rate = shared.sample_rate.load(Ordering::Relaxed) as u64;
+        let frames = samples / shared.channels.load(Ordering::Relaxed) as u64;
+        Duration::from_secs(frames / rate)
+            + Duration::from_nanos(frames % rate * NANOS_PER_SEC / rate)
+    }
+
+    /// Returns a handle with its own counters, starting from the current values.
+    fn detach(&self) -> Self {
+        let shared = &self.shared;
+        Self {
+            shared: Arc::new(Progress {
+                iterations: AtomicU64::new(shared.iterations.load(Ordering::Relaxed)),
+                samples: AtomicU64::new(shared.samples.load(Ordering::Relaxed)),
+                channels: AtomicU16::new(shared.channels.load(Ordering::Relaxed)),
+                sample_rate: AtomicU32::new(shared.sample_rate.load(Ordering::Relaxed)),
+            }),
+        }
+    }
 }
 
 impl<I> Iterator for Repeat<I>
@@ -36,11 +143,18 @@ where
     #[inline]
     fn next(&mut self) -> Option<<I as Iterator>::Item> {
         if let Some(value) = self.inner.next() {
+            self.set_samples(self.samples + 1);
             return Some(value);
         }
 
         self.inner = self.next.clone();
-        self.inner.next()
+        self.progress
+            .shared
+            .iterations
+            .fetch_add(1, Ordering::Relaxed);
+        let value = self.inner.next();
+        self.set_samples(value.is_some() as u64);
+        value
     }
 
     #[inline]
@@ -86,9 +200,17 @@ where
         None
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.inner.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
-        self.inner.try_seek(pos)
+        self.inner.try_seek(pos)?;
+        let frames = (pos.as_secs_f64() * self.inner.sample_rate().get() as f64) as u64;
+        self.set_samples(frames * self.inner.channels().get() as u64);
+        Ok(())
     }
 }
 
@@ -96,11 +218,203 @@ impl<I> Clone for Repeat<I>
 where
     I: Source,
 {
+    /// The clone has its own [`RepeatProgress`], starting from the progress of this source.
     #[inline]
     fn clone(&self) -> Repeat<I> {
         Repeat {
             inner: self.inner.clone(),
             next: self.next.clone(),
+            progress: self.progress.detach(),
+            samples: self.samples,
+        }
+    }
+}
+
+/// Internal function that builds a `RepeatFrom` object.
+pub fn repeat_from<I>(input: I, loop_start: Duration) -> RepeatFrom<I>
+where
+    I: Source,
+{
+    RepeatFrom {
+        input,
+        loop_start,
+        ended: false,
+    }
+}
+
+/// A source that plays the given source once and then keeps repeating it from a position.
+///
+/// Instead of buffering the input, this seeks the input back to the loop start every time it
+/// ends. The source ends if seeking fails or if nothing is left to play after the loop start.
+#[derive(Clone, Debug)]
+pub struct RepeatFrom<I> {
+    input: I,
+    loop_start: Duration,
+    ended: bool,
+}
+
+impl<I> RepeatFrom<I> {
+    /// Returns the position every repetition after the first one starts at.
+    #[inline]
+    pub fn loop_start(&self) -> Duration {
+        self.loop_start
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for RepeatFrom<I>
+where
+    I: Source,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.ended {
+            return None;
+        }
+        if let Some(value) = self.input.next() {
+            return Some(value);
+        }
+
+        if self.input.try_seek(self.loop_start).is_err() {
+            self.ended = true;
+            return None;
+        }
+        // Nothing after the loop start, stop instead of seeking forever.
+        let value = self.input.next();
+        self.ended = value.is_none();
+        value
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.ended {
+            (0, Some(0))
+        } else {
+            // Infinite unless seeking fails.
+            (self.input.size_hint().0, None)
+        }
+    }
+}
+
+impl<I> Source for RepeatFrom<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        if self.ended {
+            return Some(0);
+        }
+        match self.input.current_span_len() {
+            // About to loop back, seeking within the input does not change its parameters.
+            Some(0) => None,
+            len => len,
+        }
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.ended = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use crate::Sample;
+
+    #[test]
+    fn counts_iterations() {
+        let mut source =
+            SamplesBuffer::new(nz!(1), nz!(4), vec![1.0, 2.0, 3.0, 4.0]).repeat_infinite();
+        let progress = source.progress();
+        assert_eq!(progress.iterations_completed(), 0);
+        assert_eq!(progress.position_in_source(), Duration::ZERO);
+
+        let mut iterations = Vec::new();
+        let mut positions = Vec::new();
+        for _ in 0..13 {
+            source.next();
+            iterations.push(progress.iterations_completed());
+            positions.push(progress.position_in_source().as_millis());
         }
+        assert_eq!(iterations, [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3]);
+        assert_eq!(
+            positions,
+            [250, 500, 750, 1000, 250, 500, 750, 1000, 250, 500, 750, 1000, 250]
+        );
+
+        // A clone counts on its own.
+        let clone = source.clone();
+        source.next();
+        assert_eq!(clone.progress().position_in_source().as_millis(), 250);
+        assert_eq!(progress.position_in_source().as_millis(), 500);
+    }
+
+    #[test]
+    fn repeat_from_skips_intro_after_first_pass() {
+        let samples: Vec<Sample> = (0..10).map(|i| i as Sample).collect();
+        let source = SamplesBuffer::new(nz!(1), nz!(10), samples);
+
+        let played: Vec<Sample> = source
+            .repeat_from(Duration::from_millis(300))
+            .take(24)
+            .collect();
+
+        let mut expected: Vec<Sample> = (0..10).map(|i| i as Sample).collect();
+        expected.extend((3..10).map(|i| i as Sample));
+        expected.extend((3..10).map(|i| i as Sample));
+        assert_eq!(played, expected);
+    }
+
+    #[test]
+    fn repeat_from_past_the_end_stops() {
+        let source = SamplesBuffer::new(nz!(1), nz!(10), vec![1.0, 2.0]);
+
+        let played: Vec<Sample> = source.repeat_from(Duration::from_secs(5)).collect();
+        assert_eq!(played, vec![1.0, 2.0]);
     }
 }
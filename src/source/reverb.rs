@@ -0,0 +1,443 @@
+use std::time::Duration;
+
+use super::{SeekError, SpanTracker};
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Float, Sample, Source};
+
+// Tunings of the original Freeverb, in samples at 44.1kHz.
+const COMB_TUNINGS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+const ALL_PASS_TUNINGS: [usize; 4] = [556, 441, 341, 225];
+/// Extra delay added per channel so the channels do not ring in unison.
+const STEREO_SPREAD: usize = 23;
+const TUNING_SAMPLE_RATE: usize = 44100;
+
+const FIXED_GAIN: Float = 0.015;
+const SCALE_WET: Float = 3.0;
+const SCALE_DAMPING: Float = 0.4;
+const SCALE_ROOM: Float = 0.28;
+const OFFSET_ROOM: Float = 0.7;
+const ALL_PASS_FEEDBACK: Float = 0.5;
+
+/// Level below which the tail is considered to have died out, about -80dB.
+const TAIL_THRESHOLD: Float = 1e-4;
+
+/// Internal function that builds a `Reverb` object.
+pub fn reverb<I>(input: I, room_size: f32, damping: f32, wet: f32) -> Reverb<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let channels = input.channels();
+
+    Reverb {
+        input,
+        room_size,
+        damping,
+        wet,
+        tank: Tank::new(sample_rate, channels),
+        span: SpanTracker::new(sample_rate, channels),
+        in_tail: false,
+        quiet_samples: 0,
+        ended: false,
+    }
+}
+
+/// Filter that adds a Freeverb-style reverb to the source.
+///
+/// Every channel runs through eight parallel low-pass feedback comb filters followed by four
+/// all-pass filters in series. Each channel uses slightly longer delays than the previous one,
+/// which decorrelates the channels and widens the stereo image.
+///
+/// The reverb tail keeps playing after the input has ended, until it has decayed to silence.
+/// Because the length of the tail is not known in advance, `total_duration` returns `None`.
+#[derive(Clone, Debug)]
+pub struct Reverb<I> {
+    input: I,
+    room_size: f32,
+    damping: f32,
+    wet: f32,
+    tank: Tank,
+    span: SpanTracker,
+    // Whether the input has ended and only the tail is playing.
+    in_tail: bool,
+    // Number of consecutive tail samples below `TAIL_THRESHOLD`.
+    quiet_samples: usize,
+    ended: bool,
+}
+
+impl<I> Reverb<I>
+where
+    I: Source,
+{
+    /// Returns the room size, between `0.0` and `1.0`.
+    #[inline]
+    pub fn room_size(&self) -> f32 {
+        self.room_size
+    }
+
+    /// Modifies the room size. Larger rooms have a longer tail. The value is clamped to
+    /// `0.0..=1.0`.
+    #[inline]
+    pub fn set_room_size(&mut self, room_size: f32) {
+        self.room_size = room_size;
+    }
+
+    /// Returns how much the high frequencies of the tail are damped, between `0.0` and `1.0`.
+    #[inline]
+    pub fn damping(&self) -> f32 {
+        self.damping
+    }
+
+    /// Modifies how much the high frequencies of the tail are damped. The value is clamped to
+    /// `0.0..=1.0`.
+    #[inline]
+    pub fn set_damping(&mut self, damping: f32) {
+        self.damping = damping;
+    }
+
+    /// Returns the proportion of reverb in the output, between `0.0` and `1.0`.
+    #[inline]
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Modifies the proportion of reverb in the output. `0.0` only plays the dry source and
+    /// `1.0` only the reverb. The value is clamped to `0.0..=1.0`.
+    #[inline]
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    #[inline]
+    fn process(&mut self, sample: Sample) -> Sample {
+        let room_size = (self.room_size as Float).clamp(0.0, 1.0);
+        let damping = (self.damping as Float).clamp(0.0, 1.0);
+        let wet = (self.wet as Float).clamp(0.0, 1.0);
+
+        let feedback = room_size * SCALE_ROOM + OFFSET_ROOM;
+        let verb = self
+            .tank
+            .process(sample * FIXED_GAIN, feedback, damping * SCALE_DAMPING);
+        sample * (1.0 - wet) + verb * wet * SCALE_WET
+    }
+}
+
+impl<I> Iterator for Reverb<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.ended {
+            return None;
+        }
+
+        if !self.in_tail {
+            if let Some(sample) = self.input.next() {
+                let input_span_len = self.input.current_span_len();
+                let current_sample_rate = self.input.sample_rate();
+                let current_channels = self.input.channels();
+
+                let detection =
+                    self.span
+                        .advance(input_span_len, current_sample_rate, current_channels);
+                if detection.at_span_boundary && detection.parameters_changed {
+                    self.tank = Tank::new(current_sample_rate, current_channels);
+                }
+
+                return Some(self.process(sample));
+            }
+            self.in_tail = true;
+        }
+
+        // Only stop at a frame boundary, once the tail stayed quiet for a full comb loop.
+        if self.tank.current_channel == 0 && self.quiet_samples >= self.tank.tail_len() {
+            self.ended = true;
+            return None;
+        }
+
+        let sample = self.process(0.0);
+        if sample.abs() < TAIL_THRESHOLD {
+            self.quiet_samples += 1;
+        } else {
+            self.quiet_samples = 0;
+        }
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.ended {
+            (0, Some(0))
+        } else if self.in_tail {
+            (0, None)
+        } else {
+            (self.input.size_hint().0, None)
+        }
+    }
+}
+
+impl<I> Source for Reverb<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        if self.ended {
+            return Some(0);
+        }
+        if self.in_tail {
+            return None;
+        }
+        match self.input.current_span_len() {
+            // The input is about to end, the tail continues with the same parameters.
+            Some(0) => None,
+            len => len,
+        }
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        if self.in_tail {
+            self.tank.channels
+        } else {
+            self.input.channels()
+        }
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        if self.in_tail {
+            self.tank.sample_rate
+        } else {
+            self.input.sample_rate()
+        }
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.span.seek(pos, &self.input);
+        self.tank = Tank::new(self.input.sample_rate(), self.input.channels());
+        self.in_tail = false;
+        self.quiet_samples = 0;
+        self.ended = false;
+
+        Ok(())
+    }
+}
+
+/// Comb and all-pass filters for every channel.
+#[derive(Clone, Debug)]
+struct Tank {
+    networks: Vec<Network>,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+    current_channel: usize,
+}
+
+impl Tank {
+    fn new(sample_rate: SampleRate, channels: ChannelCount) -> Self {
+        let networks = (0..channels.get() as usize)
+            .map(|channel| Network::new(sample_rate, channel * STEREO_SPREAD))
+            .collect();
+
+        Self {
+            networks,
+            channels,
+            sample_rate,
+            current_channel: 0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: Sample, feedback: Float, damping: Float) -> Sample {
+        let output = self.networks[self.current_channel].process(sample, feedback, damping);
+        self.current_channel = (self.current_channel + 1) % self.networks.len();
+        output
+    }
+
+    /// Number of samples after which everything in the comb filters has been played back.
+    fn tail_len(&self) -> usize {
+        self.networks
+            .iter()
+            .flat_map(|network| network.combs.iter())
+            .map(|comb| comb.buffer.len())
+            .max()
+            .unwrap_or(0)
+            * self.networks.len()
+    }
+}
+
+/// Reverb network of a single channel.
+#[derive(Clone, Debug)]
+struct Network {
+    combs: Vec<Comb>,
+    all_passes: Vec<AllPass>,
+}
+
+impl Network {
+    fn new(sample_rate: SampleRate, spread: usize) -> Self {
+        let scale = |tuning: usize| {
+            ((tuning + spread) * sample_rate.get() as usize / TUNING_SAMPLE_RATE).max(1)
+        };
+
+        Self {
+            combs: COMB_TUNINGS
+                .iter()
+                .map(|&tuning| Comb::new(scale(tuning)))
+                .collect(),
+            all_passes: ALL_PASS_TUNINGS
+                .iter()
+                .map(|&tuning| AllPass::new(scale(tuning)))
+                .collect(),
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: Sample, feedback: Float, damping: Float) -> Sample {
+        let combed = self
+            .combs
+            .iter_mut()
+            .map(|comb| comb.process(sample, feedback, damping))
+            .sum();
+        self.all_passes
+            .iter_mut()
+            .fold(combed, |sample, all_pass| all_pass.process(sample))
+    }
+}
+
+/// Feedback comb filter with a one-pole low-pass in the feedback path.
+#[derive(Clone, Debug)]
+struct Comb {
+    buffer: Vec<Sample>,
+    index: usize,
+    filter_store: Sample,
+}
+
+impl Comb {
+    fn new(len: usize) -> Self {
+        Self {
+            buffer: vec![0.0; len],
+            index: 0,
+            filter_store: 0.0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: Sample, feedback: Float, damping: Float) -> Sample {
+        let output = self.buffer[self.index];
+        self.filter_store = output * (1.0 - damping) + self.filter_store * damping;
+        self.buffer[self.index] = sample + self.filter_store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+}
+
+/// Schroeder all-pass filter.
+#[derive(Clone, Debug)]
+struct AllPass {
+    buffer: Vec<Sample>,
+    index: usize,
+}
+
+impl AllPass {
+    fn new(len: usize) -> Self {
+        Self {
+            buffer: vec![0.0; len],
+            index: 0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: Sample) -> Sample {
+        let delayed = self.buffer[self.index];
+        self.buffer[self.index] = sample + delayed * ALL_PASS_FEEDBACK;
+        self.index = (self.index + 1) % self.buffer.len();
+        delayed - sample
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    fn rms(samples: &[Sample]) -> Float {
+        (samples.iter().map(|s| s * s).sum::<Float>() / samples.len() as Float).sqrt()
+    }
+
+    #[test]
+    fn impulse_response_decays() {
+        let mut samples = vec![0.0; 4410];
+        samples[0] = 1.0;
+        let source = SamplesBuffer::new(nz!(1), nz!(44100), samples);
+
+        let response: Vec<Sample> = source.schroeder_reverb(0.5, 0.5, 1.0).collect();
+        assert!(response.len() > 4410, "the tail continues past the source");
+        assert!(response.len() < 44100 * 10, "the tail eventually ends");
+
+        // Compare the loudness of consecutive 100ms blocks once the reverb has built up.
+        let blocks: Vec<Float> = response[4410..].chunks_exact(4410).map(rms).collect();
+        assert!(blocks.len() > 3);
+        for pair in blocks.windows(2) {
+            assert!(
+                pair[1] < pair[0],
+                "tail does not decay smoothly: {blocks:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn channels_are_decorrelated() {
+        let mut samples = vec![0.0; 2000];
+        samples[0] = 1.0;
+        samples[1] = 1.0;
+        let source = SamplesBuffer::new(nz!(2), nz!(44100), samples);
+
+        let response: Vec<Sample> = source.schroeder_reverb(0.5, 0.5, 1.0).collect();
+        assert_eq!(response.len() % 2, 0);
+        assert!(response
+            .chunks_exact(2)
+            .any(|frame| (frame[0] - frame[1]).abs() > 1e-3));
+    }
+
+    #[test]
+    fn dry_only() {
+        let source = SamplesBuffer::new(nz!(1), nz!(44100), vec![0.5, -0.5, 0.25]);
+
+        let output: Vec<Sample> = source.schroeder_reverb(0.5, 0.5, 0.0).take(3).collect();
+        assert_eq!(output, vec![0.5, -0.5, 0.25]);
+    }
+}
@@ -64,6 +64,11 @@ impl Source for SawtoothWave {
         None
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.test_saw.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, duration: Duration) -> Result<(), SeekError> {
         self.test_saw.try_seek(duration)
@@ -161,6 +161,11 @@ impl Source for SignalGenerator {
         None
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn try_seek(&mut self, duration: Duration) -> Result<(), SeekError> {
         let seek = duration_to_float(duration) * (self.sample_rate.get() as Float) / self.period;
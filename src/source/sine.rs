@@ -64,6 +64,11 @@ impl Source for SineWave {
         None
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.test_sine.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, duration: Duration) -> Result<(), SeekError> {
         self.test_sine.try_seek(duration)
@@ -18,7 +18,7 @@ where
 }
 
 /// Skips specified `duration` of the given `input` source from it's current position.
-fn do_skip_duration<I>(input: &mut I, mut duration: Duration)
+pub(crate) fn do_skip_duration<I>(input: &mut I, mut duration: Duration)
 where
     I: Source,
 {
@@ -157,6 +157,11 @@ where
             .map(|val| val.saturating_sub(self.skipped_duration))
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         self.input.try_seek(pos + self.skipped_duration)
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Float, Source};
+
+/// Internal function that builds a `SoftClip` object.
+pub(crate) fn soft_clip<I>(input: I, drive: Float) -> SoftClip<I>
+where
+    I: Source,
+{
+    SoftClip { input, drive }
+}
+
+/// Filter that applies a `tanh` saturation curve to the source.
+///
+/// Each sample is multiplied by `drive` and passed through `tanh`, so the output never exceeds
+/// ±1.0. Low level signals are left nearly untouched while peaks are rounded off smoothly, adding
+/// odd harmonics instead of the harsh distortion of hard clipping. Unlike a limiter the curve is
+/// applied instantaneously and has no attack or release.
+#[derive(Clone, Debug)]
+pub struct SoftClip<I> {
+    input: I,
+    drive: Float,
+}
+
+impl<I> SoftClip<I> {
+    /// Returns the drive applied before the saturation curve.
+    #[inline]
+    pub fn drive(&self) -> Float {
+        self.drive
+    }
+
+    /// Modifies the drive applied before the saturation curve.
+    #[inline]
+    pub fn set_drive(&mut self, drive: Float) {
+        self.drive = drive;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for SoftClip<I>
+where
+    I: Source,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.input.next().map(|value| (value * self.drive).tanh())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for SoftClip<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for SoftClip<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::TAU;
+    use crate::source::{SineWave, SquareWave};
+    use crate::Sample;
+
+    /// Magnitude of a single frequency bin, normalized to the amplitude of a sine.
+    fn amplitude_at(samples: &[Sample], freq: Float, sample_rate: Float) -> Float {
+        let (re, im) = samples
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (n, &sample)| {
+                let angle = TAU * freq * n as Float / sample_rate;
+                (re + sample * angle.cos(), im - sample * angle.sin())
+            });
+        2.0 * (re * re + im * im).sqrt() / samples.len() as Float
+    }
+
+    #[test]
+    fn full_scale_square_stays_in_range() {
+        let clipped = SquareWave::new(100.0)
+            .soft_clip(4.0)
+            .take_duration(Duration::from_millis(100));
+
+        for sample in clipped {
+            assert!((-1.0..=1.0).contains(&sample), "{sample} out of range");
+        }
+    }
+
+    #[test]
+    fn low_level_is_nearly_transparent() {
+        let dry = SineWave::new(440.0).amplify(0.01);
+        let wet = dry.clone().soft_clip(1.0);
+
+        for (dry, wet) in dry.zip(wet).take(4800) {
+            assert!((dry - wet).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn adds_harmonics() {
+        // 50 whole periods of 1kHz at 48kHz, so all bins line up exactly.
+        let dry: Vec<Sample> = SineWave::new(1000.0).take(2400).collect();
+        let clipped: Vec<Sample> = SineWave::new(1000.0).soft_clip(2.0).take(2400).collect();
+
+        let dry_third = amplitude_at(&dry, 3000.0, 48000.0);
+        let wet_third = amplitude_at(&clipped, 3000.0, 48000.0);
+        assert!(dry_third < 1e-3, "pure sine has no third harmonic");
+        assert!(wet_third > 0.05, "saturation adds a third harmonic");
+    }
+
+    #[test]
+    fn band_limited_square_gains_harmonics() {
+        // A full-scale square with its harmonics filtered out, so the clipper has to add them
+        // back. Skips the settling of the filter, then takes 50 whole periods of 1kHz.
+        let square = || SquareWave::new(1000.0).low_pass(1000);
+        let dry: Vec<Sample> = square().skip(4800).take(2400).collect();
+        let clipped: Vec<Sample> = square().soft_clip(4.0).skip(4800).take(2400).collect();
+
+        for &sample in &clipped {
+            assert!((-1.0..=1.0).contains(&sample), "{sample} out of range");
+        }
+        let ratio = |samples: &[Sample]| {
+            amplitude_at(samples, 3000.0, 48000.0) / amplitude_at(samples, 1000.0, 48000.0)
+        };
+        assert!(
+            ratio(&clipped) > 2.0 * ratio(&dry),
+            "saturation adds a third harmonic"
+        );
+    }
+}
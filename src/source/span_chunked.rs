@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds a `SpanChunked` object.
+pub(crate) fn span_chunked<I>(input: I, span_len: usize) -> SpanChunked<I>
+where
+    I: Source,
+{
+    assert!(span_len > 0, "span length must be greater than zero");
+    let input_span_left = input.current_span_len();
+    let mut chunked = SpanChunked {
+        input,
+        span_len,
+        current_span_len: 0,
+        current_span_left: 0,
+        input_span_left,
+    };
+    chunked.start_span();
+    chunked
+}
+
+/// Source that splits another source into spans of a fixed length.
+///
+/// The samples pass through unchanged, only [`current_span_len`](Source::current_span_len)
+/// differs. A span is cut short where the inner source starts a new span, so the channel
+/// count and sample rate still only change at span boundaries, and at the end of the source if
+/// its length is known. Spans are rounded down to whole frames, but are at least one frame.
+#[derive(Clone, Debug)]
+pub struct SpanChunked<I> {
+    input: I,
+    span_len: usize,
+    // Length of the span that is being played, and the samples left in it.
+    current_span_len: usize,
+    current_span_left: usize,
+    // Samples left in the current span of the inner source, `None` if it has no end.
+    input_span_left: Option<usize>,
+}
+
+impl<I> SpanChunked<I>
+where
+    I: Source,
+{
+    /// Returns the length of the spans this source is split into.
+    #[inline]
+    pub fn span_len(&self) -> usize {
+        self.span_len
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    fn start_span(&mut self) {
+        let channels = self.input.channels().get() as usize;
+        let mut len = self.span_len - self.span_len % channels;
+        len = len.max(channels);
+        if let Some(input_span_left) = self.input_span_left {
+            len = len.min(input_span_left);
+        }
+        if let (lower, Some(upper)) = self.input.size_hint() {
+            if lower == upper {
+                len = len.min(lower);
+            }
+        }
+
+        self.current_span_len = len;
+        self.current_span_left = len;
+    }
+}
+
+impl<I> Iterator for SpanChunked<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+
+        if let Some(left) = &mut self.input_span_left {
+            *left = left.saturating_sub(1);
+            if *left == 0 {
+                self.input_span_left = self.input.current_span_len();
+            }
+        }
+        self.current_span_left = self.current_span_left.saturating_sub(1);
+        if self.current_span_left == 0 {
+            self.start_span();
+        }
+
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for SpanChunked<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for SpanChunked<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        if self.input.is_exhausted() {
+            return Some(0);
+        }
+        Some(self.current_span_len)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        // The position within the span of the inner source is only known at the start.
+        self.input_span_left = if pos.is_zero() {
+            self.input.current_span_len()
+        } else {
+            None
+        };
+        self.start_span();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    /// The span length reported before every sample, and once more at the end.
+    fn reported_span_lens(mut source: impl Source) -> Vec<usize> {
+        let mut lens = vec![source.current_span_len().unwrap()];
+        while source.next().is_some() {
+            lens.push(source.current_span_len().unwrap());
+        }
+        lens
+    }
+
+    #[test]
+    fn reports_fixed_span_lens() {
+        let samples: Vec<Sample> = (0..10).map(|i| i as Sample).collect();
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), samples.clone());
+
+        let chunked = source.with_span_len(4);
+        assert_eq!(
+            reported_span_lens(chunked.clone()),
+            [4, 4, 4, 4, 4, 4, 4, 4, 2, 2, 0]
+        );
+        assert_eq!(chunked.collect::<Vec<_>>(), samples);
+    }
+
+    #[test]
+    fn ends_spans_at_inner_span_boundaries() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.0; 10]).with_span_len(6);
+        let lens = reported_span_lens(source.with_span_len(4));
+        assert_eq!(lens, [4, 4, 4, 4, 2, 2, 4, 4, 4, 4, 0]);
+    }
+}
@@ -137,6 +137,11 @@ where
         self.input.total_duration().map(|d| d.div_f32(self.factor))
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         let pos_accounting_for_speedup = pos.mul_f32(self.factor);
@@ -64,6 +64,11 @@ impl Source for SquareWave {
         None
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.test_square.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, duration: Duration) -> Result<(), SeekError> {
         self.test_square.try_seek(duration)
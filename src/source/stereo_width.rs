@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, Float, SampleRate};
+use crate::{Sample, Source};
+
+/// Internal function that builds a `StereoWidth` object.
+pub(crate) fn stereo_width<I>(input: I, width: Float) -> StereoWidth<I>
+where
+    I: Source,
+{
+    StereoWidth {
+        input,
+        width,
+        right: None,
+    }
+}
+
+/// Filter that widens or narrows the stereo image.
+///
+/// Every frame is split into mid, the average of both channels, and side, half their
+/// difference. The side is multiplied with the width before both channels are rebuilt, so `0.0`
+/// plays mono, `1.0` the original and larger values exaggerate the difference between the
+/// channels. The mid, and with it everything panned to the center, stays unchanged.
+///
+/// Only stereo is processed, sources or spans with another channel count pass through
+/// unchanged.
+#[derive(Clone, Debug)]
+pub struct StereoWidth<I> {
+    input: I,
+    width: Float,
+    // Right sample of the current frame, computed together with the left one.
+    right: Option<Sample>,
+}
+
+impl<I> StereoWidth<I> {
+    /// Returns the width the side signal is multiplied with.
+    #[inline]
+    pub fn width(&self) -> Float {
+        self.width
+    }
+
+    /// Changes the width, starting with the next frame.
+    #[inline]
+    pub fn set_width(&mut self, width: Float) {
+        self.width = width;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for StereoWidth<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if let Some(right) = self.right.take() {
+            return Some(right);
+        }
+
+        let stereo = self.input.channels().get() == 2;
+        let left = self.input.next()?;
+        if !stereo {
+            return Some(left);
+        }
+        let Some(right) = self.input.next() else {
+            return Some(left);
+        };
+
+        let mid = (left + right) / 2.0;
+        let side = (left - right) / 2.0 * self.width;
+        self.right = Some(mid - side);
+        Some(mid + side)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.right.is_some() as usize;
+        let (lower, upper) = self.input.size_hint();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+impl<I> ExactSizeIterator for StereoWidth<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for StereoWidth<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.right = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn zero_width_is_mono() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![1.0, 0.0, 0.25, -0.75]);
+        let samples: Vec<Sample> = source.stereo_width(0.0).collect();
+        // Both channels play the mid of the frame.
+        assert_eq!(samples, [0.5, 0.5, -0.25, -0.25]);
+    }
+
+    #[test]
+    fn scales_side_and_keeps_mid() {
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), vec![0.5, 0.25]);
+        assert_eq!(
+            source.clone().stereo_width(1.0).collect::<Vec<_>>(),
+            [0.5, 0.25]
+        );
+        assert_eq!(source.stereo_width(2.0).collect::<Vec<_>>(), [0.625, 0.125]);
+    }
+
+    #[test]
+    fn mono_is_unchanged() {
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), vec![0.5, -0.25, 0.1]);
+        assert_eq!(
+            source.stereo_width(0.0).collect::<Vec<_>>(),
+            [0.5, -0.25, 0.1]
+        );
+    }
+}
@@ -96,6 +96,23 @@ where
     pub fn clear_filter(&mut self) {
         self.filter = None;
     }
+
+    /// Seeks to `pos` within the taken part, where the taken part starts at `offset` in the
+    /// inner source.
+    pub(crate) fn try_seek_from(
+        &mut self,
+        offset: Duration,
+        pos: Duration,
+    ) -> Result<(), SeekError> {
+        let result = self.input.try_seek(offset + pos);
+        if result.is_ok() {
+            // Recalculate remaining duration after seek
+            self.remaining_duration = self.requested_duration.saturating_sub(pos);
+            self.span.seek(offset + pos, &self.input);
+            self.samples_in_current_frame = 0;
+        }
+        result
+    }
 }
 
 impl<I> Iterator for TakeDuration<I>
@@ -224,16 +241,14 @@ where
         }
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
-        let result = self.input.try_seek(pos);
-        if result.is_ok() {
-            // Recalculate remaining duration after seek
-            self.remaining_duration = self.requested_duration.saturating_sub(pos);
-            self.span.seek(pos, &self.input);
-            self.samples_in_current_frame = 0;
-        }
-        result
+        self.try_seek_from(Duration::ZERO, pos)
     }
 }
 
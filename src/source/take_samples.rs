@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::NANOS_PER_SEC;
+use crate::{Sample, Source};
+
+/// Internal function that builds a `TakeSamples` object.
+pub(crate) fn take_samples<I>(input: I, frames: usize) -> TakeSamples<I>
+where
+    I: Source,
+{
+    TakeSamples {
+        input,
+        requested_frames: frames,
+        remaining_frames: frames,
+        frame_channels: 0,
+        samples_in_frame: 0,
+    }
+}
+
+/// A source that truncates the given source to a number of frames.
+///
+/// Every frame holds one sample per channel, so a stereo source yields twice as many samples as
+/// frames were taken. When the channel count changes, the frames after the change are counted
+/// with the new channel count.
+#[derive(Clone, Debug)]
+pub struct TakeSamples<I> {
+    input: I,
+    requested_frames: usize,
+    remaining_frames: usize,
+    // Channels of the frame being played, and how many of its samples were played.
+    frame_channels: usize,
+    samples_in_frame: usize,
+}
+
+impl<I> TakeSamples<I>
+where
+    I: Source,
+{
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    /// Samples left until the last frame is complete, counted with the current channel count.
+    fn remaining_samples(&self) -> usize {
+        if self.samples_in_frame > 0 {
+            self.remaining_frames.saturating_sub(1) * self.input.channels().get() as usize
+                + (self.frame_channels - self.samples_in_frame)
+        } else {
+            self.remaining_frames
+                .saturating_mul(self.input.channels().get() as usize)
+        }
+    }
+}
+
+impl<I> Iterator for TakeSamples<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if self.samples_in_frame == 0 {
+            if self.remaining_frames == 0 {
+                return None;
+            }
+            // The channel count can only change at the start of a frame.
+            self.frame_channels = self.input.channels().get() as usize;
+        }
+
+        let sample = self.input.next()?;
+        self.samples_in_frame += 1;
+        if self.samples_in_frame == self.frame_channels {
+            self.samples_in_frame = 0;
+            self.remaining_frames -= 1;
+        }
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining_samples();
+        let (lower, upper) = self.input.size_hint();
+        (
+            lower.min(remaining),
+            Some(upper.map_or(remaining, |upper| upper.min(remaining))),
+        )
+    }
+}
+
+impl<I> ExactSizeIterator for TakeSamples<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for TakeSamples<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        let remaining = self.remaining_samples();
+        if remaining == 0 {
+            return Some(0);
+        }
+        self.input
+            .current_span_len()
+            .filter(|len| *len < remaining)
+            .or(Some(remaining))
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        let rate = self.input.sample_rate().get() as u64;
+        let frames = self.requested_frames as u64;
+        let requested = Duration::from_secs(frames / rate)
+            + Duration::from_nanos(frames % rate * NANOS_PER_SEC / rate);
+        Some(match self.input.total_duration() {
+            Some(duration) => duration.min(requested),
+            None => requested,
+        })
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        let frames = (pos.as_secs_f64() * self.input.sample_rate().get() as f64) as usize;
+        self.remaining_frames = self.requested_frames.saturating_sub(frames);
+        self.samples_in_frame = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn takes_frames_not_samples() {
+        let samples: Vec<Sample> = (0..10).map(|i| i as Sample).collect();
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), samples).take_samples(3);
+        assert_eq!(source.size_hint(), (6, Some(6)));
+        assert_eq!(source.total_duration(), Some(Duration::from_millis(3)));
+        assert_eq!(source.collect::<Vec<_>>(), [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn counts_frames_across_channel_changes() {
+        let (tx, source) = crate::queue::queue(false);
+        tx.append(SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0; 2]));
+        tx.append(SamplesBuffer::new(nz!(2), nz!(1000), vec![2.0; 6]));
+
+        let taken: Vec<Sample> = source.take_samples(3).collect();
+        assert_eq!(taken, [1.0, 1.0, 2.0, 2.0]);
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// How far one output may fall behind the one furthest ahead before it skips samples.
+const MAX_LAG: Duration = Duration::from_secs(1);
+
+/// Internal function that builds a `Tee` object.
+pub(crate) fn tee<I>(input: I) -> Tee<I>
+where
+    I: Source,
+{
+    let mut shared = Shared {
+        input,
+        buffer: VecDeque::new(),
+        start: 0,
+        spans: VecDeque::new(),
+        input_span_left: None,
+        ended: false,
+        positions: vec![Some(0)],
+        scratch: Vec::new(),
+    };
+    shared.start_span();
+
+    Tee {
+        shared: Arc::new(Mutex::new(shared)),
+        id: 0,
+    }
+}
+
+/// Source that can be cloned into several outputs which all play the same samples.
+///
+/// Every clone is an output that reads the inner source independently, the inner source is
+/// only pulled once. Samples are kept until every output has played them, so outputs may be
+/// consumed by different threads at slightly different speeds, for example by two
+/// [`MixerDeviceSink`](crate::MixerDeviceSink)s playing the same mix on two devices.
+///
+/// This is best effort: devices do not share a clock, so they slowly drift apart. An output
+/// that falls more than a second behind the one furthest ahead skips ahead to stay within a
+/// second of it. A clone starts at the position of the output it was cloned from. Seeking is
+/// not supported, since it would move all outputs at once.
+#[derive(Debug)]
+pub struct Tee<I>
+where
+    I: Source,
+{
+    shared: Arc<Mutex<Shared<I>>>,
+    id: usize,
+}
+
+#[derive(Debug)]
+struct Shared<I> {
+    input: I,
+    // Samples that not every output has played yet, `start` is the index of the first one.
+    buffer: VecDeque<Sample>,
+    start: u64,
+    // Spans of the input that overlap with `buffer`, the first one may start before it.
+    spans: VecDeque<SpanStart>,
+    input_span_left: Option<usize>,
+    ended: bool,
+    // Position of every output, `None` for dropped outputs.
+    positions: Vec<Option<u64>>,
+    scratch: Vec<Sample>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SpanStart {
+    start: u64,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+    len: Option<usize>,
+}
+
+impl<I> Shared<I>
+where
+    I: Source,
+{
+    fn end(&self) -> u64 {
+        self.start + self.buffer.len() as u64
+    }
+
+    fn start_span(&mut self) {
+        let len = self.input.current_span_len();
+        if len == Some(0) {
+            self.ended = true;
+            return;
+        }
+        self.spans.push_back(SpanStart {
+            start: self.end(),
+            channels: self.input.channels(),
+            sample_rate: self.input.sample_rate(),
+            len,
+        });
+        self.input_span_left = len;
+    }
+
+    /// Reads up to `samples` more samples from the input into the buffer.
+    fn pull(&mut self, mut samples: usize) {
+        while samples > 0 && !self.ended {
+            let chunk = self
+                .input_span_left
+                .map_or(samples, |left| left.min(samples));
+            self.scratch.resize(chunk, 0.0);
+            let read = self.input.fill_buffer(&mut self.scratch);
+            self.buffer.extend(&self.scratch[..read]);
+            samples -= read;
+
+            if read < chunk {
+                self.ended = true;
+            } else if let Some(left) = &mut self.input_span_left {
+                *left -= read;
+                if *left == 0 {
+                    self.start_span();
+                }
+            }
+        }
+    }
+
+    /// The span the output at `position` plays, `None` if the input was empty.
+    fn span_at(&self, position: u64) -> Option<&SpanStart> {
+        self.spans
+            .iter()
+            .rev()
+            .find(|span| span.start <= position)
+            .or(self.spans.front())
+    }
+
+    /// Drops samples every output has played, and samples of outputs that fell too far behind.
+    fn trim(&mut self) {
+        let end = self.end();
+        let mut new_start = self
+            .positions
+            .iter()
+            .flatten()
+            .copied()
+            .min()
+            .unwrap_or(end);
+
+        if let Some(span) = self.spans.back() {
+            let channels = span.channels.get() as u64;
+            let max_lag = (MAX_LAG.as_secs_f64() * span.sample_rate.get() as f64) as u64 * channels;
+            if end - new_start > max_lag {
+                new_start = end - max_lag;
+                // Keep whole frames so lagging outputs stay on the right channel.
+                new_start -= (new_start - self.start) % channels;
+                for position in self.positions.iter_mut().flatten() {
+                    *position = (*position).max(new_start);
+                }
+            }
+        }
+
+        let drop = (new_start - self.start) as usize;
+        self.buffer.drain(..drop);
+        self.start = new_start;
+        while self.spans.len() > 1 && self.spans[1].start <= self.start {
+            self.spans.pop_front();
+        }
+    }
+}
+
+impl<I> Tee<I>
+where
+    I: Source,
+{
+    fn lock(&self) -> MutexGuard<'_, Shared<I>> {
+        self.shared
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn position(shared: &Shared<I>, id: usize) -> u64 {
+        shared.positions[id].expect("only dropped outputs have no position")
+    }
+}
+
+impl<I> Clone for Tee<I>
+where
+    I: Source,
+{
+    fn clone(&self) -> Self {
+        let mut shared = self.lock();
+        let position = shared.positions[self.id];
+        let id = match shared.positions.iter().position(Option::is_none) {
+            Some(free) => {
+                shared.positions[free] = position;
+                free
+            }
+            None => {
+                shared.positions.push(position);
+                shared.positions.len() - 1
+            }
+        };
+        drop(shared);
+
+        Tee {
+            shared: Arc::clone(&self.shared),
+            id,
+        }
+    }
+}
+
+impl<I> Drop for Tee<I>
+where
+    I: Source,
+{
+    fn drop(&mut self) {
+        let mut shared = self.lock();
+        shared.positions[self.id] = None;
+        shared.trim();
+    }
+}
+
+impl<I> Iterator for Tee<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let mut sample = [0.0];
+        (self.fill_buffer(&mut sample) == 1).then_some(sample[0])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let shared = self.lock();
+        let buffered = (shared.end() - Self::position(&shared, self.id)) as usize;
+        let (lower, upper) = shared.input.size_hint();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|upper| upper.checked_add(buffered)),
+        )
+    }
+}
+
+impl<I> Source for Tee<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        let shared = self.lock();
+        let position = Self::position(&shared, self.id);
+        if shared.ended && position == shared.end() {
+            return Some(0);
+        }
+        shared.span_at(position).and_then(|span| span.len)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        let shared = self.lock();
+        shared
+            .span_at(Self::position(&shared, self.id))
+            .map_or_else(|| shared.input.channels(), |span| span.channels)
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        let shared = self.lock();
+        shared
+            .span_at(Self::position(&shared, self.id))
+            .map_or_else(|| shared.input.sample_rate(), |span| span.sample_rate)
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.lock().input.total_duration()
+    }
+
+    fn fill_buffer(&mut self, out: &mut [Sample]) -> usize {
+        let mut shared = self.lock();
+        let position = Self::position(&shared, self.id);
+        let wanted_end = position + out.len() as u64;
+        if wanted_end > shared.end() {
+            let missing = (wanted_end - shared.end()) as usize;
+            shared.pull(missing);
+        }
+
+        let offset = (position - shared.start) as usize;
+        let available = (shared.buffer.len() - offset).min(out.len());
+        for (out, sample) in out
+            .iter_mut()
+            .zip(shared.buffer.range(offset..offset + available))
+        {
+            *out = *sample;
+        }
+
+        shared.positions[self.id] = Some(position + available as u64);
+        shared.trim();
+        available
+    }
+
+    /// Never seekable, the outputs share one source and can not each seek it.
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn try_seek(&mut self, _: Duration) -> Result<(), SeekError> {
+        Err(SeekError::NotSupported {
+            underlying_source: std::any::type_name::<Self>(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use crate::source::SineWave;
+
+    #[test]
+    fn outputs_play_same_samples() {
+        let samples: Vec<Sample> = (0..100).map(|i| i as Sample).collect();
+        let first = SamplesBuffer::new(nz!(2), nz!(1000), samples.clone()).tee();
+        let mut second = first.clone();
+
+        // Read at different speeds and in different block sizes.
+        let mut out = [0.0; 7];
+        assert_eq!(second.fill_buffer(&mut out), 7);
+        assert_eq!(out[..], samples[..7]);
+        let first: Vec<Sample> = first.collect();
+        assert_eq!(first, samples);
+        let rest: Vec<Sample> = second.collect();
+        assert_eq!(rest[..], samples[7..]);
+    }
+
+    #[test]
+    fn outputs_follow_span_changes() {
+        let (tx, source) = crate::queue::queue(false);
+        tx.append(SamplesBuffer::new(nz!(2), nz!(1000), vec![1.0; 4]));
+        tx.append(SamplesBuffer::new(nz!(1), nz!(2000), vec![2.0; 3]));
+        let mut first = source.tee();
+        let mut second = first.clone();
+
+        // The first output runs ahead into the second span.
+        first.by_ref().take(5).for_each(drop);
+        assert_eq!(first.channels(), nz!(1));
+        assert_eq!(second.channels(), nz!(2));
+        assert_eq!(second.current_span_len(), Some(4));
+
+        second.by_ref().take(4).for_each(drop);
+        assert_eq!(second.channels(), nz!(1));
+        assert_eq!(second.sample_rate(), nz!(2000));
+        assert_eq!(second.by_ref().count(), 3);
+        assert_eq!(second.current_span_len(), Some(0));
+    }
+
+    #[test]
+    fn lagging_output_skips_ahead() {
+        let first = SineWave::new(440.0).tee();
+        let second = first.clone();
+
+        // Two seconds ahead at 48kHz mono.
+        let ahead = first.take(96_000).count();
+        assert_eq!(ahead, 96_000);
+        let shared = second.lock();
+        assert_eq!(Tee::position(&shared, second.id), 48_000);
+        assert_eq!(shared.buffer.len(), 48_000);
+    }
+}
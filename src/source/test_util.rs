@@ -0,0 +1,135 @@
+//! Helpers to test sources and the DSP built on them.
+//!
+//! Rendering a source to a buffer and comparing it with the expected samples is the simplest
+//! way to test an adapter. These helpers do that deterministically, without an audio device.
+//!
+//! Enable the `test-util` feature to use them in your own tests, usually as a dev-dependency:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! rodio = { version = "*", features = ["test-util"] }
+//! ```
+
+use crate::common::{ChannelCount, SampleRate};
+use crate::{Sample, Source};
+
+/// Most samples [`collect`] reads before deciding the source does not end. This is almost three
+/// minutes of stereo at 48kHz.
+pub const MAX_COLLECTED_SAMPLES: usize = 1 << 24;
+
+/// Plays a source to the end and returns its samples with the channel count and sample rate
+/// it reported before the first sample.
+///
+/// # Panics
+///
+/// Panics if the source yields more than [`MAX_COLLECTED_SAMPLES`] samples, which guards
+/// against sources that never end. Use [`Source::take_duration`] to test those.
+///
+/// # Example
+///
+/// ```
+/// use rodio::buffer::SamplesBuffer;
+/// use rodio::source::test_util::collect;
+/// use rodio::source::Source;
+///
+/// let source = SamplesBuffer::new(rodio::nz!(1), rodio::nz!(44100), vec![0.5, 1.0]);
+/// let (samples, channels, sample_rate) = collect(source.amplify(0.5));
+/// assert_eq!(samples, [0.25, 0.5]);
+/// assert_eq!((channels.get(), sample_rate.get()), (1, 44100));
+/// ```
+#[track_caller]
+pub fn collect(source: impl Source) -> (Vec<Sample>, ChannelCount, SampleRate) {
+    collect_at_most(source, MAX_COLLECTED_SAMPLES)
+}
+
+#[track_caller]
+fn collect_at_most(
+    source: impl Source,
+    max_samples: usize,
+) -> (Vec<Sample>, ChannelCount, SampleRate) {
+    let channels = source.channels();
+    let sample_rate = source.sample_rate();
+    let samples: Vec<Sample> = source.take(max_samples + 1).collect();
+    assert!(
+        samples.len() <= max_samples,
+        "source did not end within {max_samples} samples"
+    );
+    (samples, channels, sample_rate)
+}
+
+/// Asserts that two buffers hold the same number of samples and that every sample differs by
+/// at most `tolerance`.
+///
+/// Filters often give slightly different results depending on the order of floating point
+/// operations, so comparing exactly is too strict for most tests. Pass a tolerance of zero to
+/// check for bit-exact output.
+///
+/// # Panics
+///
+/// Panics with the position of the first sample that differs too much, or if the lengths
+/// differ.
+///
+/// # Example
+///
+/// ```
+/// use rodio::source::test_util::assert_samples_eq;
+///
+/// assert_samples_eq(&[0.1 + 0.2, 1.0], &[0.3, 1.0], 1e-6);
+/// ```
+#[track_caller]
+pub fn assert_samples_eq(actual: &[Sample], expected: &[Sample], tolerance: Sample) {
+    assert_eq!(
+        actual.len(),
+        expected.len(),
+        "expected {} samples, got {}",
+        expected.len(),
+        actual.len()
+    );
+    if let Some((i, (actual, expected))) =
+        actual
+            .iter()
+            .zip(expected)
+            .enumerate()
+            .find(|(_, (actual, expected))| {
+                let difference = (*actual - *expected).abs();
+                difference.is_nan() || difference > tolerance
+            })
+    {
+        panic!("sample {i} is {actual}, expected {expected} within {tolerance}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+    use crate::source::SineWave;
+
+    #[test]
+    fn collects_whole_source() {
+        let source = SamplesBuffer::new(nz!(2), nz!(8000), vec![0.1, 0.2, 0.3, 0.4]);
+        let (samples, channels, sample_rate) = collect(source);
+        assert_eq!(samples, [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(channels, nz!(2));
+        assert_eq!(sample_rate, nz!(8000));
+    }
+
+    #[test]
+    #[should_panic(expected = "did not end")]
+    fn endless_source_panics() {
+        collect_at_most(SineWave::new(440.0), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "sample 1 is")]
+    fn reports_first_difference() {
+        assert_samples_eq(&[0.0, 0.5, 1.0], &[0.0, 0.4, 0.0], 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "is NaN")]
+    fn nan_is_never_equal() {
+        assert_samples_eq(&[Sample::NAN], &[0.0], 1.0);
+    }
+}
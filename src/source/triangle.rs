@@ -64,6 +64,11 @@ impl Source for TriangleWave {
         None
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.test_tri.is_seekable()
+    }
+
     #[inline]
     fn try_seek(&mut self, duration: Duration) -> Result<(), SeekError> {
         self.test_tri.try_seek(duration)
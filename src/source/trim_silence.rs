@@ -0,0 +1,300 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::NANOS_PER_SEC;
+use crate::{Float, Sample, Source};
+
+/// Run of silence after which [`TrimSilence`] ends the source by default.
+const DEFAULT_TRAILING_SILENCE: Duration = Duration::from_millis(500);
+
+/// Internal function that builds a `TrimSilence` object.
+pub fn trim_silence<I>(input: I, threshold: Float) -> TrimSilence<I>
+where
+    I: Source,
+{
+    TrimSilence {
+        input,
+        threshold,
+        trailing_silence: DEFAULT_TRAILING_SILENCE,
+        trimming_leading: true,
+        ended: false,
+        ready: VecDeque::new(),
+        held: VecDeque::new(),
+        held_frames: 0,
+    }
+}
+
+/// Filter that removes leading and trailing silence from the source.
+///
+/// A frame is considered silent when the absolute value of every sample in it is below the
+/// threshold. Silent frames at the start of the source are dropped. Silent frames later on are
+/// held back until either a louder frame arrives, in which case they are played after all, or
+/// the run of silence grows longer than the trailing silence duration, in which case the source
+/// ends. This way quiet fades and short pauses are kept while dead air at the end is cut.
+///
+/// At most the trailing silence duration worth of frames is buffered.
+#[derive(Clone, Debug)]
+pub struct TrimSilence<I> {
+    input: I,
+    threshold: Float,
+    trailing_silence: Duration,
+    trimming_leading: bool,
+    ended: bool,
+    // Samples that are ready to be returned.
+    ready: VecDeque<Sample>,
+    // Silent frames that are held back until we know whether the silence ends.
+    held: VecDeque<Sample>,
+    held_frames: u64,
+}
+
+impl<I> TrimSilence<I>
+where
+    I: Source,
+{
+    /// Sets how long a run of silence must last before the source ends. Defaults to 500ms.
+    #[inline]
+    pub fn with_trailing_silence(mut self, duration: Duration) -> Self {
+        self.trailing_silence = duration;
+        self
+    }
+
+    /// Returns the amplitude below which samples are considered silent.
+    #[inline]
+    pub fn threshold(&self) -> Float {
+        self.threshold
+    }
+
+    /// Modifies the amplitude below which samples are considered silent.
+    #[inline]
+    pub fn set_threshold(&mut self, threshold: Float) {
+        self.threshold = threshold;
+    }
+
+    /// Returns how long a run of silence must last before the source ends.
+    #[inline]
+    pub fn trailing_silence(&self) -> Duration {
+        self.trailing_silence
+    }
+
+    /// Modifies how long a run of silence must last before the source ends.
+    #[inline]
+    pub fn set_trailing_silence(&mut self, duration: Duration) {
+        self.trailing_silence = duration;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    /// Reads the next frame of the input into `ready` or `held`. Returns `false` once the
+    /// source should end.
+    fn read_frame(&mut self) -> bool {
+        let channels = self.input.channels().get() as usize;
+        let sample_rate = self.input.sample_rate();
+
+        let start = self.held.len();
+        let mut silent = true;
+        for _ in 0..channels {
+            let Some(sample) = self.input.next() else {
+                // Input ended, drop the held silence together with any incomplete frame.
+                self.held.clear();
+                return false;
+            };
+            silent &= sample.abs() < self.threshold;
+            self.held.push_back(sample);
+        }
+
+        if silent {
+            if self.trimming_leading {
+                self.held.truncate(start);
+                return true;
+            }
+
+            self.held_frames += 1;
+            if self.held_frames > self.max_held_frames(sample_rate) {
+                self.held.clear();
+                return false;
+            }
+        } else {
+            self.trimming_leading = false;
+            self.held_frames = 0;
+            // `ready` is always empty when reading, the held silence turned out to be a pause.
+            std::mem::swap(&mut self.ready, &mut self.held);
+        }
+
+        true
+    }
+
+    #[inline]
+    fn max_held_frames(&self, sample_rate: SampleRate) -> u64 {
+        (self.trailing_silence.as_nanos() * sample_rate.get() as u128 / NANOS_PER_SEC as u128)
+            as u64
+    }
+}
+
+impl<I> Iterator for TrimSilence<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        loop {
+            if let Some(sample) = self.ready.pop_front() {
+                return Some(sample);
+            }
+            if self.ended {
+                return None;
+            }
+            if !self.read_frame() {
+                self.ended = true;
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.ended {
+            return (self.ready.len(), Some(self.ready.len()));
+        }
+
+        let buffered = self.ready.len() + self.held.len();
+        let (_, max) = self.input.size_hint();
+        (
+            self.ready.len(),
+            max.and_then(|max| max.checked_add(buffered)),
+        )
+    }
+}
+
+impl<I> Source for TrimSilence<I>
+where
+    I: Source,
+{
+    /// Unknown until the source ends, the span length of the input does not account for the
+    /// silence that is dropped or held back.
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        if self.ended && self.ready.is_empty() {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns whether the inner source can seek.
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    /// Seeking to the start trims leading silence again, seeking anywhere else plays from that
+    /// position even if it is silent.
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.trimming_leading = pos == Duration::ZERO;
+        self.ended = false;
+        self.ready.clear();
+        self.held.clear();
+        self.held_frames = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+    use crate::math::nz;
+
+    #[test]
+    fn trims_both_ends() {
+        let mut samples = vec![0.0; 1000];
+        samples.extend([0.5, -0.5, 0.25, -0.25]);
+        samples.extend(vec![0.0; 1000]);
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), samples);
+
+        let trimmed: Vec<Sample> = source.trim_silence(0.01).collect();
+        assert_eq!(trimmed, vec![0.5, -0.5, 0.25, -0.25]);
+    }
+
+    #[test]
+    fn keeps_short_pauses_and_quiet_fades() {
+        let mut samples = vec![0.0; 10];
+        samples.extend([0.5, 0.005, 0.0, 0.002, 0.0, 0.5]);
+        samples.extend(vec![0.0; 10]);
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), samples);
+
+        let trimmed: Vec<Sample> = source.trim_silence(0.01).collect();
+        assert_eq!(trimmed, vec![0.5, 0.005, 0.0, 0.002, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn long_silence_ends_source() {
+        let mut samples = vec![0.5];
+        samples.extend(vec![0.0; 20]);
+        samples.push(0.5);
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), samples);
+
+        let trimmed: Vec<Sample> = source
+            .trim_silence(0.01)
+            .with_trailing_silence(Duration::from_millis(10))
+            .collect();
+        assert_eq!(trimmed, vec![0.5]);
+    }
+
+    #[test]
+    fn works_on_whole_frames() {
+        let samples = vec![0.0, 0.0, 0.0, 0.5, 0.5, 0.0, 0.0, 0.0];
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), samples);
+
+        let trimmed: Vec<Sample> = source.trim_silence(0.01).collect();
+        assert_eq!(trimmed, vec![0.0, 0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn span_len_is_unknown_until_end() {
+        let samples = vec![0.0, 0.0, 0.5, 0.0, 0.0];
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), samples);
+
+        let mut trimmed = source.trim_silence(0.01);
+        assert_eq!(trimmed.current_span_len(), None);
+        assert_eq!(trimmed.next(), Some(0.5));
+        assert_eq!(trimmed.current_span_len(), None);
+        assert_eq!(trimmed.next(), None);
+        assert_eq!(trimmed.current_span_len(), Some(0));
+    }
+}
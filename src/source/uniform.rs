@@ -3,7 +3,7 @@ use std::time::Duration;
 
 use super::SeekError;
 use crate::common::{ChannelCount, SampleRate};
-use crate::conversions::{ChannelCountConverter, SampleRateConverter};
+use crate::conversions::{ChannelCountConverter, DownmixMode, SampleRateConverter};
 use crate::Source;
 
 /// An iterator that reads from a `Source` and converts the samples to a
@@ -21,6 +21,7 @@ where
     target_channels: ChannelCount,
     target_sample_rate: SampleRate,
     total_duration: Option<Duration>,
+    downmix: DownmixMode,
 }
 
 impl<I> UniformSourceIterator<I>
@@ -43,14 +44,24 @@ where
             target_channels,
             target_sample_rate,
             total_duration,
+            downmix: DownmixMode::default(),
         }
     }
 
+    /// Sets how all channels are mixed together when converting to mono, by default
+    /// [`DownmixMode::Average`]. See [`ChannelCountConverter::with_downmix_mode`].
+    #[inline]
+    pub fn with_downmix_mode(mut self, mode: DownmixMode) -> Self {
+        self.downmix = mode;
+        self
+    }
+
     #[inline]
     fn bootstrap(
         input: I,
         target_channels: ChannelCount,
         target_sample_rate: SampleRate,
+        downmix: DownmixMode,
     ) -> ChannelCountConverter<SampleRateConverter<Take<I>>> {
         // Limit the span length to something reasonable
         let span_len = input.current_span_len().map(|x| x.min(32768));
@@ -64,7 +75,7 @@ where
         };
         let input =
             SampleRateConverter::new(input, from_sample_rate, target_sample_rate, from_channels);
-        ChannelCountConverter::new(input, from_channels, target_channels)
+        ChannelCountConverter::new(input, from_channels, target_channels).with_downmix_mode(downmix)
     }
 }
 
@@ -88,8 +99,12 @@ where
                 .expect("pending is Some when inner is None"),
         };
 
-        let mut input =
-            UniformSourceIterator::bootstrap(input, self.target_channels, self.target_sample_rate);
+        let mut input = UniformSourceIterator::bootstrap(
+            input,
+            self.target_channels,
+            self.target_sample_rate,
+            self.downmix,
+        );
 
         let value = input.next();
         self.inner = Some(input);
@@ -132,6 +147,17 @@ where
         self.total_duration
     }
 
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        if let Some(input) = self.inner.as_ref() {
+            input.inner().inner().inner().is_seekable()
+        } else if let Some(pending) = self.pending.as_ref() {
+            pending.is_seekable()
+        } else {
+            true
+        }
+    }
+
     #[inline]
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         if let Some(input) = self.inner.as_mut() {
@@ -151,6 +177,11 @@ struct Take<I> {
 }
 
 impl<I> Take<I> {
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.iter
+    }
+
     #[inline]
     pub fn inner_mut(&mut self) -> &mut I {
         &mut self.iter
@@ -0,0 +1,275 @@
+use std::time::Duration;
+
+use super::SeekError;
+use crate::common::{ChannelCount, Float, SampleRate};
+use crate::math::{duration_to_coefficient, nz, FRAC_1_SQRT_2, PI};
+use crate::{Sample, Source};
+
+/// How much later the surround channels play than the front channels.
+const SURROUND_DELAY: Duration = Duration::from_millis(12);
+
+/// Frequency below which the sum of both channels goes to the LFE channel.
+const LFE_CUTOFF: Float = 120.0;
+
+/// Internal function that builds an `UpmixSurround` object.
+pub(crate) fn upmix_surround<I>(input: I) -> UpmixSurround<I>
+where
+    I: Source,
+{
+    let input_channels = input.channels();
+    let sample_rate = input.sample_rate();
+    UpmixSurround {
+        input,
+        frame: [0.0; 6],
+        frame_len: 0,
+        frame_pos: 0,
+        span_len: None,
+        input_channels,
+        sample_rate,
+        delay: Vec::new(),
+        delay_pos: 0,
+        lfe: 0.0,
+        lfe_coeff: 0.0,
+        filters_rate: None,
+    }
+}
+
+/// Filter that upmixes stereo to 5.1 surround.
+///
+/// The channels are in the usual order of WAV and FLAC files: left, right, center, LFE, left
+/// surround and right surround. The front channels play the input unchanged. The center plays
+/// the sum of both input channels at -3 dB, and the LFE channel the same sum below 120 Hz. The
+/// surround channels play the difference between the input channels, which holds the ambience
+/// of most recordings, in opposite phase and delayed by a few milliseconds so it is not heard
+/// as coming from the back.
+///
+/// Only stereo is upmixed, sources or spans with another channel count pass through unchanged.
+#[derive(Clone, Debug)]
+pub struct UpmixSurround<I> {
+    input: I,
+    // The frame being played and what the input reported when it was read.
+    frame: [Sample; 6],
+    frame_len: usize,
+    frame_pos: usize,
+    span_len: Option<usize>,
+    input_channels: ChannelCount,
+    sample_rate: SampleRate,
+    // Ring buffer delaying the surround channels.
+    delay: Vec<Sample>,
+    delay_pos: usize,
+    // Low-pass filter state of the LFE channel.
+    lfe: Sample,
+    lfe_coeff: Float,
+    // Sample rate the delay and filter were set up for.
+    filters_rate: Option<SampleRate>,
+}
+
+impl<I> UpmixSurround<I>
+where
+    I: Source,
+{
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    #[inline]
+    fn in_frame(&self) -> bool {
+        self.frame_pos < self.frame_len
+    }
+
+    fn output_channels(input_channels: ChannelCount) -> ChannelCount {
+        if input_channels.get() == 2 {
+            nz!(6)
+        } else {
+            input_channels
+        }
+    }
+
+    /// Reads the next frame of the input and upmixes it, returns `false` at the end.
+    fn next_frame(&mut self) -> bool {
+        self.span_len = self.input.current_span_len();
+        self.input_channels = self.input.channels();
+        self.sample_rate = self.input.sample_rate();
+        self.frame_pos = 0;
+        self.frame_len = 0;
+
+        if self.input_channels.get() != 2 {
+            // Passed through one sample at a time.
+            let Some(sample) = self.input.next() else {
+                return false;
+            };
+            self.frame[0] = sample;
+            self.frame_len = 1;
+            return true;
+        }
+
+        let (Some(left), Some(right)) = (self.input.next(), self.input.next()) else {
+            return false;
+        };
+        if self.filters_rate != Some(self.sample_rate) {
+            self.reset_filters();
+        }
+
+        let sum = (left + right) * 0.5;
+        self.lfe = sum + self.lfe_coeff * (self.lfe - sum);
+        let surround = std::mem::replace(&mut self.delay[self.delay_pos], (left - right) * 0.5);
+        self.delay_pos = (self.delay_pos + 1) % self.delay.len();
+
+        self.frame = [
+            left,
+            right,
+            sum * FRAC_1_SQRT_2,
+            self.lfe,
+            surround,
+            -surround,
+        ];
+        self.frame_len = 6;
+        true
+    }
+
+    fn reset_filters(&mut self) {
+        let delay_frames = SURROUND_DELAY.as_secs_f64() * self.sample_rate.get() as f64;
+        self.delay.clear();
+        self.delay.resize((delay_frames as usize).max(1), 0.0);
+        self.delay_pos = 0;
+        self.lfe = 0.0;
+        let time_constant = Duration::from_secs_f64(1.0 / (2.0 * PI * LFE_CUTOFF) as f64);
+        self.lfe_coeff = duration_to_coefficient(time_constant, self.sample_rate);
+        self.filters_rate = Some(self.sample_rate);
+    }
+}
+
+impl<I> Iterator for UpmixSurround<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        if !self.in_frame() && !self.next_frame() {
+            return None;
+        }
+        let sample = self.frame[self.frame_pos];
+        self.frame_pos += 1;
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.input.size_hint();
+        let input_channels = self.input.channels();
+        let channels = Self::output_channels(input_channels).get() as usize;
+        let scale = |len: usize| len / input_channels.get() as usize * channels;
+        let buffered = self.frame_len - self.frame_pos;
+        (
+            scale(lower) + buffered,
+            upper.map(|upper| scale(upper) + buffered),
+        )
+    }
+}
+
+impl<I> Source for UpmixSurround<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        let (span_len, input_channels) = if self.in_frame() {
+            (self.span_len, self.input_channels)
+        } else {
+            (self.input.current_span_len(), self.input.channels())
+        };
+        let channels = Self::output_channels(input_channels).get() as usize;
+        span_len.map(|len| len / input_channels.get() as usize * channels)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        if self.in_frame() {
+            Self::output_channels(self.input_channels)
+        } else {
+            Self::output_channels(self.input.channels())
+        }
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        if self.in_frame() {
+            self.sample_rate
+        } else {
+            self.input.sample_rate()
+        }
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.frame_len = 0;
+        self.frame_pos = 0;
+        self.filters_rate = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::SamplesBuffer;
+
+    #[test]
+    fn upmixes_stereo_to_six_channels() {
+        let frames: Vec<[Sample; 2]> = (0..1000)
+            .map(|i| [(i % 7) as Sample / 10.0, -((i % 5) as Sample) / 10.0])
+            .collect();
+        let source = SamplesBuffer::new(nz!(2), nz!(1000), frames.concat()).upmix_surround();
+        assert_eq!(source.channels(), nz!(6));
+        assert_eq!(source.current_span_len(), Some(6000));
+
+        let output: Vec<Sample> = source.collect();
+        assert_eq!(output.len(), 6000);
+        for (frame, [left, right]) in output.chunks(6).zip(&frames) {
+            assert_eq!(frame[..2], [*left, *right]);
+            assert!((frame[2] - (left + right) / 2.0 * FRAC_1_SQRT_2).abs() < 1e-6);
+            assert_eq!(frame[4], -frame[5]);
+        }
+
+        // 12ms at 1000Hz.
+        let surround: Vec<Sample> = output.chunks(6).map(|frame| frame[4]).collect();
+        assert_eq!(surround[..12], [0.0; 12]);
+        let [left, right] = frames[1];
+        assert_eq!(surround[13], (left - right) / 2.0);
+    }
+
+    #[test]
+    fn passes_through_other_layouts() {
+        let samples = vec![0.1, 0.2, 0.3];
+        let source = SamplesBuffer::new(nz!(1), nz!(1000), samples.clone()).upmix_surround();
+        assert_eq!(source.channels(), nz!(1));
+        assert_eq!(source.collect::<Vec<_>>(), samples);
+    }
+}
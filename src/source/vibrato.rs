@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+use super::fractional_delay::FractionalDelay;
+use super::{SeekError, SpanTracker};
+use crate::common::{ChannelCount, SampleRate};
+use crate::math::{duration_to_float, TAU};
+use crate::{Float, Sample, Source};
+
+/// Internal function that builds a `Vibrato` object.
+pub(crate) fn vibrato<I>(input: I, rate_hz: Float, depth: Duration) -> Vibrato<I>
+where
+    I: Source,
+{
+    let sample_rate = input.sample_rate();
+    let channels = input.channels();
+
+    Vibrato {
+        input,
+        rate_hz,
+        depth,
+        delay_line: DelayLine::new(sample_rate, channels, depth),
+        span: SpanTracker::new(sample_rate, channels),
+    }
+}
+
+/// Filter that periodically raises and lowers the pitch of the source.
+///
+/// The input is written into a delay line per channel and read back at a delay that moves
+/// between zero and twice `depth` at `rate_hz`. While the delay grows the pitch drops, while it
+/// shrinks the pitch rises. The pitch swings by up to `2π * rate_hz * depth` relative to the
+/// input, so 5 Hz with a depth of 1 ms changes the pitch by about ±3%.
+#[derive(Clone, Debug)]
+pub struct Vibrato<I> {
+    input: I,
+    rate_hz: Float,
+    depth: Duration,
+    delay_line: DelayLine,
+    span: SpanTracker,
+}
+
+impl<I> Vibrato<I>
+where
+    I: Source,
+{
+    /// Returns how often per second the pitch goes up and down.
+    #[inline]
+    pub fn rate_hz(&self) -> Float {
+        self.rate_hz
+    }
+
+    /// Changes how often per second the pitch goes up and down.
+    #[inline]
+    pub fn set_rate_hz(&mut self, rate_hz: Float) {
+        self.rate_hz = rate_hz;
+    }
+
+    /// Returns how far the delay swings around its center.
+    #[inline]
+    pub fn depth(&self) -> Duration {
+        self.depth
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I> Iterator for Vibrato<I>
+where
+    I: Source,
+{
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let sample = self.input.next()?;
+
+        let input_span_len = self.input.current_span_len();
+        let current_sample_rate = self.input.sample_rate();
+        let current_channels = self.input.channels();
+
+        let detection = self
+            .span
+            .advance(input_span_len, current_sample_rate, current_channels);
+        if detection.at_span_boundary && detection.parameters_changed {
+            self.delay_line = DelayLine::new(current_sample_rate, current_channels, self.depth);
+        }
+
+        Some(self.delay_line.process(sample, self.rate_hz))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Vibrato<I> where I: Source + ExactSizeIterator {}
+
+impl<I> Source for Vibrato<I>
+where
+    I: Source,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.input.is_seekable()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.span.seek(pos, &self.input);
+        self.delay_line =
+            DelayLine::new(self.input.sample_rate(), self.input.channels(), self.depth);
+
+        Ok(())
+    }
+}
+
+/// Delay line whose delay moves between zero and twice the depth.
+#[derive(Clone, Debug)]
+struct DelayLine {
+    delay: FractionalDelay,
+    sample_rate: Float,
+    // Delay swing around the center, in frames.
+    depth: Float,
+    // Position within the modulation period, in `[0.0, 1.0)`.
+    phase: Float,
+}
+
+impl DelayLine {
+    fn new(sample_rate: SampleRate, channels: ChannelCount, depth: Duration) -> Self {
+        let sample_rate = sample_rate.get() as Float;
+        let depth = duration_to_float(depth) * sample_rate;
+
+        Self {
+            delay: FractionalDelay::new(channels, 2.0 * depth),
+            sample_rate,
+            depth,
+            phase: 0.0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: Sample, rate_hz: Float) -> Sample {
+        self.delay.write(sample);
+        // Starts at zero delay so the output begins without latency.
+        let output = self
+            .delay
+            .read(self.depth * (1.0 - (TAU * self.phase).cos()));
+
+        if self.delay.advance() {
+            self.phase = (self.phase + rate_hz / self.sample_rate).rem_euclid(1.0);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SineWave;
+
+    /// Frequency of every period, from the interpolated positions of positive zero crossings.
+    fn period_frequencies(samples: &[Sample], sample_rate: Float) -> Vec<Float> {
+        let crossings: Vec<Float> = samples
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0] <= 0.0 && pair[1] > 0.0)
+            .map(|(n, pair)| n as Float + pair[0] / (pair[0] - pair[1]))
+            .collect();
+        crossings
+            .windows(2)
+            .map(|pair| sample_rate / (pair[1] - pair[0]))
+            .collect()
+    }
+
+    #[test]
+    fn pitch_oscillates_around_input() {
+        let source = SineWave::new(1000.0).take_duration(Duration::from_secs(1));
+        let expected_len = source.clone().count();
+
+        let output: Vec<Sample> = source.vibrato(5.0, Duration::from_millis(1)).collect();
+        assert_eq!(output.len(), expected_len);
+
+        // 2π * 5Hz * 1ms swings the pitch by up to 3.1%.
+        let frequencies = period_frequencies(&output, 48000.0);
+        let (min, max) = frequencies
+            .iter()
+            .fold((Float::MAX, Float::MIN), |(min, max), &f| {
+                (min.min(f), max.max(f))
+            });
+        assert!(min > 960.0 && min < 975.0, "lowest pitch {min}Hz");
+        assert!(max > 1025.0 && max < 1040.0, "highest pitch {max}Hz");
+
+        let mean = frequencies.iter().sum::<Float>() / frequencies.len() as Float;
+        assert!((mean - 1000.0).abs() < 2.0, "mean pitch {mean}Hz");
+    }
+}
@@ -111,6 +111,12 @@ impl Source for Zero {
         })
     }
 
+    /// Only silence of a known length can seek, endless silence has no position to seek to.
+    #[inline]
+    fn is_seekable(&self) -> bool {
+        self.total_samples.is_some()
+    }
+
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         if let (Some(total_samples), Some(total_duration)) =
             (self.total_samples, self.total_duration())
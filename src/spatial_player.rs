@@ -173,6 +173,14 @@ impl SpatialPlayer {
         self.player.sleep_until_end();
     }
 
+    /// Sleeps the current thread until the sound ends or the timeout expires.
+    ///
+    /// Returns `true` if the sound ended. See [`Player::sleep_until_end_timeout`].
+    #[inline]
+    pub fn sleep_until_end_timeout(&self, timeout: Duration) -> bool {
+        self.player.sleep_until_end_timeout(timeout)
+    }
+
     /// Returns true if this sink has no more sounds to play.
     #[inline]
     pub fn empty(&self) -> bool {
@@ -202,8 +210,8 @@ impl SpatialPlayer {
     /// It will return an error if an implementation ran
     /// into one during the seek.  
     ///
-    /// When seeking beyond the end of a source this
-    /// function might return an error if the duration of the source is not known.
+    /// When seeking beyond the end of a source this function might return
+    /// [`SeekError::OutOfRange`] if the duration of the source is not known.
     pub fn try_seek(&self, pos: Duration) -> Result<(), SeekError> {
         self.player.try_seek(pos)
     }
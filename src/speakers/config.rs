@@ -74,6 +74,8 @@ impl OutputConfig {
             sample_rate: self.sample_rate,
             buffer_size: cpal::BufferSize::Fixed(self.buffer_size_frames()),
             sample_format: self.sample_format,
+            underrun_fill: Default::default(),
+            clip_mode: Default::default(),
         }
     }
 }
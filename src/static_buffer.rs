@@ -21,6 +21,25 @@ use crate::source::SeekError;
 use crate::{Sample, Source};
 
 /// A buffer of samples treated as a source.
+///
+/// Plays samples from a `static` without copying them, which suits short sound effects that
+/// are compiled into the program:
+///
+/// ```
+/// use rodio::static_buffer::StaticSamplesBuffer;
+/// use rodio::{nz, Player, Sample};
+///
+/// static CLICK: [Sample; 4] = [0.0, 1.0, -1.0, 0.0];
+///
+/// let (player, _output) = Player::new();
+/// player.append(StaticSamplesBuffer::new(nz!(1), nz!(44100), &CLICK));
+/// ```
+///
+/// Players only accept `'static` sources, so the samples must live for the whole program.
+/// Samples with a shorter lifetime can be copied into a
+/// [`SamplesBuffer`](crate::buffer::SamplesBuffer) with
+/// [`SamplesBuffer::from_slice`](crate::buffer::SamplesBuffer::from_slice). Cloning a
+/// `StaticSamplesBuffer` is cheap and does not copy the samples.
 #[derive(Clone)]
 pub struct StaticSamplesBuffer {
     data: SliceIter<'static, Sample>,
@@ -121,6 +140,7 @@ mod tests {
     use crate::math::nz;
     use crate::source::Source;
     use crate::static_buffer::StaticSamplesBuffer;
+    use crate::{Player, Sample};
 
     #[test]
     fn basic() {
@@ -135,6 +155,16 @@ mod tests {
         assert_eq!(dur.subsec_nanos(), 500_000_000);
     }
 
+    #[test]
+    fn plays_in_player() {
+        static SAMPLES: [Sample; 4] = [0.25, 0.5, -0.5, -0.25];
+
+        let (player, output) = Player::new();
+        player.append(StaticSamplesBuffer::new(nz!(1), nz!(1), &SAMPLES));
+        let played: Vec<Sample> = output.skip_while(|s| *s == 0.0).take(4).collect();
+        assert_eq!(played, SAMPLES);
+    }
+
     #[test]
     fn iteration() {
         let mut buf = StaticSamplesBuffer::new(nz!(1), nz!(44100), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
@@ -15,23 +15,35 @@
 //!
 //! You can manually specify the buffer size if you want lower latency. For more
 //! info see [buffer_size](DeviceSinkBuilder::with_buffer_size).
+//! Alternatively describe the latency you need with a
+//! [latency hint](DeviceSinkBuilder::with_latency_hint) and let rodio pick a size the device
+//! supports.
 //!
 //! If you find a good way to reliably get a good buffer size on all platforms
 //! please contribute your solution to us!
 use crate::common::{assert_error_traits, ChannelCount, SampleRate};
-use crate::math::{nearest_multiple_of_two, nz};
-use crate::mixer::{mixer, Mixer};
+use crate::conversions::convert_samples;
+use crate::math::{nearest_multiple_of_two, nz, NANOS_PER_SEC};
+use crate::mixer::{mixer, Mixer, MixerSource};
 use crate::player::Player;
-use crate::{decoder, Source};
+use crate::{decoder, Float, Source};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{BufferSize, Sample, SampleFormat, StreamConfig, I24};
+use cpal::{BufferSize, SampleFormat, StreamConfig, SupportedBufferSize, I24};
 use std::fmt;
 use std::io::{Read, Seek};
 use std::marker::Sync;
 use std::num::NonZero;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
 
 const HZ_44100: SampleRate = nz!(44_100);
 
+/// How long [`UnderrunFill::FadeToSilence`] takes to reach silence.
+const UNDERRUN_FADE_DURATION: Duration = Duration::from_millis(5);
+
 /// `cpal::Stream` container. Use `mixer()` method to control output.
 ///
 /// <div class="warning">When dropped playback will end, and the associated
@@ -59,29 +71,381 @@ pub struct MixerDeviceSink {
     config: DeviceSinkConfig,
     mixer: Mixer,
     log_on_drop: bool,
-    _stream: cpal::Stream,
+    frames_played: Arc<AtomicU64>,
+    // Exactly one of these pulls samples from the mixer.
+    _stream: Option<cpal::Stream>,
+    device_stream: Option<Arc<DeviceStream>>,
+    _null_output: Option<NullOutput>,
+}
+
+/// How long to wait between attempts to reopen a lost device.
+const RECONNECT_PERIOD: Duration = Duration::from_millis(500);
+
+type ErrorCallback = dyn FnMut(cpal::StreamError) + Send;
+
+/// The stream of a sink opened on a device, with everything needed to open it again once the
+/// device is lost.
+struct DeviceStream {
+    stream: Mutex<Option<cpal::Stream>>,
+    // The device currently playing, which can differ from `device_id` after a reconnect.
+    device: Mutex<cpal::Device>,
+    device_id: Option<cpal::DeviceId>,
+    config: DeviceSinkConfig,
+    // The data callback owns the mixer source, and sends it back here once the stream is
+    // dropped so a new stream can continue to pull from the same mixer.
+    returned_source: Mutex<Receiver<MixerSource>>,
+    return_source: Sender<MixerSource>,
+    frames_played: Arc<AtomicU64>,
+    error_callback: Arc<Mutex<ErrorCallback>>,
+    paused: AtomicBool,
+    // Owned separately so the error callback does not have to upgrade a `Weak` on the audio
+    // thread, which could end up dropping the stream from its own callback.
+    disconnected: Arc<AtomicBool>,
+    auto_reconnect: AtomicBool,
+    on_disconnect: Mutex<Option<Box<dyn FnMut() + Send>>>,
+}
+
+/// Holds the mixer source in the data callback of a stream, and hands it back to the
+/// `DeviceStream` when the callback is dropped together with the stream.
+struct SourceSlot {
+    source: Option<MixerSource>,
+    return_source: Sender<MixerSource>,
+}
+
+impl Drop for SourceSlot {
+    fn drop(&mut self) {
+        if let Some(source) = self.source.take() {
+            // Fails only if the sink was dropped, then nothing plays the mixer anymore.
+            let _ = self.return_source.send(source);
+        }
+    }
+}
+
+impl DeviceStream {
+    fn build(
+        self: &Arc<Self>,
+        device: &cpal::Device,
+        source: MixerSource,
+    ) -> Result<cpal::Stream, DeviceSinkError> {
+        let state = Arc::downgrade(self);
+        let disconnected = Arc::clone(&self.disconnected);
+        let error_callback = Arc::clone(&self.error_callback);
+        let source = SourceSlot {
+            source: Some(source),
+            return_source: self.return_source.clone(),
+        };
+        MixerDeviceSink::init_stream(
+            device,
+            &self.config,
+            source,
+            Arc::clone(&self.frames_played),
+            move |err| {
+                let lost = matches!(
+                    err,
+                    cpal::StreamError::DeviceNotAvailable | cpal::StreamError::StreamInvalidated
+                );
+                if lost && !disconnected.swap(true, Ordering::Relaxed) {
+                    spawn_reconnect(state.clone(), true);
+                }
+                let mut error_callback = error_callback.lock().unwrap();
+                error_callback(err);
+            },
+        )
+    }
+
+    /// Opens the device again, or the default device of its host if it is gone, and continues
+    /// playing the mixer on it.
+    fn reconnect(self: &Arc<Self>) -> Result<(), DeviceSinkError> {
+        // Only one reconnect runs at a time. The stream itself stays unlocked while waiting, so
+        // `pause`, `play` and friends do not stall.
+        let returned_source = self.returned_source.lock().unwrap();
+        if !self.disconnected.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let host = self
+            .device_id
+            .as_ref()
+            .and_then(|id| cpal::host_from_id(id.0).ok())
+            .unwrap_or_else(cpal::default_host);
+        let device = self
+            .device_id
+            .as_ref()
+            .and_then(|id| host.device_by_id(id))
+            .or_else(|| host.default_output_device())
+            .ok_or(DeviceSinkError::NoDevice)?;
+
+        // Dropping the lost stream drops its data callback, which hands back the mixer source.
+        // Some backends do that on another thread, so wait for it a little.
+        let lost = self.stream.lock().unwrap().take();
+        drop(lost);
+        let source = returned_source
+            .recv_timeout(RECONNECT_PERIOD)
+            .map_err(|_| DeviceSinkError::NoDevice)?;
+        // If building fails the source is handed back again for the next attempt.
+        let stream = self.build(&device, source)?;
+
+        let mut current = self.stream.lock().unwrap();
+        // `pause` sets the flag before it takes the lock, so it is either seen here or pauses
+        // the new stream itself.
+        if !self.paused.load(Ordering::Relaxed) {
+            stream.play().map_err(DeviceSinkError::PlayError)?;
+        }
+        *current = Some(stream);
+        drop(current);
+        *self.device.lock().unwrap() = device;
+        self.disconnected.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// What the reconnect thread needs from the stream of a lost device.
+trait Reconnect: Send + Sync + 'static {
+    fn notify_disconnect(&self);
+    fn auto_reconnect(&self) -> bool;
+    /// Returns `true` once the stream plays again.
+    fn try_reconnect(self: &Arc<Self>) -> bool;
+}
+
+impl Reconnect for DeviceStream {
+    fn notify_disconnect(&self) {
+        if let Some(on_disconnect) = self.on_disconnect.lock().unwrap().as_mut() {
+            on_disconnect();
+        }
+    }
+
+    fn auto_reconnect(&self) -> bool {
+        self.auto_reconnect.load(Ordering::Relaxed)
+    }
+
+    fn try_reconnect(self: &Arc<Self>) -> bool {
+        self.reconnect().is_ok()
+    }
+}
+
+/// Reports a lost device if `notify` is set and, while auto-reconnect is enabled, tries to
+/// reopen it until that works or the sink is dropped. Enabling auto-reconnect later starts
+/// trying again.
+fn spawn_reconnect<T: Reconnect>(state: Weak<T>, notify: bool) -> Option<thread::JoinHandle<()>> {
+    // Best effort, if no thread can be spawned the sink simply stays silent.
+    thread::Builder::new()
+        .name("rodio reconnect".to_owned())
+        .spawn(move || {
+            if notify {
+                if let Some(state) = state.upgrade() {
+                    state.notify_disconnect();
+                }
+            }
+            loop {
+                let Some(state) = state.upgrade() else {
+                    return;
+                };
+                if !state.auto_reconnect() || state.try_reconnect() {
+                    return;
+                }
+                drop(state);
+                thread::sleep(RECONNECT_PERIOD);
+            }
+        })
+        .ok()
+}
+
+/// How often the null output pulls samples from the mixer.
+const NULL_OUTPUT_PERIOD: Duration = Duration::from_millis(10);
+
+/// Thread that pulls samples from the mixer at real-time rate and discards them.
+struct NullOutput {
+    stop: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Drop for NullOutput {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
 }
 
 impl MixerDeviceSink {
+    /// Creates a sink that is not connected to any audio device.
+    ///
+    /// The mixer works exactly like the mixer of a real device, but a background thread pulls
+    /// its samples at roughly real-time speed and throws them away. This makes it possible to
+    /// test code that plays sounds, including [`Player::sleep_until_end`], on machines without
+    /// audio hardware such as CI servers.
+    ///
+    /// Unlike sinks opened on a device, this does not log a message when dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use rodio::{nz, MixerDeviceSink, Player};
+    ///
+    /// let sink = MixerDeviceSink::null(nz!(2), nz!(44_100));
+    /// let player = Player::connect_new(sink.mixer());
+    /// ```
+    pub fn null(channel_count: ChannelCount, sample_rate: SampleRate) -> MixerDeviceSink {
+        let (controller, mut source) = mixer(channel_count, sample_rate);
+        let frames_played = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let paused = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let frames_played = Arc::clone(&frames_played);
+            let stop = Arc::clone(&stop);
+            let paused = Arc::clone(&paused);
+            thread::Builder::new()
+                .name("rodio null output".to_owned())
+                .spawn(move || {
+                    let mut start = Instant::now();
+                    let mut played_before_start = 0;
+                    let mut played = 0;
+                    while !stop.load(Ordering::Relaxed) {
+                        thread::sleep(NULL_OUTPUT_PERIOD);
+                        if paused.load(Ordering::Relaxed) {
+                            start = Instant::now();
+                            played_before_start = played;
+                            continue;
+                        }
+                        let due = played_before_start
+                            + (start.elapsed().as_nanos() * sample_rate.get() as u128
+                                / NANOS_PER_SEC as u128) as u64;
+                        let samples = (due - played) * channel_count.get() as u64;
+                        for _ in 0..samples {
+                            source.next();
+                        }
+                        frames_played.fetch_add(due - played, Ordering::Relaxed);
+                        played = due;
+                    }
+                })
+                .expect("failed to spawn null output thread")
+        };
+
+        Self {
+            config: DeviceSinkConfig {
+                channel_count,
+                sample_rate,
+                ..DeviceSinkConfig::default()
+            },
+            mixer: controller,
+            log_on_drop: false,
+            frames_played,
+            _stream: None,
+            device_stream: None,
+            _null_output: Some(NullOutput {
+                stop,
+                paused,
+                thread: Some(thread),
+            }),
+        }
+    }
+
+    /// Wraps a cpal stream that was built by the caller, for cases where rodio's own stream
+    /// setup does not fit, for example a custom data callback.
+    ///
+    /// The stream's data callback must pull its samples from the [`MixerSource`](crate::mixer::MixerSource) belonging to
+    /// `mixer`, and must run at the mixer's channel count and sample rate. Rodio can not check
+    /// this; if the stream does not pull from the mixer nothing added to it is heard.
+    /// `sample_format` is the format the stream was built with and is only reported through
+    /// [`config`](MixerDeviceSink::config).
+    ///
+    /// The stream is started and kept alive until the returned sink is dropped. Rodio does not
+    /// see the frames the stream plays, so [`frames_played`](MixerDeviceSink::frames_played)
+    /// stays zero.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use cpal::traits::{DeviceTrait, HostTrait};
+    /// use rodio::mixer::mixer;
+    /// use rodio::{nz, MixerDeviceSink, Source};
+    ///
+    /// let device = cpal::default_host().default_output_device().unwrap();
+    /// let (mixer, mut source) = mixer(nz!(2), nz!(48_000));
+    /// let config = cpal::StreamConfig {
+    ///     channels: 2,
+    ///     sample_rate: 48_000,
+    ///     buffer_size: cpal::BufferSize::Default,
+    /// };
+    /// let stream = device.build_output_stream(
+    ///     &config,
+    ///     move |data: &mut [f32], _| {
+    ///         for sample in data.iter_mut() {
+    ///             *sample = source.next().unwrap_or(0.0);
+    ///         }
+    ///     },
+    ///     |err| eprintln!("{err}"),
+    ///     None,
+    /// )?;
+    /// let sink = MixerDeviceSink::from_raw(stream, mixer, cpal::SampleFormat::F32)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_raw(
+        stream: cpal::Stream,
+        mixer: Mixer,
+        sample_format: SampleFormat,
+    ) -> Result<MixerDeviceSink, DeviceSinkError> {
+        stream.play().map_err(DeviceSinkError::PlayError)?;
+        Ok(Self {
+            config: DeviceSinkConfig {
+                channel_count: mixer.channels(),
+                sample_rate: mixer.sample_rate(),
+                sample_format,
+                ..DeviceSinkConfig::default()
+            },
+            mixer,
+            log_on_drop: true,
+            frames_played: Arc::new(AtomicU64::new(0)),
+            _stream: Some(stream),
+            device_stream: None,
+            _null_output: None,
+        })
+    }
+
     /// Access the sink's mixer.
     pub fn mixer(&self) -> &Mixer {
         &self.mixer
     }
-    /// pause underlaying audio stream reducing cpu usage
+    /// Pauses the audio stream, so the device stops pulling samples from the mixer.
     ///
-    /// will silenty fail to play audio until play() is called
+    /// This pauses everything playing through this sink at the device level and saves power
+    /// and CPU while nothing needs to be heard. Unlike [`Player::pause`], which keeps the
+    /// device running and outputs silence for that player, no samples are processed at all
+    /// until [`play`](MixerDeviceSink::play) is called. Sounds resume where they were paused.
     ///
-    /// This is an experimental API it is likely to removed once a better solution is found without notice or a change log entry
-    #[cfg(feature = "experimental")]
-    pub fn pause(&self) {
-        let _ = self._stream.pause();
+    /// On some backends pausing does not fully stop the data callback, so the mixer may still
+    /// be pulled now and then. Backends that can not pause at all return an error.
+    pub fn pause(&self) -> Result<(), DeviceSinkError> {
+        if let Some(stream) = &self._stream {
+            stream.pause().map_err(DeviceSinkError::PauseError)?;
+        }
+        if let Some(device_stream) = &self.device_stream {
+            device_stream.paused.store(true, Ordering::Relaxed);
+            if let Some(stream) = &*device_stream.stream.lock().unwrap() {
+                stream.pause().map_err(DeviceSinkError::PauseError)?;
+            }
+        }
+        if let Some(null_output) = &self._null_output {
+            null_output.paused.store(true, Ordering::Relaxed);
+        }
+        Ok(())
     }
-    /// resume underlaying audio stream
-    ///
-    /// This is an experimental API it is likely to removed once a better solution is found without notice or a change log entry
-    #[cfg(feature = "experimental")]
-    pub fn play(&self) {
-        let _ = self._stream.play();
+
+    /// Resumes the audio stream after it was paused with [`pause`](MixerDeviceSink::pause).
+    pub fn play(&self) -> Result<(), DeviceSinkError> {
+        if let Some(stream) = &self._stream {
+            stream.play().map_err(DeviceSinkError::PlayError)?;
+        }
+        if let Some(device_stream) = &self.device_stream {
+            device_stream.paused.store(false, Ordering::Relaxed);
+            if let Some(stream) = &*device_stream.stream.lock().unwrap() {
+                stream.play().map_err(DeviceSinkError::PlayError)?;
+            }
+        }
+        if let Some(null_output) = &self._null_output {
+            null_output.paused.store(false, Ordering::Relaxed);
+        }
+        Ok(())
     }
 
     /// Access the sink's config.
@@ -89,6 +453,88 @@ impl MixerDeviceSink {
         &self.config
     }
 
+    /// Returns the number of frames the audio device has taken from this sink so far.
+    ///
+    /// Divide by the [sample rate](DeviceSinkConfig::sample_rate) to get a playback clock that
+    /// does not depend on wall time, for example to synchronize visuals or subtitles. The
+    /// counter advances whenever the device pulls audio, including while the mixer outputs
+    /// silence because nothing is playing or all players are paused.
+    ///
+    /// Frames are counted when they are handed to the device, so the audio currently heard
+    /// lags behind by roughly the [buffer size](DeviceSinkBuilder::with_buffer_size).
+    pub fn frames_played(&self) -> u64 {
+        self.frames_played.load(Ordering::Relaxed)
+    }
+
+    /// Returns the volume the summed output of the mixer is multiplied with.
+    pub fn master_volume(&self) -> Float {
+        self.mixer.master_volume()
+    }
+
+    /// Sets the volume the summed output of the mixer is multiplied with. Defaults to `1.0`.
+    ///
+    /// This applies on top of the volume of each [`Player`], so a player at `0.5` on a sink at
+    /// `0.5` plays at a quarter of its original amplitude. The output moves to the new volume
+    /// over a few milliseconds to avoid clicks.
+    pub fn set_master_volume(&self, volume: Float) {
+        self.mixer.set_master_volume(volume);
+    }
+
+    /// Calls `callback` when the audio device is lost, for example because it was unplugged.
+    ///
+    /// The callback runs on a background thread, once per lost device. Only sinks opened by
+    /// rodio on a device notice this, the callback of a [null](MixerDeviceSink::null) sink or
+    /// one built [from a raw stream](MixerDeviceSink::from_raw) is never called. Replaces the
+    /// callback set before.
+    pub fn on_disconnect<F>(&self, callback: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        if let Some(device_stream) = &self.device_stream {
+            *device_stream.on_disconnect.lock().unwrap() = Some(Box::new(callback));
+        }
+    }
+
+    /// Reopens the output when the audio device is lost. Off by default.
+    ///
+    /// While the device is gone, rodio tries every half second to open it again, or the
+    /// default output device of the same host if it does not come back. The new stream plays
+    /// the same mixer, so [`Player`]s and sources added to it keep playing and are not
+    /// affected. What plays while no device is open is skipped at real-time rate or paused,
+    /// depending on the backend.
+    ///
+    /// This is best effort: the new device has to support the exact
+    /// [configuration](MixerDeviceSink::config) of this sink, otherwise rodio keeps trying
+    /// until it is dropped. Not every backend reports a lost device, and some report it only
+    /// once the device is used again.
+    ///
+    /// Disabling it stops the retries, enabling it while the device is lost starts them again.
+    pub fn set_auto_reconnect(&self, enabled: bool) {
+        if let Some(device_stream) = &self.device_stream {
+            let was_enabled = device_stream
+                .auto_reconnect
+                .swap(enabled, Ordering::Relaxed);
+            if enabled && !was_enabled && device_stream.disconnected.load(Ordering::Relaxed) {
+                spawn_reconnect(Arc::downgrade(device_stream), false);
+            }
+        }
+    }
+
+    /// Returns the name of the device this sink plays through.
+    ///
+    /// Useful to show which device is in use, since
+    /// [`open_sink_or_fallback`](DeviceSinkBuilder::open_sink_or_fallback) and
+    /// [`open_default_sink`](DeviceSinkBuilder::open_default_sink) may pick another device than
+    /// the default one. After an [auto-reconnect](MixerDeviceSink::set_auto_reconnect) this is
+    /// the device that was reopened. Returns `None` for a [null](MixerDeviceSink::null) sink,
+    /// one built [from a raw stream](MixerDeviceSink::from_raw), or if the backend does not
+    /// report a name.
+    pub fn device_name(&self) -> Option<String> {
+        let device_stream = self.device_stream.as_ref()?;
+        let description = device_stream.device.lock().unwrap().description().ok()?;
+        Some(description.name().to_string())
+    }
+
     /// When [`MixerDeviceSink`] is dropped a message is logged to stderr or
     /// emitted through tracing if the tracing feature is enabled.
     pub fn log_on_drop(&mut self, enabled: bool) {
@@ -116,12 +562,18 @@ impl fmt::Debug for MixerDeviceSink {
 }
 
 /// Describes the OS-Sink's configuration
+///
+/// Usually built by the [`DeviceSinkBuilder`]. Build one with [`new`](DeviceSinkConfig::new)
+/// and the `with_*` methods to open a device with exactly this configuration through
+/// [`MixerDeviceSink::open`].
 #[derive(Copy, Clone, Debug)]
 pub struct DeviceSinkConfig {
     pub(crate) channel_count: ChannelCount,
     pub(crate) sample_rate: SampleRate,
     pub(crate) buffer_size: BufferSize,
     pub(crate) sample_format: SampleFormat,
+    pub(crate) underrun_fill: UnderrunFill,
+    pub(crate) clip_mode: ClipMode,
 }
 
 impl Default for DeviceSinkConfig {
@@ -131,11 +583,47 @@ impl Default for DeviceSinkConfig {
             sample_rate: HZ_44100,
             buffer_size: BufferSize::Default,
             sample_format: SampleFormat::F32,
+            underrun_fill: UnderrunFill::default(),
+            clip_mode: ClipMode::default(),
         }
     }
 }
 
 impl DeviceSinkConfig {
+    /// Builds a configuration with the given channel count and sample rate, and the defaults
+    /// of the [`DeviceSinkBuilder`] for everything else.
+    pub fn new(channel_count: ChannelCount, sample_rate: SampleRate) -> Self {
+        Self {
+            channel_count,
+            sample_rate,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the buffer size, see [`DeviceSinkBuilder::with_buffer_size`].
+    pub fn with_buffer_size(mut self, buffer_size: BufferSize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Sets the sample format the device is opened with.
+    pub fn with_sample_format(mut self, sample_format: SampleFormat) -> Self {
+        self.sample_format = sample_format;
+        self
+    }
+
+    /// Sets what the OS-Sink writes when the mixer has no samples.
+    pub fn with_underrun_fill(mut self, underrun_fill: UnderrunFill) -> Self {
+        self.underrun_fill = underrun_fill;
+        self
+    }
+
+    /// Sets how the OS-Sink handles samples outside of ±1.0.
+    pub fn with_clip_mode(mut self, clip_mode: ClipMode) -> Self {
+        self.clip_mode = clip_mode;
+        self
+    }
+
     /// Access the OS-Sink config's channel count.
     pub fn channel_count(&self) -> ChannelCount {
         self.channel_count
@@ -155,6 +643,121 @@ impl DeviceSinkConfig {
     pub fn sample_format(&self) -> SampleFormat {
         self.sample_format
     }
+
+    /// Access what the OS-Sink writes when the mixer has no samples.
+    pub fn underrun_fill(&self) -> UnderrunFill {
+        self.underrun_fill
+    }
+
+    /// Access how the OS-Sink handles samples outside of ±1.0.
+    pub fn clip_mode(&self) -> ClipMode {
+        self.clip_mode
+    }
+}
+
+/// What the OS-Sink writes to the device when the mixer runs out of samples, for example
+/// because nothing is playing or a source could not keep up.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UnderrunFill {
+    /// Write silence.
+    #[default]
+    Silence,
+    /// Keep repeating the last frame that was played. Note that this also holds the last frame
+    /// after the mixer finished playing, which may leave a DC offset on the output.
+    HoldLast,
+    /// Fade the last frame that was played to silence over a few milliseconds. This avoids the
+    /// click of a sudden jump to silence.
+    FadeToSilence,
+}
+
+/// Fills the gaps in the output of the mixer according to an [`UnderrunFill`].
+struct UnderrunFiller {
+    fill: UnderrunFill,
+    last_frame: Vec<crate::Sample>,
+    channel: usize,
+    fade_frames: u32,
+    fade_frames_left: u32,
+}
+
+impl UnderrunFiller {
+    fn new(config: &DeviceSinkConfig) -> Self {
+        let fade_frames = (UNDERRUN_FADE_DURATION.as_nanos() as u64
+            * config.sample_rate.get() as u64
+            / NANOS_PER_SEC)
+            .max(1) as u32;
+        Self {
+            fill: config.underrun_fill,
+            last_frame: vec![0.0; config.channel_count.get() as usize],
+            channel: 0,
+            fade_frames,
+            fade_frames_left: fade_frames,
+        }
+    }
+
+    #[inline]
+    fn fill(&mut self, sample: Option<crate::Sample>) -> crate::Sample {
+        let channel = self.channel;
+        self.channel = (self.channel + 1) % self.last_frame.len();
+
+        if let Some(sample) = sample {
+            self.last_frame[channel] = sample;
+            self.fade_frames_left = self.fade_frames;
+            return sample;
+        }
+
+        match self.fill {
+            UnderrunFill::Silence => 0.0,
+            UnderrunFill::HoldLast => self.last_frame[channel],
+            UnderrunFill::FadeToSilence => {
+                if channel == 0 {
+                    self.fade_frames_left = self.fade_frames_left.saturating_sub(1);
+                }
+                let gain = self.fade_frames_left as crate::Float / self.fade_frames as crate::Float;
+                self.last_frame[channel] * gain
+            }
+        }
+    }
+}
+
+/// How the OS-Sink handles samples outside of ±1.0 before they are sent to the device.
+///
+/// Sounds that are loud on their own can add up to more than full scale when mixed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ClipMode {
+    /// Cut samples off at ±1.0. Integer sample formats always did this when converting,
+    /// this makes float formats behave the same.
+    #[default]
+    Hard,
+    /// Pass all samples through `tanh`. Peaks are rounded off smoothly instead of cut off,
+    /// which sounds less harsh, but quiet samples are slightly attenuated too.
+    SoftTanh,
+    /// Wrap samples around to the other end of the range. Sounds terrible, which makes
+    /// clipping easy to hear while debugging.
+    Wrap,
+}
+
+impl ClipMode {
+    fn apply(self, samples: &mut [crate::Sample]) {
+        match self {
+            ClipMode::Hard => {
+                for sample in samples {
+                    *sample = sample.clamp(-1.0, 1.0);
+                }
+            }
+            ClipMode::SoftTanh => {
+                for sample in samples {
+                    *sample = sample.tanh();
+                }
+            }
+            ClipMode::Wrap => {
+                for sample in samples {
+                    if sample.abs() > 1.0 {
+                        *sample = (*sample + 1.0).rem_euclid(2.0) - 1.0;
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl core::fmt::Debug for DeviceSinkBuilder {
@@ -173,6 +776,9 @@ impl core::fmt::Debug for DeviceSinkBuilder {
         f.debug_struct("DeviceSinkBuilder")
             .field("device", &device)
             .field("config", &self.config)
+            .field("fallback_policy", &self.fallback_policy)
+            .field("exclusive", &self.exclusive)
+            .field("master_volume", &self.master_volume)
             .finish()
     }
 }
@@ -196,6 +802,12 @@ where
 {
     device: Option<cpal::Device>,
     config: DeviceSinkConfig,
+    fallback_policy: FallbackPolicy,
+    exclusive: bool,
+    master_volume: Float,
+    latency_hint: Option<LatencyHint>,
+    // Whether the buffer size was set by the user, which takes precedence over the hint.
+    explicit_buffer_size: bool,
     error_callback: E,
 }
 
@@ -204,11 +816,85 @@ impl Default for DeviceSinkBuilder {
         Self {
             device: None,
             config: DeviceSinkConfig::default(),
+            fallback_policy: FallbackPolicy::default(),
+            exclusive: false,
+            master_volume: 1.0,
+            latency_hint: None,
+            explicit_buffer_size: false,
             error_callback: default_error_callback,
         }
     }
 }
 
+/// Controls what [`DeviceSinkBuilder::open_sink_or_fallback`] tries when the requested
+/// configuration can not be opened.
+///
+/// The default tries the other configurations supported by the device in the order cpal
+/// recommends and never switches to another device.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FallbackPolicy {
+    /// Order in which the configurations supported by the device are tried.
+    pub config: ConfigFallback,
+    /// Whether to try the other output devices of the default host if no configuration of the
+    /// selected device works. Leave this disabled to make sure audio never silently plays
+    /// through another device.
+    pub other_devices: bool,
+}
+
+/// Order in which the supported configurations of a device are tried, see [`FallbackPolicy`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ConfigFallback {
+    /// Use cpal's default heuristics, which prefer more channels, floating point samples and
+    /// the maximum sample rate of each supported range.
+    #[default]
+    DeviceHeuristics,
+    /// Try the supported configurations that can run at the requested sample rate first. This
+    /// avoids resampling when the device can play the requested rate.
+    PreferRequestedSampleRate,
+    /// Try the device's default configuration first.
+    PreferDeviceDefault,
+    /// Try configurations with the largest sample format and highest sample rate first.
+    PreferHighestQuality,
+}
+
+/// The latency to aim for when choosing the buffer size, see
+/// [`DeviceSinkBuilder::with_latency_hint`].
+///
+/// Like the latency hint of Web Audio this describes the use case instead of a number of
+/// frames. Each hint targets a buffer duration, which is clamped to the buffer sizes the device
+/// supports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LatencyHint {
+    /// Aims for about 10ms, so changes such as starting a sound effect are heard right away.
+    /// Costs more CPU time and is more likely to underrun on a busy system.
+    Interactive,
+    /// Aims for about 50ms, a good trade-off for games and media playback. This is close to
+    /// what rodio uses without a hint.
+    Balanced,
+    /// Aims for about 200ms, letting the system wake up less often. Suited to background music
+    /// and other playback that is not controlled interactively.
+    PowerSaving,
+}
+
+impl LatencyHint {
+    fn target_millis(self) -> u32 {
+        match self {
+            LatencyHint::Interactive => 10,
+            LatencyHint::Balanced => 50,
+            LatencyHint::PowerSaving => 200,
+        }
+    }
+
+    /// Picks a buffer size of about the targeted duration within the supported range.
+    fn buffer_size(self, sample_rate: SampleRate, supported: &SupportedBufferSize) -> u32 {
+        let target = nearest_multiple_of_two(sample_rate.get() * self.target_millis() / 1000);
+        match *supported {
+            SupportedBufferSize::Range { min, max } if min <= max => target.clamp(min, max),
+            _ => target,
+        }
+    }
+}
+
 impl DeviceSinkBuilder {
     /// Sets output device and its default parameters.
     pub fn from_device(device: cpal::Device) -> Result<DeviceSinkBuilder, DeviceSinkError> {
@@ -236,7 +922,32 @@ impl DeviceSinkBuilder {
 
     /// Sets default OS-Sink parameters for default output audio device.
     pub fn from_default_device() -> Result<DeviceSinkBuilder, DeviceSinkError> {
-        let default_device = cpal::default_host()
+        Self::from_host(cpal::default_host())
+    }
+
+    /// Sets default OS-Sink parameters for the default output device of an audio host.
+    ///
+    /// A host is an audio backend such as ALSA or JACK on Linux, or WASAPI or ASIO on Windows.
+    /// Use this to play through a backend other than the system default. See
+    /// [`available_hosts`] for the hosts that can be used on this system.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rodio::DeviceSinkBuilder;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// for host in rodio::stream::available_hosts() {
+    ///     println!("available host: {}", host.id().name());
+    /// }
+    ///
+    /// let host = rodio::stream::available_hosts()
+    ///     .next()
+    ///     .expect("no audio host available");
+    /// let _sink = DeviceSinkBuilder::from_host(host)?.open_stream()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_host(host: cpal::Host) -> Result<DeviceSinkBuilder, DeviceSinkError> {
+        let default_device = host
             .default_output_device()
             .ok_or(DeviceSinkError::NoDevice)?;
         Self::from_device(default_device)
@@ -250,22 +961,7 @@ impl DeviceSinkBuilder {
         Self::from_default_device()
             .and_then(|x| x.open_stream())
             .or_else(|original_err| {
-                let devices = match cpal::default_host().output_devices() {
-                    Ok(devices) => devices,
-                    Err(err) => {
-                        #[cfg(feature = "tracing")]
-                        tracing::error!("error getting list of output devices: {err}");
-                        #[cfg(not(feature = "tracing"))]
-                        eprintln!("error getting list of output devices: {err}");
-                        return Err(original_err);
-                    }
-                };
-                devices
-                    .filter(|dev| {
-                        dev.description()
-                            .map(|desc| desc.driver().is_some_and(|driver| driver != "null"))
-                            .unwrap_or(false)
-                    })
+                other_output_devices(&cpal::default_host())
                     .find_map(|d| {
                         Self::from_device(d)
                             .and_then(|x| x.open_sink_or_fallback())
@@ -339,8 +1035,36 @@ where
     /// - Low-latency (audio production, live monitoring): 512-1024
     /// - General use (games, media playback): 1024-2048
     /// - Stability-focused (background music, non-interactive): 2048-4096
+    ///
+    /// An explicit buffer size takes precedence over a
+    /// [latency hint](Self::with_latency_hint).
     pub fn with_buffer_size(mut self, buffer_size: cpal::BufferSize) -> DeviceSinkBuilder<E> {
         self.config.buffer_size = buffer_size;
+        self.explicit_buffer_size = true;
+        self
+    }
+
+    /// Chooses the buffer size based on the latency the application needs, instead of a number
+    /// of frames. See [`LatencyHint`] for the durations each hint aims for.
+    ///
+    /// The buffer size is picked from the range the device supports for the configured
+    /// channel count, sample rate and sample format when the sink is opened. It is ignored if
+    /// a buffer size is set with [`with_buffer_size`](Self::with_buffer_size).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rodio::stream::LatencyHint;
+    /// use rodio::DeviceSinkBuilder;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sink = DeviceSinkBuilder::from_default_device()?
+    ///     .with_latency_hint(LatencyHint::Interactive)
+    ///     .open_stream()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_latency_hint(mut self, hint: LatencyHint) -> DeviceSinkBuilder<E> {
+        self.latency_hint = Some(hint);
         self
     }
 
@@ -362,8 +1086,11 @@ where
             sample_rate: NonZero::new(config.sample_rate())
                 .expect("no valid cpal config has zero sample rate"),
             sample_format: config.sample_format(),
+            underrun_fill: self.config.underrun_fill,
+            clip_mode: self.config.clip_mode,
             ..Default::default()
         };
+        self.explicit_buffer_size = false;
         self
     }
 
@@ -377,6 +1104,24 @@ where
             buffer_size: config.buffer_size,
             ..self.config
         };
+        self.explicit_buffer_size = matches!(config.buffer_size, BufferSize::Fixed(_));
+        self
+    }
+
+    /// Sets what is written to the device when the mixer runs out of samples. Defaults to
+    /// [`UnderrunFill::Silence`].
+    ///
+    /// Holding or fading out the last frame reduces audible clicks when playback can not keep
+    /// up for a moment.
+    pub fn with_underrun_fill(mut self, fill: UnderrunFill) -> DeviceSinkBuilder<E> {
+        self.config.underrun_fill = fill;
+        self
+    }
+
+    /// Sets how samples outside of ±1.0 are handled before they are sent to the device.
+    /// Defaults to [`ClipMode::Hard`].
+    pub fn with_clip_mode(mut self, clip_mode: ClipMode) -> DeviceSinkBuilder<E> {
+        self.config.clip_mode = clip_mode;
         self
     }
 
@@ -388,34 +1133,158 @@ where
         DeviceSinkBuilder {
             device: self.device,
             config: self.config,
+            fallback_policy: self.fallback_policy,
+            exclusive: self.exclusive,
+            master_volume: self.master_volume,
+            latency_hint: self.latency_hint,
+            explicit_buffer_size: self.explicit_buffer_size,
             error_callback: callback,
         }
     }
 
+    /// Sets what [`open_sink_or_fallback`](Self::open_sink_or_fallback) tries when the
+    /// configured stream can not be opened. See [`FallbackPolicy`] for the default.
+    pub fn with_fallback_policy(mut self, policy: FallbackPolicy) -> DeviceSinkBuilder<E> {
+        self.fallback_policy = policy;
+        self
+    }
+
+    /// Requests exclusive access to the device, bypassing the system mixer for lower and more
+    /// deterministic latency. Defaults to `false`, sharing the device with other applications.
+    ///
+    /// # Platform support
+    /// Exclusive mode exists on WASAPI (Windows) and CoreAudio (macOS, hog mode), but cpal does
+    /// not expose it on any platform yet. Until it does, opening a sink with exclusive mode
+    /// requested fails with [`DeviceSinkError::ExclusiveModeNotSupported`] instead of silently
+    /// opening the device in shared mode.
+    pub fn with_exclusive(mut self, exclusive: bool) -> DeviceSinkBuilder<E> {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// Sets the volume the summed output of the sink is multiplied with. Defaults to `1.0`.
+    ///
+    /// See [`MixerDeviceSink::set_master_volume`].
+    pub fn with_master_volume(mut self, volume: Float) -> DeviceSinkBuilder<E> {
+        self.master_volume = volume;
+        self
+    }
+
+    /// Returns the configurations supported by the device, in the order
+    /// [`open_sink_or_fallback`](Self::open_sink_or_fallback) tries them when the configured
+    /// stream can not be opened.
+    ///
+    /// The order follows the [fallback policy](Self::with_fallback_policy). Use this to offer
+    /// sample rates and formats in a settings UI, then pass the chosen one to
+    /// [`with_supported_config`](Self::with_supported_config).
+    ///
+    /// # Panics
+    /// Panics if no device was set.
+    pub fn supported_configs(&self) -> Result<Vec<cpal::SupportedStreamConfig>, DeviceSinkError> {
+        let device = self.device.as_ref().expect("No output device specified");
+        fallback_configs(device, &self.config, self.fallback_policy)
+    }
+
+    fn with_optional_latency_hint(mut self, hint: Option<LatencyHint>) -> DeviceSinkBuilder<E> {
+        self.latency_hint = hint;
+        self
+    }
+
+    fn check_exclusive(&self, device: &cpal::Device) -> Result<(), DeviceSinkError> {
+        if self.exclusive && !supports_exclusive_mode(device) {
+            return Err(DeviceSinkError::ExclusiveModeNotSupported);
+        }
+        Ok(())
+    }
+
+    /// The configuration to open `device` with, with the buffer size picked from the latency
+    /// hint unless one was set explicitly.
+    fn stream_config(&self, device: &cpal::Device) -> DeviceSinkConfig {
+        let mut config = self.config;
+        let Some(hint) = self.latency_hint.filter(|_| !self.explicit_buffer_size) else {
+            return config;
+        };
+
+        let rate = config.sample_rate.get();
+        let supported = device
+            .supported_output_configs()
+            .ok()
+            .and_then(|mut ranges| {
+                ranges.find(|range| {
+                    range.channels() == config.channel_count.get()
+                        && range.sample_format() == config.sample_format
+                        && range.min_sample_rate() <= rate
+                        && rate <= range.max_sample_rate()
+                })
+            })
+            .map(|range| *range.buffer_size())
+            .unwrap_or(SupportedBufferSize::Unknown);
+        config.buffer_size = BufferSize::Fixed(hint.buffer_size(config.sample_rate, &supported));
+        config
+    }
+
     /// Open OS-Sink using parameters configured so far.
     pub fn open_stream(self) -> Result<MixerDeviceSink, DeviceSinkError> {
         let device = self.device.as_ref().expect("No output device specified");
+        self.check_exclusive(device)?;
 
-        MixerDeviceSink::open(device, &self.config, self.error_callback)
+        let config = self.stream_config(device);
+        let sink = MixerDeviceSink::open(device, &config, self.error_callback)?;
+        sink.set_master_volume(self.master_volume);
+        Ok(sink)
     }
 
     /// Try opening a new OS-Sink with the builder's current stream configuration.
     /// Failing that attempt to open stream with other available configurations
-    /// supported by the device.
+    /// supported by the device, and other devices if the
+    /// [fallback policy](Self::with_fallback_policy) allows it.
     /// If all attempts fail returns initial error.
     pub fn open_sink_or_fallback(&self) -> Result<MixerDeviceSink, DeviceSinkError>
+    where
+        E: Clone,
+    {
+        let device = self.device.as_ref().expect("No output device specified");
+        self.check_exclusive(device)?;
+
+        self.open_device_or_fallback()
+            .or_else(|err| {
+                if !self.fallback_policy.other_devices {
+                    return Err(err);
+                }
+                let device_id = device.id().ok();
+                other_output_devices(&cpal::default_host())
+                    .filter(|dev| device_id.is_none() || dev.id().ok() != device_id)
+                    .find_map(|dev| {
+                        DeviceSinkBuilder::from_device(dev)
+                            .ok()?
+                            .with_fallback_policy(self.fallback_policy)
+                            .with_error_callback(self.error_callback.clone())
+                            .with_optional_latency_hint(self.latency_hint)
+                            .open_device_or_fallback()
+                            .ok()
+                    })
+                    .ok_or(err)
+            })
+            .inspect(|sink| sink.set_master_volume(self.master_volume))
+    }
+
+    fn open_device_or_fallback(&self) -> Result<MixerDeviceSink, DeviceSinkError>
     where
         E: Clone,
     {
         let device = self.device.as_ref().expect("No output device specified");
         let error_callback = &self.error_callback;
 
-        MixerDeviceSink::open(device, &self.config, error_callback.clone()).or_else(|err| {
-            for supported_config in supported_output_configs(device)? {
+        let config = self.stream_config(device);
+        MixerDeviceSink::open(device, &config, error_callback.clone()).or_else(|err| {
+            for supported_config in fallback_configs(device, &self.config, self.fallback_policy)? {
                 if let Ok(handle) = DeviceSinkBuilder::default()
                     .with_device(device.clone())
+                    .with_underrun_fill(self.config.underrun_fill)
+                    .with_clip_mode(self.config.clip_mode)
                     .with_supported_config(&supported_config)
                     .with_error_callback(error_callback.clone())
+                    .with_optional_latency_hint(self.latency_hint)
                     .open_stream()
                 {
                     return Ok(handle);
@@ -426,8 +1295,83 @@ where
     }
 }
 
+/// Whether the device can be opened in exclusive mode. cpal has no API for exclusive mode yet,
+/// so no device supports it.
+fn supports_exclusive_mode(_device: &cpal::Device) -> bool {
+    false
+}
+
+/// Output devices that are not the null device, used when falling back to another device.
+fn other_output_devices(host: &cpal::Host) -> impl Iterator<Item = cpal::Device> {
+    let devices = match host.output_devices() {
+        Ok(devices) => Some(devices),
+        Err(err) => {
+            #[cfg(feature = "tracing")]
+            tracing::error!("error getting list of output devices: {err}");
+            #[cfg(not(feature = "tracing"))]
+            eprintln!("error getting list of output devices: {err}");
+            None
+        }
+    };
+    devices.into_iter().flatten().filter(|dev| {
+        dev.description()
+            .map(|desc| desc.driver().is_some_and(|driver| driver != "null"))
+            .unwrap_or(false)
+    })
+}
+
+/// Supported configurations of the device in the order the fallback policy tries them.
+fn fallback_configs(
+    device: &cpal::Device,
+    requested: &DeviceSinkConfig,
+    policy: FallbackPolicy,
+) -> Result<Vec<cpal::SupportedStreamConfig>, DeviceSinkError> {
+    let mut configs: Vec<_> = supported_output_configs(device)?.collect();
+
+    match policy.config {
+        ConfigFallback::DeviceHeuristics => {}
+        ConfigFallback::PreferRequestedSampleRate => {
+            let rate = requested.sample_rate.get();
+            let mut at_rate: Vec<_> = device
+                .supported_output_configs()
+                .map_err(DeviceSinkError::SupportedConfigsError)?
+                .filter(|range| range.min_sample_rate() <= rate && rate <= range.max_sample_rate())
+                .collect();
+            at_rate.sort_by(|a, b| b.cmp_default_heuristics(a));
+            configs.splice(
+                0..0,
+                at_rate
+                    .into_iter()
+                    .map(|range| range.with_sample_rate(rate)),
+            );
+        }
+        ConfigFallback::PreferDeviceDefault => {
+            if let Ok(default) = device.default_output_config() {
+                configs.insert(0, default);
+            }
+        }
+        ConfigFallback::PreferHighestQuality => {
+            configs.sort_by_key(|config| {
+                let format = config.sample_format();
+                std::cmp::Reverse((
+                    format.sample_size(),
+                    format.is_float(),
+                    config.sample_rate(),
+                ))
+            });
+        }
+    }
+
+    Ok(configs)
+}
+
 /// A convenience function. Plays a sound once.
 /// Returns a `Player` that can be used to control the sound.
+///
+/// # Errors
+///
+/// Returns [`PlayError::IoError`] if reading `input` failed, and [`PlayError::DecoderError`]
+/// if its format is not supported or the data could not be decoded.
 pub fn play<R>(mixer: &Mixer, input: R) -> Result<Player, PlayError>
 where
     R: Read + Seek + Send + Sync + 'static,
@@ -456,17 +1400,25 @@ where
 {
     /// Attempting to decode the audio failed.
     #[error("Failed to decode audio")]
-    DecoderError(
-        #[from]
-        #[source]
-        decoder::DecoderError,
-    ),
+    DecoderError(#[source] decoder::DecoderError),
+    /// Reading the audio failed, for example because the file could not be read.
+    #[error("Failed to read audio")]
+    IoError(#[source] Arc<std::io::Error>),
     /// The output device was lost.
     #[error("No output device")]
     NoDevice,
 }
 assert_error_traits!(PlayError);
 
+impl From<decoder::DecoderError> for PlayError {
+    fn from(source: decoder::DecoderError) -> Self {
+        match source {
+            decoder::DecoderError::IoError(error) => PlayError::IoError(error),
+            other => PlayError::DecoderError(other),
+        }
+    }
+}
+
 /// Errors that might occur when interfacing with audio output.
 #[derive(Debug, thiserror::Error)]
 pub enum DeviceSinkError {
@@ -474,6 +1426,9 @@ pub enum DeviceSinkError {
     /// details.
     #[error("Could not start playing the stream")]
     PlayError(#[source] cpal::PlayStreamError),
+    /// Could not pause the sink, see [cpal::PauseStreamError] for details.
+    #[error("Could not pause the stream")]
+    PauseError(#[source] cpal::PauseStreamError),
     /// Failed to get the stream config for the given device. See
     /// [cpal::DefaultStreamConfigError] for details.
     #[error("Failed to get the config for the given device")]
@@ -492,6 +1447,10 @@ pub enum DeviceSinkError {
     /// an issue if you run into this.
     #[error("New cpal sample format that rodio does not yet support please open an issue if you run into this.")]
     UnsupportedSampleFormat,
+    /// Exclusive mode was requested with [`DeviceSinkBuilder::with_exclusive`] but the audio
+    /// backend does not support it.
+    #[error("The audio backend does not support opening the device in exclusive mode")]
+    ExclusiveModeNotSupported,
 }
 
 impl MixerDeviceSink {
@@ -501,7 +1460,37 @@ impl MixerDeviceSink {
         }
     }
 
-    pub(crate) fn open<E>(
+    /// Opens `device` with exactly the given configuration, bypassing the
+    /// [`DeviceSinkBuilder`].
+    ///
+    /// Unlike [`DeviceSinkBuilder::open_sink_or_fallback`] no other configuration is tried if
+    /// the device does not support this one. `error_callback` is called on the audio thread
+    /// when the stream reports an error, also for streams opened by
+    /// [auto-reconnect](MixerDeviceSink::set_auto_reconnect).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer size is [fixed](BufferSize::Fixed) to zero.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rodio::cpal::traits::HostTrait;
+    /// use rodio::cpal::{BufferSize, SampleFormat};
+    /// use rodio::stream::{DeviceSinkConfig, MixerDeviceSink};
+    /// use rodio::nz;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let device = rodio::cpal::default_host()
+    ///     .default_output_device()
+    ///     .ok_or("no output device")?;
+    /// let config = DeviceSinkConfig::new(nz!(2), nz!(48_000))
+    ///     .with_sample_format(SampleFormat::I16)
+    ///     .with_buffer_size(BufferSize::Fixed(480));
+    /// let sink = MixerDeviceSink::open(&device, &config, |err| eprintln!("{err}"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open<E>(
         device: &cpal::Device,
         config: &DeviceSinkConfig,
         error_callback: E,
@@ -511,28 +1500,53 @@ impl MixerDeviceSink {
     {
         Self::validate_config(config);
         let (controller, source) = mixer(config.channel_count, config.sample_rate);
-        Self::init_stream(device, config, source, error_callback).and_then(|stream| {
-            stream.play().map_err(DeviceSinkError::PlayError)?;
-            Ok(Self {
-                _stream: stream,
-                mixer: controller,
-                config: *config,
-                log_on_drop: true,
-            })
+        let frames_played = Arc::new(AtomicU64::new(0));
+        let (return_source, returned_source) = channel();
+        let device_stream = Arc::new(DeviceStream {
+            stream: Mutex::new(None),
+            device: Mutex::new(device.clone()),
+            device_id: device.id().ok(),
+            config: *config,
+            returned_source: Mutex::new(returned_source),
+            return_source,
+            frames_played: Arc::clone(&frames_played),
+            error_callback: Arc::new(Mutex::new(error_callback)),
+            paused: AtomicBool::new(false),
+            disconnected: Arc::new(AtomicBool::new(false)),
+            auto_reconnect: AtomicBool::new(false),
+            on_disconnect: Mutex::new(None),
+        });
+        let stream = device_stream.build(device, source)?;
+        stream.play().map_err(DeviceSinkError::PlayError)?;
+        *device_stream.stream.lock().unwrap() = Some(stream);
+        Ok(Self {
+            _stream: None,
+            device_stream: Some(device_stream),
+            _null_output: None,
+            mixer: controller,
+            config: *config,
+            log_on_drop: true,
+            frames_played,
         })
     }
 
-    fn init_stream<S, E>(
+    fn init_stream<E>(
         device: &cpal::Device,
         config: &DeviceSinkConfig,
-        mut samples: S,
+        mut source: SourceSlot,
+        frames_played: Arc<AtomicU64>,
         error_callback: E,
     ) -> Result<cpal::Stream, DeviceSinkError>
     where
-        S: Source + Send + 'static,
         E: FnMut(cpal::StreamError) + Send + 'static,
     {
         let cpal_config = config.into();
+        let channels = config.channel_count.get() as usize;
+        let mut underrun = UnderrunFiller::new(config);
+        let clip_mode = config.clip_mode;
+        // Grows to the largest buffer the device asks for, after that the callback no longer
+        // allocates.
+        let mut buffer: Vec<crate::Sample> = Vec::new();
 
         macro_rules! build_output_streams {
             ($($sample_format:tt, $generic:ty);+) => {
@@ -540,13 +1554,35 @@ impl MixerDeviceSink {
                     $(
                         cpal::SampleFormat::$sample_format => device.build_output_stream::<$generic, _, _>(
                             cpal_config,
-                            move |data, _| {
-                                data.iter_mut().for_each(|d| {
-                                    *d = samples
-                                        .next()
-                                        .map(Sample::from_sample)
-                                        .unwrap_or(<$generic>::EQUILIBRIUM)
-                                })
+                            move |data: &mut [$generic], _| {
+                                if buffer.len() < data.len() {
+                                    buffer.resize(data.len(), 0.0);
+                                }
+                                let buffer = &mut buffer[..data.len()];
+                                // Only empty once the slot is dropped with the stream.
+                                let Some(samples) = source.source.as_mut() else {
+                                    buffer.fill(0.0);
+                                    convert_samples(buffer, data);
+                                    return;
+                                };
+
+                                let mut written = 0;
+                                while written < buffer.len() {
+                                    let filled = samples.fill_buffer(&mut buffer[written..]);
+                                    for sample in &mut buffer[written..written + filled] {
+                                        *sample = underrun.fill(Some(*sample));
+                                    }
+                                    written += filled;
+                                    // The source returned `None` for this sample.
+                                    if written < buffer.len() {
+                                        buffer[written] = underrun.fill(None);
+                                        written += 1;
+                                    }
+                                }
+                                clip_mode.apply(buffer);
+                                convert_samples(buffer, data);
+                                let frames = (data.len() / channels) as u64;
+                                frames_played.fetch_add(frames, Ordering::Relaxed);
                             },
                             error_callback,
                             None,
@@ -576,6 +1612,16 @@ impl MixerDeviceSink {
     }
 }
 
+/// Returns the audio hosts that can be used on this system.
+///
+/// Hosts that are compiled in but fail to initialize, for example because their server is not
+/// running, are left out. Pass one to [`DeviceSinkBuilder::from_host`] to use it.
+pub fn available_hosts() -> impl Iterator<Item = cpal::Host> {
+    cpal::available_hosts()
+        .into_iter()
+        .filter_map(|id| cpal::host_from_id(id).ok())
+}
+
 /// Return all formats supported by the device.
 pub fn supported_output_configs(
     device: &cpal::Device,
@@ -598,3 +1644,363 @@ pub fn supported_output_configs(
         formats
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads the first bytes of a file, then fails.
+    struct FailingReader {
+        data: std::io::Cursor<&'static [u8]>,
+        fail_at: u64,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.data.position() >= self.fail_at {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionReset,
+                    "connection lost",
+                ));
+            }
+            let max = (self.fail_at - self.data.position()) as usize;
+            let len = buf.len().min(max);
+            self.data.read(&mut buf[..len])
+        }
+    }
+
+    impl Seek for FailingReader {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.data.seek(pos)
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "wav")]
+    fn play_reports_io_errors() {
+        let (mixer, _output) = mixer(nz!(2), nz!(44_100));
+        let reader = FailingReader {
+            data: std::io::Cursor::new(include_bytes!("../assets/music.wav")),
+            fail_at: 20,
+        };
+        match play(&mixer, reader) {
+            Err(PlayError::IoError(error)) => {
+                assert_eq!(error.kind(), std::io::ErrorKind::ConnectionReset)
+            }
+            other => panic!("expected an I/O error, got {:?}", other.err()),
+        }
+
+        let garbage = std::io::Cursor::new(vec![0u8; 1024]);
+        assert!(matches!(
+            play(&mixer, garbage),
+            Err(PlayError::DecoderError(_))
+        ));
+    }
+
+    #[test]
+    fn config_constructor() {
+        let config = DeviceSinkConfig::new(nz!(1), nz!(48_000))
+            .with_sample_format(SampleFormat::I16)
+            .with_buffer_size(BufferSize::Fixed(256))
+            .with_clip_mode(ClipMode::Wrap);
+        assert_eq!(config.channel_count(), nz!(1));
+        assert_eq!(config.sample_rate(), nz!(48_000));
+        assert_eq!(config.sample_format(), SampleFormat::I16);
+        assert_eq!(config.underrun_fill(), UnderrunFill::Silence);
+        assert_eq!(config.clip_mode(), ClipMode::Wrap);
+
+        let stream_config = StreamConfig::from(&config);
+        assert_eq!(stream_config.channels, 1);
+        assert_eq!(stream_config.sample_rate, 48_000);
+        assert_eq!(stream_config.buffer_size, BufferSize::Fixed(256));
+    }
+
+    #[test]
+    fn null_sink_plays_in_real_time() {
+        let sink = MixerDeviceSink::null(nz!(2), nz!(1000));
+        let player = Player::connect_new(sink.mixer());
+        player.append(crate::buffer::SamplesBuffer::new(
+            nz!(2),
+            nz!(1000),
+            vec![0.5; 100],
+        ));
+
+        let start = Instant::now();
+        player.sleep_until_end();
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(40),
+            "played too fast: {elapsed:?}"
+        );
+        assert!(
+            elapsed < Duration::from_secs(2),
+            "played too slow: {elapsed:?}"
+        );
+        assert!(sink.frames_played() >= 50);
+    }
+
+    #[test]
+    fn paused_sink_stops_pulling() {
+        let sink = MixerDeviceSink::null(nz!(1), nz!(1000));
+        sink.pause().unwrap();
+        // Let the output thread finish the period it may be in.
+        thread::sleep(NULL_OUTPUT_PERIOD * 3);
+
+        let frames = sink.frames_played();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(sink.frames_played(), frames);
+
+        sink.play().unwrap();
+        thread::sleep(Duration::from_millis(50));
+        assert!(sink.frames_played() > frames);
+    }
+
+    #[test]
+    fn underrun_fill() {
+        let config = |underrun_fill| DeviceSinkConfig {
+            channel_count: nz!(2),
+            sample_rate: nz!(1000),
+            underrun_fill,
+            ..DeviceSinkConfig::default()
+        };
+        let played = [Some(0.5), Some(-0.5), None, None];
+
+        let mut silence = UnderrunFiller::new(&config(UnderrunFill::Silence));
+        let output: Vec<_> = played.iter().map(|s| silence.fill(*s)).collect();
+        assert_eq!(output, [0.5, -0.5, 0.0, 0.0]);
+
+        let mut hold = UnderrunFiller::new(&config(UnderrunFill::HoldLast));
+        let output: Vec<_> = played.iter().map(|s| hold.fill(*s)).collect();
+        assert_eq!(output, [0.5, -0.5, 0.5, -0.5]);
+
+        // 5ms at 1000Hz fades out over 5 frames.
+        let mut fade = UnderrunFiller::new(&config(UnderrunFill::FadeToSilence));
+        let mut output: Vec<_> = played.iter().map(|s| fade.fill(*s)).collect();
+        output.extend((0..10).map(|_| fade.fill(None)));
+        assert!((output[2] - 0.4).abs() < 1e-6 && (output[3] + 0.4).abs() < 1e-6);
+        assert!(output[4..].windows(4).all(|w| w[2].abs() <= w[0].abs()));
+        assert_eq!(output[10..], [0.0; 4]);
+
+        // Playback resumes at full volume.
+        assert_eq!(fade.fill(Some(0.25)), 0.25);
+    }
+
+    #[test]
+    fn clip_modes() {
+        // Two sounds that add up to more than full scale.
+        let (mixer, source) = crate::mixer::mixer(nz!(1), nz!(1000));
+        for _ in 0..2 {
+            let loud = vec![0.25, 0.75, -0.75];
+            mixer.add(crate::buffer::SamplesBuffer::new(nz!(1), nz!(1000), loud));
+        }
+        let mixed: Vec<_> = source.take(3).collect();
+        assert_eq!(mixed, [0.5, 1.5, -1.5]);
+
+        let clip = |mode: ClipMode| {
+            let mut samples = mixed.clone();
+            mode.apply(&mut samples);
+            samples
+        };
+        assert_eq!(clip(ClipMode::Hard), [0.5, 1.0, -1.0]);
+        assert_eq!(clip(ClipMode::Wrap), [0.5, -0.5, 0.5]);
+        let soft = clip(ClipMode::SoftTanh);
+        assert!(soft[1] < 1.0 && soft[1] > 0.9 && soft[2] == -soft[1]);
+        assert!(soft[0] < 0.5 && soft[0] > 0.45);
+    }
+
+    #[test]
+    fn latency_hint_buffer_sizes() {
+        let range = SupportedBufferSize::Range { min: 64, max: 4096 };
+        let size = |hint: LatencyHint| hint.buffer_size(nz!(48_000), &range);
+        assert_eq!(size(LatencyHint::Interactive), 512);
+        assert_eq!(size(LatencyHint::Balanced), 2048);
+        assert_eq!(size(LatencyHint::PowerSaving), 4096);
+
+        // Targets outside of a narrow range are clamped to its ends.
+        let narrow = SupportedBufferSize::Range {
+            min: 1024,
+            max: 1536,
+        };
+        assert_eq!(
+            LatencyHint::Interactive.buffer_size(nz!(48_000), &narrow),
+            1024
+        );
+        assert_eq!(
+            LatencyHint::PowerSaving.buffer_size(nz!(48_000), &narrow),
+            1536
+        );
+
+        let unknown = SupportedBufferSize::Unknown;
+        assert_eq!(
+            LatencyHint::PowerSaving.buffer_size(nz!(48_000), &unknown),
+            8192
+        );
+    }
+
+    #[test]
+    fn explicit_buffer_size_wins_over_latency_hint() {
+        let builder = DeviceSinkBuilder::default()
+            .with_latency_hint(LatencyHint::Interactive)
+            .with_buffer_size(BufferSize::Fixed(256));
+        assert!(builder.explicit_buffer_size);
+        let builder = DeviceSinkBuilder::default()
+            .with_buffer_size(BufferSize::Fixed(256))
+            .with_latency_hint(LatencyHint::Interactive);
+        assert!(builder.explicit_buffer_size);
+    }
+
+    #[test]
+    fn device_name() {
+        assert_eq!(MixerDeviceSink::null(nz!(1), nz!(1000)).device_name(), None);
+
+        // Needs an output device, which is not available on every CI machine.
+        let Ok(mut sink) = DeviceSinkBuilder::open_default_sink() else {
+            return;
+        };
+        sink.log_on_drop(false);
+        if let Some(name) = sink.device_name() {
+            assert!(!name.is_empty());
+        }
+    }
+
+    #[test]
+    fn exclusive_mode_is_not_silently_ignored() {
+        // Needs an output device, which is not available on every CI machine.
+        let Ok(builder) = DeviceSinkBuilder::from_default_device() else {
+            return;
+        };
+        let err = builder.with_exclusive(true).open_stream().unwrap_err();
+        assert!(matches!(err, DeviceSinkError::ExclusiveModeNotSupported));
+    }
+
+    #[test]
+    fn supported_configs_are_fallback_order() {
+        // Needs an output device, which is not available on every CI machine.
+        let Ok(builder) = DeviceSinkBuilder::from_default_device() else {
+            return;
+        };
+        let builder = builder.with_fallback_policy(FallbackPolicy {
+            config: ConfigFallback::PreferDeviceDefault,
+            ..FallbackPolicy::default()
+        });
+        let Ok(configs) = builder.supported_configs() else {
+            return;
+        };
+        if let Ok(default) = builder.device.as_ref().unwrap().default_output_config() {
+            assert_eq!(configs.first(), Some(&default));
+        }
+    }
+
+    #[test]
+    fn available_hosts_include_default() {
+        let default_id = cpal::default_host().id();
+        assert!(available_hosts().any(|host| host.id() == default_id));
+    }
+
+    #[test]
+    fn master_volume_reaches_mixer() {
+        let sink = MixerDeviceSink::null(nz!(2), nz!(1000));
+        assert_eq!(sink.master_volume(), 1.0);
+        sink.set_master_volume(0.5);
+        assert_eq!(sink.master_volume(), 0.5);
+        assert_eq!(sink.mixer().master_volume(), 0.5);
+    }
+
+    /// Counts what the reconnect thread does, reconnecting after `fail_attempts` failures.
+    #[derive(Default)]
+    struct FakeStream {
+        auto_reconnect: AtomicBool,
+        fail_attempts: usize,
+        notified: AtomicU64,
+        attempts: AtomicU64,
+    }
+
+    impl Reconnect for FakeStream {
+        fn notify_disconnect(&self) {
+            self.notified.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn auto_reconnect(&self) -> bool {
+            self.auto_reconnect.load(Ordering::Relaxed)
+        }
+
+        fn try_reconnect(self: &Arc<Self>) -> bool {
+            let attempt = self.attempts.fetch_add(1, Ordering::Relaxed) as usize;
+            attempt >= self.fail_attempts
+        }
+    }
+
+    #[test]
+    fn disconnect_without_auto_reconnect_stops_after_notifying() {
+        let state = Arc::new(FakeStream::default());
+        spawn_reconnect(Arc::downgrade(&state), true)
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(state.notified.load(Ordering::Relaxed), 1);
+        assert_eq!(state.attempts.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn auto_reconnect_retries_until_reconnected() {
+        let state = Arc::new(FakeStream {
+            auto_reconnect: AtomicBool::new(true),
+            fail_attempts: 1,
+            ..FakeStream::default()
+        });
+        spawn_reconnect(Arc::downgrade(&state), true)
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(state.notified.load(Ordering::Relaxed), 1);
+        assert_eq!(state.attempts.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn enabling_auto_reconnect_later_does_not_notify_again() {
+        let state = Arc::new(FakeStream {
+            auto_reconnect: AtomicBool::new(true),
+            ..FakeStream::default()
+        });
+        spawn_reconnect(Arc::downgrade(&state), false)
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(state.notified.load(Ordering::Relaxed), 0);
+        assert_eq!(state.attempts.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn reconnect_stops_once_sink_is_dropped() {
+        let state = Arc::new(FakeStream {
+            auto_reconnect: AtomicBool::new(true),
+            fail_attempts: usize::MAX,
+            ..FakeStream::default()
+        });
+        let handle = spawn_reconnect(Arc::downgrade(&state), true).unwrap();
+        drop(state);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn dropped_stream_hands_back_mixer_source() {
+        let (controller, source) = mixer(nz!(1), nz!(1000));
+        controller.add(crate::buffer::SamplesBuffer::new(
+            nz!(1),
+            nz!(1000),
+            vec![0.25, 0.5, 0.75],
+        ));
+        let (return_source, returned_source) = channel();
+        let mut slot = SourceSlot {
+            source: Some(source),
+            return_source,
+        };
+        assert_eq!(slot.source.as_mut().unwrap().next(), Some(0.25));
+        drop(slot);
+
+        // The next stream continues where the lost one stopped.
+        let mut source = returned_source.try_recv().unwrap();
+        assert_eq!(source.next(), Some(0.5));
+        assert_eq!(source.next(), Some(0.75));
+    }
+}
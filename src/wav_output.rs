@@ -95,14 +95,15 @@ pub fn wav_to_writer(
     Ok(())
 }
 
-struct WholeFrames<I: Iterator<Item = Sample>> {
+/// Drops a trailing partial frame so the channels in the written file line up.
+pub(crate) struct WholeFrames<I: Iterator<Item = Sample>> {
     buffer: Vec<Sample>,
     pos: usize,
     source: I,
 }
 
 impl<S: Source> WholeFrames<S> {
-    fn new(source: S) -> Self {
+    pub(crate) fn new(source: S) -> Self {
         Self {
             buffer: vec![0.0; source.channels().get().into()],
             pos: source.channels().get().into(),
@@ -22,3 +22,134 @@ fn test_flac_encodings() {
         assert_eq!(decoder.total_duration(), Some(Duration::from_secs(3)));
     }
 }
+
+/// Replaces the Vorbis comment block of a FLAC file with one holding the given comments.
+#[cfg(feature = "symphonia-flac")]
+fn with_vorbis_comments(flac: &[u8], comments: &[&str]) -> Vec<u8> {
+    const VORBIS_COMMENT: u8 = 4;
+    const LAST_BLOCK: u8 = 0x80;
+
+    let mut block = Vec::new();
+    let vendor = b"rodio";
+    block.extend((vendor.len() as u32).to_le_bytes());
+    block.extend(vendor);
+    block.extend((comments.len() as u32).to_le_bytes());
+    for comment in comments {
+        block.extend((comment.len() as u32).to_le_bytes());
+        block.extend(comment.as_bytes());
+    }
+
+    // Keep the blocks in front of the comments, and the audio after the last block.
+    let mut output = flac[..4].to_vec();
+    let mut pos = 4;
+    loop {
+        let header = flac[pos];
+        let len = u32::from_be_bytes([0, flac[pos + 1], flac[pos + 2], flac[pos + 3]]) as usize;
+        let end = pos + 4 + len;
+        if header & !LAST_BLOCK != VORBIS_COMMENT {
+            output.push(header & !LAST_BLOCK);
+            output.extend(&flac[pos + 1..end]);
+        }
+        pos = end;
+        if header & LAST_BLOCK != 0 {
+            break;
+        }
+    }
+    output.push(VORBIS_COMMENT | LAST_BLOCK);
+    output.extend(&(block.len() as u32).to_be_bytes()[1..]);
+    output.extend(block);
+    output.extend(&flac[pos..]);
+    output
+}
+
+#[cfg(feature = "symphonia-flac")]
+#[test]
+fn test_flac_replay_gain() {
+    use rodio::Decoder;
+    use std::io::Cursor;
+
+    let flac = std::fs::read("assets/audacity16bit_level5.flac").unwrap();
+    let decoder = Decoder::try_from(Cursor::new(flac.clone())).unwrap();
+    assert_eq!(decoder.replay_gain(), None);
+    assert_eq!(decoder.replay_gain_peak(), None);
+
+    let tagged = with_vorbis_comments(
+        &flac,
+        &[
+            "REPLAYGAIN_TRACK_GAIN=-6.02 dB",
+            "REPLAYGAIN_TRACK_PEAK=0.250000",
+        ],
+    );
+    let decoder = Decoder::try_from(Cursor::new(tagged)).unwrap();
+    assert_eq!(decoder.replay_gain(), Some(-6.02));
+    assert_eq!(decoder.replay_gain_peak(), Some(0.25));
+
+    // The peak is far below full scale, so clipping does not limit the gain.
+    let reference: Vec<_> = Decoder::try_from(Cursor::new(flac)).unwrap().collect();
+    let gain = decoder.replay_gain().unwrap();
+    let peak = decoder.replay_gain_peak();
+    let adjusted: Vec<_> = decoder.apply_replay_gain(gain, peak).collect();
+    assert_eq!(adjusted.len(), reference.len());
+    for (adjusted, reference) in adjusted.iter().zip(&reference) {
+        assert!((adjusted - reference * 0.5).abs() < 1e-3);
+    }
+}
+
+#[cfg(feature = "symphonia-flac")]
+#[test]
+fn test_flac_chapters() {
+    use rodio::decoder::Chapter;
+    use rodio::Decoder;
+    use std::io::Cursor;
+
+    let flac = std::fs::read("assets/audacity16bit_level5.flac").unwrap();
+    let decoder = Decoder::try_from(Cursor::new(flac.clone())).unwrap();
+    assert!(decoder.chapters().is_empty());
+
+    let tagged = with_vorbis_comments(
+        &flac,
+        &[
+            "CHAPTER001=00:00:00.000",
+            "CHAPTER001NAME=Opening",
+            "CHAPTER002=00:00:01.500",
+            "CHAPTER002NAME=Second part",
+        ],
+    );
+    let mut decoder = Decoder::try_from(Cursor::new(tagged)).unwrap();
+    let chapters = decoder.chapters();
+    assert_eq!(
+        chapters,
+        [
+            Chapter {
+                start: Duration::ZERO,
+                title: "Opening".to_owned()
+            },
+            Chapter {
+                start: Duration::from_millis(1500),
+                title: "Second part".to_owned()
+            },
+        ]
+    );
+    decoder.try_seek(chapters[1].start).unwrap();
+    assert_eq!(decoder.get_pos(), Duration::from_millis(1500));
+}
+
+#[cfg(feature = "symphonia-flac")]
+#[test]
+fn test_flac_from_symphonia_source() {
+    use std::io::Cursor;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::probe::Hint;
+
+    let flac = std::fs::read("assets/music.flac").unwrap();
+    let mss = MediaSourceStream::new(Box::new(Cursor::new(flac)), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension("flac");
+
+    let mut decoder = rodio::Decoder::from_symphonia_source(mss, hint).unwrap();
+    assert_eq!(decoder.codec_name(), "flac");
+    assert!(decoder.total_duration().is_some());
+    assert!(decoder.is_seekable());
+    decoder.try_seek(Duration::from_secs(1)).unwrap();
+    assert!(decoder.any(|sample| sample != 0.0));
+}
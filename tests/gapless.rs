@@ -0,0 +1,40 @@
+#[cfg(feature = "symphonia-mp3")]
+#[test]
+fn mp3_priming_is_trimmed() {
+    use rodio::decoder::GaplessInfo;
+    use rodio::{Decoder, Source};
+
+    // Encoded by ffmpeg, which writes the delay and padding into a LAME tag.
+    let decode = |gapless| {
+        let file = std::fs::File::open("assets/RL.mp3").unwrap();
+        let len = file.metadata().unwrap().len();
+        Decoder::builder()
+            .with_data(file)
+            .with_byte_len(len)
+            .with_gapless(gapless)
+            .build()
+            .unwrap()
+    };
+
+    let gapless = decode(true);
+    let expected = GaplessInfo {
+        delay: 1105,
+        padding: 239,
+    };
+    assert_eq!(gapless.gapless_info(), Some(expected));
+    let channels = gapless.channels().get() as usize;
+    let gapless: Vec<_> = gapless.collect();
+
+    let untrimmed = decode(false);
+    assert_eq!(untrimmed.gapless_info(), Some(expected));
+    let untrimmed: Vec<_> = untrimmed.collect();
+
+    let priming = expected.delay as usize * channels;
+    let padding = expected.padding as usize * channels;
+    assert_eq!(untrimmed.len(), gapless.len() + priming + padding);
+    assert_eq!(
+        untrimmed[priming..priming + 1000],
+        gapless[..1000],
+        "the audio starts right after the priming frames"
+    );
+}
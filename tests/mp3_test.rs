@@ -0,0 +1,79 @@
+#[cfg(feature = "symphonia-mp3")]
+#[test]
+fn test_mp3_codec_info() {
+    let file = std::fs::File::open("assets/music.mp3").unwrap();
+    let decoder = rodio::Decoder::try_from(file).unwrap();
+
+    assert_eq!(decoder.codec_name(), "mp3");
+    // Estimated from the file length, so only roughly the encoded bitrate.
+    let bitrate = decoder.bitrate().unwrap();
+    assert!((100_000..400_000).contains(&bitrate), "bitrate {bitrate}");
+}
+
+/// Replaces the ID3v2 tag in front of an MP3 file with an ID3v2.4 tag holding one chapter
+/// per `(start in ms, title)`.
+#[cfg(feature = "symphonia-mp3")]
+fn with_id3_chapters(mp3: &[u8], chapters: &[(u32, &str)]) -> Vec<u8> {
+    let syncsafe = |len: usize| [21, 14, 7, 0].map(|shift| (len >> shift) as u8 & 0x7f);
+    let frame = |id: &[u8], body: &[u8]| {
+        let mut frame = id.to_vec();
+        frame.extend(syncsafe(body.len()));
+        frame.extend([0, 0]);
+        frame.extend(body);
+        frame
+    };
+
+    let mut frames = Vec::new();
+    for (i, (start_ms, title)) in chapters.iter().enumerate() {
+        let mut body = format!("chp{i}\0").into_bytes();
+        body.extend(start_ms.to_be_bytes());
+        body.extend([0xff; 12]);
+        let mut text = vec![3];
+        text.extend(title.as_bytes());
+        body.extend(frame(b"TIT2", &text));
+        frames.extend(frame(b"CHAP", &body));
+    }
+
+    assert_eq!(&mp3[..3], b"ID3");
+    let old_len = 10
+        + mp3[6..10]
+            .iter()
+            .fold(0, |len, byte| (len << 7) | usize::from(*byte));
+    let mut output = b"ID3\x04\x00\x00".to_vec();
+    output.extend(syncsafe(frames.len()));
+    output.extend(frames);
+    output.extend(&mp3[old_len..]);
+    output
+}
+
+#[cfg(feature = "symphonia-mp3")]
+#[test]
+fn test_mp3_chapters() {
+    use rodio::decoder::Chapter;
+    use rodio::Decoder;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    let mp3 = std::fs::read("assets/music.mp3").unwrap();
+    assert!(Decoder::try_from(Cursor::new(mp3.clone()))
+        .unwrap()
+        .chapters()
+        .is_empty());
+
+    let tagged = with_id3_chapters(&mp3, &[(0, "Intro"), (2250, "Theme")]);
+    let decoder = Decoder::try_from(Cursor::new(tagged)).unwrap();
+    assert_eq!(
+        decoder.chapters(),
+        [
+            Chapter {
+                start: Duration::ZERO,
+                title: "Intro".to_owned()
+            },
+            Chapter {
+                start: Duration::from_millis(2250),
+                title: "Theme".to_owned()
+            },
+        ]
+    );
+    assert!(decoder.count() > 0);
+}
@@ -106,6 +106,31 @@ fn seek_returns_err_if_unsupported(
     assert_eq!(res.is_ok(), supports_seek, "decoder: {decoder_name}");
 }
 
+#[cfg(any(
+    feature = "claxon",
+    feature = "minimp3",
+    feature = "symphonia-flac",
+    feature = "symphonia-mp3",
+    feature = "symphonia-isomp4",
+    feature = "symphonia-ogg",
+    feature = "symphonia-wav",
+    feature = "hound",
+))]
+#[apply(all_decoders)]
+#[trace]
+fn is_seekable_matches_try_seek(
+    #[case] format: &'static str,
+    #[case] supports_seek: bool,
+    #[case] decoder_name: &'static str,
+) {
+    let decoder = get_music(format);
+    assert_eq!(
+        decoder.is_seekable(),
+        supports_seek,
+        "decoder: {decoder_name}"
+    );
+}
+
 #[cfg(any(
     feature = "symphonia-flac",
     feature = "symphonia-mp3",
@@ -279,6 +304,27 @@ fn random_access_seeks() {
     );
 }
 
+#[cfg(any(feature = "symphonia-wav", feature = "symphonia-flac"))]
+#[rstest]
+#[cfg_attr(feature = "symphonia-wav", case("wav"))]
+#[cfg_attr(feature = "symphonia-flac", case("flac"))]
+fn seek_to_exact_frame(#[case] format: &'static str) {
+    let samples: Vec<Sample> = get_music(format).collect();
+    let mut source = get_music(format);
+    let channels = source.channels().get() as usize;
+
+    for frame in [44_101, 12_345, 3] {
+        source.try_seek_frame(frame).unwrap();
+        let start = frame as usize * channels;
+        let played: Vec<Sample> = source.by_ref().take(channels * 4).collect();
+        assert_eq!(
+            played,
+            samples[start..start + channels * 4],
+            "frame {frame}"
+        );
+    }
+}
+
 fn second_channel_beep_range<R: rodio::Source>(source: &mut R) -> std::ops::Range<usize> {
     let channels = source.channels().get() as usize;
     let samples: Vec<Sample> = source.by_ref().collect();
@@ -31,3 +31,160 @@ fn test_wav_encodings() {
     let mut decoder = rodio::Decoder::try_from(file).unwrap();
     assert!(decoder.any(|x| x != 0.0));
 }
+
+#[cfg(any(feature = "hound", feature = "wav"))]
+#[test]
+fn test_wav_position() {
+    use rodio::Source;
+    use std::time::Duration;
+
+    let file = std::fs::File::open("assets/lmms16bit.wav").unwrap();
+    let mut decoder = rodio::Decoder::try_from(file).unwrap();
+    assert_eq!(decoder.get_pos(), Duration::ZERO);
+
+    let samples_per_second =
+        decoder.channels().get() as usize * decoder.sample_rate().get() as usize;
+    decoder.by_ref().take(samples_per_second / 2).for_each(drop);
+    assert_eq!(decoder.get_pos(), Duration::from_millis(500));
+
+    // Seeking converts through floating point, so allow an error of less than a frame.
+    decoder.try_seek(Duration::from_millis(100)).unwrap();
+    let error = decoder.get_pos().abs_diff(Duration::from_millis(100));
+    assert!(error < Duration::from_secs(1) / decoder.sample_rate().get());
+}
+
+#[cfg(all(feature = "wav_output", any(feature = "hound", feature = "wav")))]
+#[test]
+fn test_wav_max_channels() {
+    use rodio::buffer::SamplesBuffer;
+    use rodio::math::{nz, FRAC_1_SQRT_2};
+    use rodio::Source;
+
+    // One frame of 5.1: left, right, center, LFE, surround left, surround right.
+    let frame = [0.1, 0.2, 0.3, 0.9, 0.4, 0.5];
+    let samples: Vec<_> = frame.iter().copied().cycle().take(6 * 100).collect();
+    let mut wav = std::io::Cursor::new(Vec::new());
+    rodio::wav_to_writer(SamplesBuffer::new(nz!(6), nz!(44100), samples), &mut wav).unwrap();
+    wav.set_position(0);
+
+    let decoder = rodio::Decoder::try_from(wav.clone()).unwrap();
+    assert_eq!(decoder.channels(), nz!(6));
+
+    let decoder = rodio::Decoder::try_from(wav)
+        .unwrap()
+        .with_max_channels(nz!(2));
+    assert_eq!(decoder.channels(), nz!(2));
+    let output: Vec<_> = decoder.collect();
+    assert_eq!(output.len(), 2 * 100);
+
+    let total = 1.0 + 2.0 * FRAC_1_SQRT_2;
+    let left = (0.1 + 0.3 * FRAC_1_SQRT_2 + 0.4 * FRAC_1_SQRT_2) / total;
+    let right = (0.2 + 0.3 * FRAC_1_SQRT_2 + 0.5 * FRAC_1_SQRT_2) / total;
+    for frame in output.chunks(2) {
+        assert!((frame[0] - left).abs() < 1e-6);
+        assert!((frame[1] - right).abs() < 1e-6);
+    }
+}
+
+#[cfg(all(feature = "mmap", any(feature = "hound", feature = "wav")))]
+#[test]
+fn test_wav_mmap() {
+    use rodio::Source;
+    use std::time::Duration;
+
+    let file = std::fs::File::open("assets/lmms16bit.wav").unwrap();
+    let expected: Vec<_> = rodio::Decoder::try_from(file).unwrap().collect();
+
+    let mut decoder = rodio::Decoder::new_mmap("assets/lmms16bit.wav").unwrap();
+    assert!(decoder.total_duration().is_some());
+    let samples: Vec<_> = decoder.by_ref().collect();
+    assert_eq!(samples, expected);
+
+    decoder.try_seek(Duration::ZERO).unwrap();
+    assert_eq!(decoder.next(), expected.first().copied());
+}
+
+#[cfg(any(feature = "hound", feature = "wav"))]
+#[test]
+fn test_wav_shared_buffer() {
+    use rodio::Source;
+    use std::time::Duration;
+
+    let file = std::fs::File::open("assets/lmms16bit.wav").unwrap();
+    let expected: Vec<_> = rodio::Decoder::try_from(file).unwrap().collect();
+
+    let file = std::fs::File::open("assets/lmms16bit.wav").unwrap();
+    let shared = rodio::Decoder::try_from(file).unwrap().into_shared_buffer();
+    let mut first = shared.source();
+    let mut second = shared.source();
+
+    // Both sources play at the same time, each from its own position.
+    let start: Vec<_> = first.by_ref().take(1000).collect();
+    assert_eq!(start, expected[..1000]);
+    second.try_seek(Duration::from_millis(500)).unwrap();
+    let offset = shared.sample_rate().get() as usize / 2 * shared.channels().get() as usize;
+    assert_eq!(second.next(), expected.get(offset).copied());
+    assert_eq!(first.next(), expected.get(1000).copied());
+
+    // New sources start at the beginning.
+    assert_eq!(shared.source().collect::<Vec<_>>(), expected);
+}
+
+#[cfg(feature = "symphonia-wav")]
+#[test]
+fn test_wav_codec_info() {
+    let file = std::fs::File::open("assets/music.wav").unwrap();
+    let decoder = rodio::Decoder::try_from(file).unwrap();
+
+    assert_eq!(decoder.codec_name(), "pcm_s16le");
+    assert_eq!(decoder.bitrate(), Some(16 * 44_100 * 2));
+}
+
+#[cfg(feature = "symphonia-wav")]
+#[test]
+fn test_wav_loop_points() {
+    use std::io::Cursor;
+
+    let chunk = |id: &[u8], body: &[u8]| {
+        let mut chunk = id.to_vec();
+        chunk.extend((body.len() as u32).to_le_bytes());
+        chunk.extend(body);
+        chunk
+    };
+    // 16 bit mono PCM at 8 kHz.
+    let mut fmt = Vec::new();
+    for field in [1u16, 1] {
+        fmt.extend(field.to_le_bytes());
+    }
+    fmt.extend(8000u32.to_le_bytes());
+    fmt.extend(16_000u32.to_le_bytes());
+    for field in [2u16, 16] {
+        fmt.extend(field.to_le_bytes());
+    }
+    // One loop from frame 100 up to and including frame 299.
+    let mut smpl = vec![0; 28];
+    for field in [1u32, 0, 0, 0, 100, 299, 0, 0] {
+        smpl.extend(field.to_le_bytes());
+    }
+    let data: Vec<u8> = (0..400i16).flat_map(|i| (i * 50).to_le_bytes()).collect();
+
+    let with_loop = |smpl: Option<&[u8]>| {
+        let mut chunks = chunk(b"fmt ", &fmt);
+        chunks.extend(chunk(b"data", &data));
+        if let Some(smpl) = smpl {
+            chunks.extend(chunk(b"smpl", smpl));
+        }
+        let mut wav = b"RIFF".to_vec();
+        wav.extend((chunks.len() as u32 + 4).to_le_bytes());
+        wav.extend(b"WAVE");
+        wav.extend(chunks);
+        wav
+    };
+
+    let decoder = rodio::Decoder::try_from(Cursor::new(with_loop(Some(&smpl)))).unwrap();
+    assert_eq!(decoder.loop_points(), Some((100, 300)));
+    assert_eq!(decoder.count(), 400);
+
+    let decoder = rodio::Decoder::try_from(Cursor::new(with_loop(None))).unwrap();
+    assert_eq!(decoder.loop_points(), None);
+}